@@ -1,4 +1,10 @@
-use std::sync::Arc;
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+    time::{SystemTime, UNIX_EPOCH},
+};
 
 use winit::{
     application::ApplicationHandler,
@@ -9,7 +15,7 @@ use winit::{
 };
 
 use crate::{
-    assets, commands, events, game, handle, input,
+    assets, commands, diagnostics, events, game, handle, input,
     rendering::{backend, renderer},
     workers,
 };
@@ -20,13 +26,17 @@ enum AppEvent {
 }
 
 struct State {
+    backend: Arc<backend::Backend>,
     commands: Arc<commands::Commands>,
+    events: events::Sender,
     input: Arc<input::Input>,
     window: Arc<Window>,
 
     _game: Arc<game::Game>,
-    _schemes: [handle::Handle; 1],
-    _workers: [handle::Handle; 1],
+    _commands: [handle::Handle; 6],
+    _handlers: [handle::Handle; 1],
+    _schemes: [handle::Handle; 2],
+    _workers: [handle::Handle; 2],
 }
 
 impl State {
@@ -35,14 +45,21 @@ impl State {
         events: Arc<events::Events>,
         commands: Arc<commands::Commands>,
         input: Arc<input::Input>,
+        present_mode: backend::PresentMode,
         event_loop: &ActiveEventLoop,
     ) -> State {
         let window = State::init_window(event_loop);
 
-        let backend = backend::Backend::new(event_loop, window.clone());
+        let backend = backend::Backend::new(event_loop, window.clone(), present_mode);
         let assets = assets::Assets::new(backend.clone());
         let renderer = renderer::Renderer::new(backend.clone());
 
+        const INPUT_SCHEME_PATH: &str = "input.json";
+        // NOTE: user scheme overrides bindings of default scheme, but not of contexts
+        const USER_SCHEME_PRIORITY: input::SchemePriority = 1;
+
+        let paused: Arc<AtomicBool> = Default::default();
+
         let inner = State {
             _game: game::Game::new(
                 workers,
@@ -53,21 +70,110 @@ impl State {
                 renderer.clone(),
             ),
 
-            _schemes: [input.add_scheme(
-                input::Scheme::default()
-                    .add("camera_follow", [input::Key::KbdF])
-                    .add("camera_zoom_out", [input::Key::KbdQ])
-                    .add("camera_zoom_in", [input::Key::KbdE])
-                    .add("player_forward", [input::Key::KbdW])
-                    .add("player_backward", [input::Key::KbdS])
-                    .add("player_incline_left", [input::Key::KbdA])
-                    .add("player_incline_right", [input::Key::KbdD])
-                    .add("player_weapon_fire", [input::Key::KbdSpace]),
-            )],
+            _commands: [
+                commands.add(
+                    "dump_render_graphs",
+                    commands::StatefulCommand::new(renderer.clone(), |_, renderer| {
+                        println!("{}", renderer.describe_graphs());
+
+                        true
+                    }),
+                ),
+                commands.add(
+                    "dump_input_scheme",
+                    commands::StatefulCommand::new((), |_, _| {
+                        let scheme = State::default_scheme().to_serializable();
+
+                        match serde_json::to_string_pretty(&scheme) {
+                            Ok(scheme) => println!("{}", scheme),
+                            Err(error) => diagnostics::warning(error),
+                        }
+
+                        true
+                    }),
+                ),
+                commands.add(
+                    "dump_worker_stats",
+                    commands::StatefulCommand::new(workers.stats_reader(), |_, stats| {
+                        for (name, stats) in stats.stats().unwrap_or_default() {
+                            println!(
+                                "{}: {:.1} updates per second (last {:.3} ms, average {:.3} ms)",
+                                name,
+                                stats.rate(),
+                                1000.0 * stats.last_elapsed,
+                                1000.0 * stats.average_elapsed
+                            );
+                        }
+
+                        true
+                    }),
+                ),
+                commands.add(
+                    "pause",
+                    commands::StatefulCommand::new(
+                        (input.clone(), paused.clone()),
+                        |_, (input, paused)| {
+                            if !paused.swap(true, Ordering::Relaxed) {
+                                input.push_context(State::pause_scheme());
+                            }
+
+                            true
+                        },
+                    ),
+                ),
+                commands.add(
+                    "resume",
+                    commands::StatefulCommand::new(
+                        (input.clone(), paused),
+                        |_, (input, paused)| {
+                            if paused.swap(false, Ordering::Relaxed) {
+                                input.pop_context();
+                            }
+
+                            true
+                        },
+                    ),
+                ),
+                commands.add(
+                    "screenshot",
+                    commands::StatefulCommand::new(renderer.clone(), |_, renderer| {
+                        let timestamp = SystemTime::now()
+                            .duration_since(UNIX_EPOCH)
+                            .unwrap_or_default()
+                            .as_millis();
+
+                        renderer.request_screenshot(format!("screenshot_{}.png", timestamp));
+
+                        true
+                    }),
+                ),
+            ],
 
-            _workers: [renderer::spawn_worker(workers, renderer.clone())],
+            _handlers: [events.add_handler({
+                let backend = backend.clone();
+
+                move |event| {
+                    if let events::Event::WindowResized(extent) = event {
+                        backend.resize_swapchain(*extent);
+                    }
+                }
+            })],
+
+            _schemes: [
+                input.add_scheme(State::default_scheme()),
+                input.add_scheme(
+                    input::Scheme::load(INPUT_SCHEME_PATH).with_priority(USER_SCHEME_PRIORITY),
+                ),
+            ],
+
+            _workers: [
+                renderer::spawn_worker(workers, renderer.clone()),
+                assets::spawn_worker(workers, assets.clone()),
+            ],
 
+            backend,
             commands,
+            events: events.get_sender(),
             input,
             window,
         };
@@ -75,6 +181,79 @@ impl State {
         inner
     }
 
+    fn default_scheme() -> input::Scheme {
+        input::Scheme::default()
+            .add("camera_follow", [input::Key::KbdF])
+            .add_with_mode(
+                "camera_detach",
+                input::TriggerMode::OnPress,
+                [input::Key::KbdC],
+            )
+            .add("camera_pan_up", [input::Key::KbdArrowUp])
+            .add("camera_pan_down", [input::Key::KbdArrowDown])
+            .add("camera_pan_left", [input::Key::KbdArrowLeft])
+            .add("camera_pan_right", [input::Key::KbdArrowRight])
+            .add("camera_zoom_out", [input::Key::KbdQ])
+            .add("camera_zoom_in", [input::Key::KbdE])
+            .add_axes("camera_zoom", [input::Axis::MouseWheel])
+            .add("player_forward", [input::Key::KbdW])
+            .add("player_backward", [input::Key::KbdS])
+            .add("player_incline_left", [input::Key::KbdA])
+            .add("player_incline_right", [input::Key::KbdD])
+            .add("player_weapon_fire", [input::Key::KbdSpace])
+            .add_with_mode(
+                "player_switch_weapon",
+                input::TriggerMode::OnPress,
+                [input::Key::KbdX],
+            )
+            .add_with_mode("pause", input::TriggerMode::OnPress, [input::Key::KbdP])
+            .add_with_mode("restart", input::TriggerMode::OnPress, [input::Key::KbdR])
+            .add_with_mode(
+                "resume",
+                input::TriggerMode::OnPress,
+                [input::Key::KbdEnter],
+            )
+            .add_with_mode(
+                "dump_render_graphs",
+                input::TriggerMode::OnPress,
+                [input::Key::KbdG],
+            )
+            .add_with_mode(
+                "dump_input_scheme",
+                input::TriggerMode::OnPress,
+                [input::Key::KbdJ],
+            )
+            .add_with_mode(
+                "dump_worker_stats",
+                input::TriggerMode::OnPress,
+                [input::Key::KbdT],
+            )
+            .add_with_mode(
+                "dump_systems",
+                input::TriggerMode::OnPress,
+                [input::Key::KbdH],
+            )
+            .add_with_mode(
+                "screenshot",
+                input::TriggerMode::OnPress,
+                [input::Key::KbdK],
+            )
+            .add_with_mode(
+                "toggle_metrics_export",
+                input::TriggerMode::OnPress,
+                [input::Key::KbdM],
+            )
+    }
+
+    /// Scheme of input context, which is active while game is paused: pause key resumes game
+    fn pause_scheme() -> input::Scheme {
+        input::Scheme::default().add_with_mode(
+            "resume",
+            input::TriggerMode::OnPress,
+            [input::Key::KbdP, input::Key::KbdEnter],
+        )
+    }
+
     fn init_window(event_loop: &ActiveEventLoop) -> Arc<Window> {
         let attributes = WindowAttributes::default()
             .with_title("Asteroids")
@@ -87,12 +266,25 @@ impl State {
         Arc::new(window)
     }
 
+    /// Shuts state down: game workers are stopped first, as they produce events and scene data, then renderer
+    /// and assets workers, and finally GPU is awaited before remaining resources are dropped
+    fn shutdown(self) {
+        drop(self._game);
+        drop(self._workers);
+
+        self.backend.wait_idle();
+    }
+
     fn dispatch_window_event(&mut self, _: &ActiveEventLoop, event: WindowEvent) {
         match event {
             WindowEvent::RedrawRequested => {
                 self.window.request_redraw();
             }
 
+            WindowEvent::Resized(size) => {
+                self.events.send(events::Event::WindowResized(size.into()));
+            }
+
             WindowEvent::CloseRequested => {
                 self.commands.invoke("exit", &[]);
             }
@@ -101,6 +293,14 @@ impl State {
                 self.input.dispatch_key_event(event);
             }
 
+            WindowEvent::MouseWheel { delta, .. } => {
+                self.input.dispatch_mouse_wheel(delta);
+            }
+
+            WindowEvent::Focused(false) => {
+                self.input.release_all();
+            }
+
             _ => {}
         }
     }
@@ -111,16 +311,17 @@ struct App {
     commands: Arc<commands::Commands>,
     events: Arc<events::Events>,
     input: Arc<input::Input>,
+    present_mode: backend::PresentMode,
 
     state: Option<State>,
+    workers_handles: Option<[handle::Handle; 2]>,
 
     _commands: [handle::Handle; 1],
     _schemes: [handle::Handle; 1],
-    _workers: [handle::Handle; 1],
 }
 
 impl App {
-    fn new(proxy: EventLoopProxy<AppEvent>) -> App {
+    fn new(proxy: EventLoopProxy<AppEvent>, present_mode: backend::PresentMode) -> App {
         let workers: workers::Workers = Default::default();
         let commands: Arc<commands::Commands> = Default::default();
         let events: Arc<events::Events> = Default::default();
@@ -140,18 +341,37 @@ impl App {
                 input.add_scheme(input::Scheme::default().add("exit", [input::Key::KbdEscape]))
             ],
 
-            _workers: [events::spawn_worker(&workers, events.clone())],
-
             state: Default::default(),
+            workers_handles: Some([
+                events::spawn_worker(&workers, events.clone()),
+                input::spawn_worker(&workers, input.clone()),
+            ]),
 
             workers,
             commands,
             events,
             input,
+            present_mode,
         };
 
         app
     }
+
+    /// Shuts application down in dependency order instead of relying on drop order: state (game, renderer and
+    /// assets) goes first and GPU work is awaited, then input and events workers are stopped and pending events
+    /// are delivered. Calling it again does nothing
+    fn shutdown(&mut self) {
+        if let Some(state) = self.state.take() {
+            state.shutdown();
+        }
+
+        if let Some([events_worker, input_worker]) = self.workers_handles.take() {
+            drop(input_worker);
+            drop(events_worker);
+
+            self.events.flush();
+        }
+    }
 }
 
 impl ApplicationHandler<AppEvent> for App {
@@ -161,6 +381,7 @@ impl ApplicationHandler<AppEvent> for App {
             self.events.clone(),
             self.commands.clone(),
             self.input.clone(),
+            self.present_mode,
             event_loop,
         );
 
@@ -179,16 +400,66 @@ impl ApplicationHandler<AppEvent> for App {
             AppEvent::Exit => event_loop.exit(),
         }
     }
+
+    fn exiting(&mut self, _: &ActiveEventLoop) {
+        self.shutdown();
+    }
+}
+
+/// INTERNAL: parses present mode from command line arguments: `--present-mode=<fifo|mailbox|immediate>`;
+/// [backend::PresentMode::Fifo] is used if it is not specified or invalid
+fn parse_present_mode<I>(args: I) -> backend::PresentMode
+where
+    I: IntoIterator<Item = String>,
+{
+    const PRESENT_MODE_ARG: &str = "--present-mode=";
+
+    args.into_iter()
+        .filter_map(|arg| arg.strip_prefix(PRESENT_MODE_ARG).map(str::to_string))
+        .last()
+        .and_then(|value| {
+            value
+                .parse()
+                .inspect_err(|error| diagnostics::warning(format_args!("{}, fifo is used", error)))
+                .ok()
+        })
+        .unwrap_or(backend::PresentMode::Fifo)
 }
 
 pub fn run() {
+    let present_mode = parse_present_mode(std::env::args().skip(1));
+
     let event_loop = EventLoop::with_user_event()
         .build()
         .expect("failed to create event loop for viewport");
 
     let proxy = event_loop.create_proxy();
-    let mut app = App::new(proxy);
+    let mut app = App::new(proxy, present_mode);
 
     event_loop.set_control_flow(ControlFlow::Poll);
     event_loop.run_app(&mut app).expect("application failure");
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn present_mode_is_parsed_from_arguments() {
+        let args = |args: &[&str]| args.iter().map(|arg| arg.to_string()).collect::<Vec<_>>();
+
+        assert_eq!(parse_present_mode(args(&[])), backend::PresentMode::Fifo);
+        assert_eq!(
+            parse_present_mode(args(&["--present-mode=mailbox"])),
+            backend::PresentMode::Mailbox
+        );
+        assert_eq!(
+            parse_present_mode(args(&["--present-mode=immediate", "--present-mode=fifo"])),
+            backend::PresentMode::Fifo
+        );
+        assert_eq!(
+            parse_present_mode(args(&["--present-mode=vsync"])),
+            backend::PresentMode::Fifo
+        );
+    }
+}
@@ -0,0 +1,293 @@
+use glam::Vec2;
+
+/// Width of glyph in pixels
+pub const GLYPH_WIDTH: usize = 5;
+
+/// Height of glyph in pixels
+pub const GLYPH_HEIGHT: usize = 7;
+
+/// INTERNAL: width of glyph cell in atlas, glyphs are separated by single transparent column to avoid bleeding
+const CELL_WIDTH: usize = GLYPH_WIDTH + 1;
+
+/// INTERNAL: list of glyphs of built-in fixed-width font: upper case latin letters, digits and few punctuation marks;
+/// each glyph is described by rows of pixels, `#` is filled pixel
+const GLYPHS: [(char, [&str; GLYPH_HEIGHT]); 41] = [
+    (
+        ' ',
+        [
+            ".....", ".....", ".....", ".....", ".....", ".....", ".....",
+        ],
+    ),
+    (
+        '0',
+        [
+            ".###.", "#...#", "#..##", "#.#.#", "##..#", "#...#", ".###.",
+        ],
+    ),
+    (
+        '1',
+        [
+            "..#..", ".##..", "..#..", "..#..", "..#..", "..#..", ".###.",
+        ],
+    ),
+    (
+        '2',
+        [
+            ".###.", "#...#", "....#", "...#.", "..#..", ".#...", "#####",
+        ],
+    ),
+    (
+        '3',
+        [
+            "#####", "...#.", "..#..", "...#.", "....#", "#...#", ".###.",
+        ],
+    ),
+    (
+        '4',
+        [
+            "...#.", "..##.", ".#.#.", "#..#.", "#####", "...#.", "...#.",
+        ],
+    ),
+    (
+        '5',
+        [
+            "#####", "#....", "####.", "....#", "....#", "#...#", ".###.",
+        ],
+    ),
+    (
+        '6',
+        [
+            "..##.", ".#...", "#....", "####.", "#...#", "#...#", ".###.",
+        ],
+    ),
+    (
+        '7',
+        [
+            "#####", "....#", "...#.", "..#..", ".#...", ".#...", ".#...",
+        ],
+    ),
+    (
+        '8',
+        [
+            ".###.", "#...#", "#...#", ".###.", "#...#", "#...#", ".###.",
+        ],
+    ),
+    (
+        '9',
+        [
+            ".###.", "#...#", "#...#", ".####", "....#", "...#.", ".##..",
+        ],
+    ),
+    (
+        'A',
+        [
+            ".###.", "#...#", "#...#", "#####", "#...#", "#...#", "#...#",
+        ],
+    ),
+    (
+        'B',
+        [
+            "####.", "#...#", "#...#", "####.", "#...#", "#...#", "####.",
+        ],
+    ),
+    (
+        'C',
+        [
+            ".###.", "#...#", "#....", "#....", "#....", "#...#", ".###.",
+        ],
+    ),
+    (
+        'D',
+        [
+            "###..", "#..#.", "#...#", "#...#", "#...#", "#..#.", "###..",
+        ],
+    ),
+    (
+        'E',
+        [
+            "#####", "#....", "#....", "####.", "#....", "#....", "#####",
+        ],
+    ),
+    (
+        'F',
+        [
+            "#####", "#....", "#....", "####.", "#....", "#....", "#....",
+        ],
+    ),
+    (
+        'G',
+        [
+            ".###.", "#...#", "#....", "#.###", "#...#", "#...#", ".####",
+        ],
+    ),
+    (
+        'H',
+        [
+            "#...#", "#...#", "#...#", "#####", "#...#", "#...#", "#...#",
+        ],
+    ),
+    (
+        'I',
+        [
+            ".###.", "..#..", "..#..", "..#..", "..#..", "..#..", ".###.",
+        ],
+    ),
+    (
+        'J',
+        [
+            "..###", "...#.", "...#.", "...#.", "...#.", "#..#.", ".##..",
+        ],
+    ),
+    (
+        'K',
+        [
+            "#...#", "#..#.", "#.#..", "##...", "#.#..", "#..#.", "#...#",
+        ],
+    ),
+    (
+        'L',
+        [
+            "#....", "#....", "#....", "#....", "#....", "#....", "#####",
+        ],
+    ),
+    (
+        'M',
+        [
+            "#...#", "##.##", "#.#.#", "#.#.#", "#...#", "#...#", "#...#",
+        ],
+    ),
+    (
+        'N',
+        [
+            "#...#", "#...#", "##..#", "#.#.#", "#..##", "#...#", "#...#",
+        ],
+    ),
+    (
+        'O',
+        [
+            ".###.", "#...#", "#...#", "#...#", "#...#", "#...#", ".###.",
+        ],
+    ),
+    (
+        'P',
+        [
+            "####.", "#...#", "#...#", "####.", "#....", "#....", "#....",
+        ],
+    ),
+    (
+        'Q',
+        [
+            ".###.", "#...#", "#...#", "#...#", "#.#.#", "#..#.", ".##.#",
+        ],
+    ),
+    (
+        'R',
+        [
+            "####.", "#...#", "#...#", "####.", "#.#..", "#..#.", "#...#",
+        ],
+    ),
+    (
+        'S',
+        [
+            ".####", "#....", "#....", ".###.", "....#", "....#", "####.",
+        ],
+    ),
+    (
+        'T',
+        [
+            "#####", "..#..", "..#..", "..#..", "..#..", "..#..", "..#..",
+        ],
+    ),
+    (
+        'U',
+        [
+            "#...#", "#...#", "#...#", "#...#", "#...#", "#...#", ".###.",
+        ],
+    ),
+    (
+        'V',
+        [
+            "#...#", "#...#", "#...#", "#...#", "#...#", ".#.#.", "..#..",
+        ],
+    ),
+    (
+        'W',
+        [
+            "#...#", "#...#", "#...#", "#.#.#", "#.#.#", "#.#.#", ".#.#.",
+        ],
+    ),
+    (
+        'X',
+        [
+            "#...#", "#...#", ".#.#.", "..#..", ".#.#.", "#...#", "#...#",
+        ],
+    ),
+    (
+        'Y',
+        [
+            "#...#", "#...#", ".#.#.", "..#..", "..#..", "..#..", "..#..",
+        ],
+    ),
+    (
+        'Z',
+        [
+            "#####", "....#", "...#.", "..#..", ".#...", "#....", "#####",
+        ],
+    ),
+    (
+        ':',
+        [
+            ".....", "..#..", "..#..", ".....", "..#..", "..#..", ".....",
+        ],
+    ),
+    (
+        ',',
+        [
+            ".....", ".....", ".....", ".....", "..##.", "..#..", ".#...",
+        ],
+    ),
+    (
+        '.',
+        [
+            ".....", ".....", ".....", ".....", ".....", ".##..", ".##..",
+        ],
+    ),
+    (
+        '-',
+        [
+            ".....", ".....", ".....", "#####", ".....", ".....", ".....",
+        ],
+    ),
+];
+
+/// Builds font atlas: glyphs are placed in single row, filled pixels are opaque white, others are transparent;
+/// returns extent of atlas and list of its RGBA pixels
+pub fn atlas() -> ([f32; 2], Vec<[u8; 4]>) {
+    let width = GLYPHS.len() * CELL_WIDTH;
+    let mut pixels = vec![[0; 4]; width * GLYPH_HEIGHT];
+
+    for (index, (_, rows)) in GLYPHS.iter().enumerate() {
+        for (y, row) in rows.iter().enumerate() {
+            for (x, pixel) in row.chars().enumerate() {
+                if pixel == '#' {
+                    pixels[y * width + index * CELL_WIDTH + x] = [255; 4];
+                }
+            }
+        }
+    }
+
+    ([width as f32, GLYPH_HEIGHT as f32], pixels)
+}
+
+/// Returns texture coordinates of top-left and bottom-right corners of glyph in atlas, built by [atlas];
+/// lower case letters are mapped to upper case ones, [None] is returned if there is no such glyph
+pub fn glyph_uv(character: char) -> Option<(Vec2, Vec2)> {
+    let character = character.to_ascii_uppercase();
+    let index = GLYPHS.iter().position(|(glyph, _)| *glyph == character)?;
+
+    let width = (GLYPHS.len() * CELL_WIDTH) as f32;
+    let min = Vec2::new((index * CELL_WIDTH) as f32 / width, 0.0);
+    let max = min + Vec2::new(GLYPH_WIDTH as f32 / width, 1.0);
+
+    Some((min, max))
+}
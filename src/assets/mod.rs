@@ -1,23 +1,39 @@
 use std::{
     collections::BTreeMap,
-    sync::{Arc, RwLock},
+    fmt, fs,
+    hash::{DefaultHasher, Hash, Hasher},
+    io,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex, RwLock, Weak, mpsc},
+    thread,
+    time::Duration,
 };
 
+use glam::Vec2;
+use notify::Watcher;
+use serde::Deserialize;
+
 use crate::{
     assets::types::Vertex,
-    rendering::{backend, buffer, pipeline},
+    diagnostics, handle,
+    rendering::{backend, buffer, image, pipeline, sampler},
+    workers,
 };
 
+pub mod font;
 pub mod models;
 pub mod shaders;
 pub mod types;
 
 /// Mesh asset data
+#[derive(Clone)]
 pub struct MeshAsset {
     /// Vertex buffer
     pub vertex: buffer::Buffer<Vertex>,
     /// Index buffer
     pub index: buffer::Buffer<u32>,
+    /// Radius of bounding circle of mesh vertices around mesh origin
+    pub radius: f32,
 }
 
 /// Pipeline asset data
@@ -26,16 +42,27 @@ pub struct PipelineAsset {
     pub pipeline: pipeline::Pipeline,
 }
 
+/// Texture asset data
+pub struct TextureAsset {
+    /// View of texture image
+    pub image_view: image::ImageView,
+    /// Sampler of texture
+    pub sampler: sampler::Sampler,
+}
+
 /// An asset
 ///
 /// See content of next structures for specific details:
 /// * [MeshAsset]
 /// * [PipelineAsset]
+/// * [TextureAsset]
 pub enum Asset {
     /// Variant of an asset with [MeshAsset] data
     Mesh(MeshAsset),
     /// Variant of an asset with [PipelineAsset] data
     Pipeline(PipelineAsset),
+    /// Variant of an asset with [TextureAsset] data
+    Texture(TextureAsset),
 }
 
 impl Asset {
@@ -56,6 +83,15 @@ impl Asset {
             None
         }
     }
+
+    /// Returns reference to [TextureAsset] if [Asset] is a [Asset::Texture] variant
+    pub fn as_texture(&self) -> Option<&TextureAsset> {
+        if let Asset::Texture(texture) = self {
+            Some(texture)
+        } else {
+            None
+        }
+    }
 }
 
 impl From<MeshAsset> for Asset {
@@ -70,6 +106,12 @@ impl From<PipelineAsset> for Asset {
     }
 }
 
+impl From<TextureAsset> for Asset {
+    fn from(value: TextureAsset) -> Self {
+        Self::Texture(value)
+    }
+}
+
 /// Context for [IntoAsset::into_asset] trait method
 pub struct IntoAssetContext {
     backend: Arc<backend::Backend>,
@@ -79,6 +121,17 @@ pub struct IntoAssetContext {
 pub trait IntoAsset {
     /// Converts definition into [Asset] instance
     fn into_asset(self, context: IntoAssetContext) -> Asset;
+
+    /// Returns hash of definition content, if assets with identical content can share GPU resources
+    fn content_hash(&self) -> Option<u64> {
+        None
+    }
+}
+
+/// Trait of definition, which can be constructed from content of a file
+pub trait FromFile: Sized {
+    /// Constructs definition from file content, returns [None] if content is invalid (e.g. file is partially written)
+    fn from_file(bytes: Vec<u8>) -> Option<Self>;
 }
 
 /// Definition of [MeshAsset]
@@ -89,8 +142,106 @@ pub struct MeshAssetDef {
     pub indices: Vec<u32>,
 }
 
+/// Enumeration of [MeshAssetDef::validate] errors
+#[derive(Debug)]
+pub enum MeshValidationError {
+    /// Count of indices is not a multiple of triangle vertices count
+    IncompleteTriangle(usize),
+    /// Index references vertex, which does not exist
+    IndexOutOfBounds {
+        /// Position of index in list of indices
+        position: usize,
+        /// Value of index
+        index: u32,
+        /// Count of vertices
+        vertices: usize,
+    },
+}
+
+impl fmt::Display for MeshValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MeshValidationError::IncompleteTriangle(count) => {
+                write!(f, "count of indices {} is not a multiple of 3", count)
+            }
+
+            MeshValidationError::IndexOutOfBounds {
+                position,
+                index,
+                vertices,
+            } => write!(
+                f,
+                "index {} at position {} is out of bounds of {} vertices",
+                index, position, vertices
+            ),
+        }
+    }
+}
+
+impl std::error::Error for MeshValidationError {}
+
+impl MeshAssetDef {
+    /// Validates that indices form complete triangles and reference existing vertices
+    pub fn validate(&self) -> Result<(), MeshValidationError> {
+        if !self.indices.len().is_multiple_of(3) {
+            return Err(MeshValidationError::IncompleteTriangle(self.indices.len()));
+        }
+
+        let out_of_bounds = self
+            .indices
+            .iter()
+            .enumerate()
+            .find(|(_, index)| **index as usize >= self.vertices.len());
+
+        match out_of_bounds {
+            Some((position, index)) => Err(MeshValidationError::IndexOutOfBounds {
+                position,
+                index: *index,
+                vertices: self.vertices.len(),
+            }),
+
+            None => Ok(()),
+        }
+    }
+}
+
+/// INTERNAL: file format of [MeshAssetDef], each vertex is `[x, y, u, v]`
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct MeshFile {
+    vertices: Vec<[f32; 4]>,
+    indices: Vec<u32>,
+}
+
+impl FromFile for MeshAssetDef {
+    fn from_file(bytes: Vec<u8>) -> Option<Self> {
+        let file: MeshFile = serde_json::from_slice(&bytes).ok()?;
+
+        let definition = MeshAssetDef {
+            vertices: file
+                .vertices
+                .into_iter()
+                .map(|[x, y, u, v]| Vertex {
+                    position: Vec2::new(x, y),
+                    uv: Vec2::new(u, v),
+                })
+                .collect(),
+            indices: file.indices,
+        };
+
+        definition.validate().ok()?;
+
+        Some(definition)
+    }
+}
+
 impl IntoAsset for MeshAssetDef {
     fn into_asset(self, context: IntoAssetContext) -> Asset {
+        // NOTE: malformed mesh must be caught here, otherwise draw reads out of bounds of vertex buffer on GPU
+        if let Err(error) = self.validate() {
+            panic!("invalid mesh: {}", error);
+        }
+
         let mesh = MeshAsset {
             vertex: buffer::BufferFactory::create(
                 context.backend.as_ref(),
@@ -107,18 +258,57 @@ impl IntoAsset for MeshAssetDef {
                     data: buffer::BufferData::Slice(&self.indices),
                 },
             ),
+
+            radius: self
+                .vertices
+                .iter()
+                .map(|vertex| vertex.position.length())
+                .fold(0.0, f32::max),
         };
 
         mesh.into()
     }
+
+    fn content_hash(&self) -> Option<u64> {
+        let mut hasher = DefaultHasher::new();
+
+        for vertex in &self.vertices {
+            vertex
+                .position
+                .to_array()
+                .map(f32::to_bits)
+                .hash(&mut hasher);
+            vertex.uv.to_array().map(f32::to_bits).hash(&mut hasher);
+        }
+
+        self.indices.hash(&mut hasher);
+
+        Some(hasher.finish())
+    }
 }
 
 /// Definition of [PipelineAsset]
+#[derive(Clone)]
 pub struct PipelineAssetDef {
     /// List of shaders
     pub shaders: Vec<pipeline::ShaderFactory>,
     /// List of shader bindings
     pub bindings: Vec<pipeline::InputDataBinding>,
+    /// Samples count per pixel
+    pub samples: image::SampleCount,
+    /// Primitive topology
+    pub topology: pipeline::PipelineTopology,
+    /// Depth test is enabled
+    pub depth_test: bool,
+}
+
+impl PipelineAssetDef {
+    /// Replaces shader at specified position in list of shaders
+    pub fn with_shader(mut self, index: usize, shader: ShaderDef) -> PipelineAssetDef {
+        self.shaders[index] = shader.factory;
+
+        self
+    }
 }
 
 impl IntoAsset for PipelineAssetDef {
@@ -129,6 +319,9 @@ impl IntoAsset for PipelineAssetDef {
                 pipeline::PipelineDef {
                     shaders: self.shaders,
                     bindings: self.bindings,
+                    samples: self.samples,
+                    topology: self.topology,
+                    depth_test: self.depth_test,
                 },
             ),
         };
@@ -137,6 +330,99 @@ impl IntoAsset for PipelineAssetDef {
     }
 }
 
+/// Definition of shader of [PipelineAssetDef], see [PipelineAssetDef::with_shader]
+pub struct ShaderDef {
+    /// Shader factory
+    pub factory: pipeline::ShaderFactory,
+}
+
+impl FromFile for ShaderDef {
+    fn from_file(bytes: Vec<u8>) -> Option<Self> {
+        let factory = pipeline::spirv_shader_factory(&bytes)?;
+
+        Some(ShaderDef { factory })
+    }
+}
+
+/// Definition of [TextureAsset]
+pub struct TextureAssetDef {
+    /// Content of PNG file
+    pub bytes: Vec<u8>,
+}
+
+impl TextureAssetDef {
+    /// INTERNAL: decodes PNG content into extent and list of RGBA pixels
+    fn decode(&self) -> ([f32; 2], Vec<[u8; 4]>) {
+        let decoded = ::image::load_from_memory_with_format(&self.bytes, ::image::ImageFormat::Png)
+            .expect("failed to decode texture")
+            .into_rgba8();
+
+        let extent = [decoded.width() as f32, decoded.height() as f32];
+        let pixels = decoded.pixels().map(|pixel| pixel.0).collect();
+
+        (extent, pixels)
+    }
+}
+
+impl FromFile for TextureAssetDef {
+    fn from_file(bytes: Vec<u8>) -> Option<Self> {
+        ::image::load_from_memory_with_format(&bytes, ::image::ImageFormat::Png).ok()?;
+
+        Some(TextureAssetDef { bytes })
+    }
+}
+
+impl IntoAsset for TextureAssetDef {
+    fn into_asset(self, context: IntoAssetContext) -> Asset {
+        let (extent, pixels) = self.decode();
+
+        let texture = RawTextureAssetDef {
+            extent,
+            pixels,
+            filter: sampler::SamplerFilter::Linear,
+        };
+
+        texture.into_asset(context)
+    }
+}
+
+/// Definition of [TextureAsset] with pixels provided as is, e.g. generated in code
+pub struct RawTextureAssetDef {
+    /// Extent of texture in pixels
+    pub extent: [f32; 2],
+    /// List of RGBA pixels, row by row
+    pub pixels: Vec<[u8; 4]>,
+    /// Filter of texture sampler
+    pub filter: sampler::SamplerFilter,
+}
+
+impl IntoAsset for RawTextureAssetDef {
+    fn into_asset(self, context: IntoAssetContext) -> Asset {
+        let image = image::ImageFactory::create_with_data(
+            context.backend.as_ref(),
+            image::ImageDef {
+                usage: image::ImageUsage::SAMPLED,
+                extent: self.extent,
+                format: image::ImageFormat::RGBA,
+                samples: image::SampleCount::Sample1,
+            },
+            &self.pixels,
+        );
+
+        let texture = TextureAsset {
+            image_view: image.view(),
+            sampler: sampler::SamplerFactory::create(
+                context.backend.as_ref(),
+                sampler::SamplerDef {
+                    filter: self.filter,
+                },
+            ),
+        };
+
+        texture.into()
+    }
+}
+
 /// Reference to asset
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
 pub struct AssetRef(String);
@@ -153,23 +439,110 @@ impl From<String> for AssetRef {
     }
 }
 
+impl fmt::Display for AssetRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// INTERNAL: function, which constructs [Asset] from file content
+type SourceLoader = Arc<dyn Fn(Vec<u8>, IntoAssetContext) -> Option<Asset> + Send + Sync>;
+
+/// INTERNAL: source file of an asset
+struct Source {
+    asset_ref: AssetRef,
+    loader: SourceLoader,
+}
+
+/// INTERNAL: receiver of file watcher events
+type WatcherReceiver = mpsc::Receiver<notify::Result<notify::Event>>;
+
 /// Assets infrastructure
 pub struct Assets {
     backend: Arc<backend::Backend>,
     assets: RwLock<BTreeMap<AssetRef, Arc<Asset>>>,
+    contents: Mutex<BTreeMap<u64, Weak<Asset>>>,
+    sources: RwLock<BTreeMap<PathBuf, Source>>,
+    watcher: Mutex<Option<notify::RecommendedWatcher>>,
+    receiver: Mutex<WatcherReceiver>,
 }
 
 impl Assets {
     /// Creates new instance of [Assets]
     pub fn new(backend: Arc<backend::Backend>) -> Arc<Assets> {
+        let (sender, receiver) = mpsc::channel();
+
+        let watcher = notify::recommended_watcher(sender)
+            .inspect_err(|error| {
+                diagnostics::warning(format_args!("failed to create asset watcher: {}", error))
+            })
+            .ok();
+
         let assets = Assets {
             backend,
             assets: Default::default(),
+            contents: Default::default(),
+            sources: Default::default(),
+            watcher: Mutex::new(watcher),
+            receiver: Mutex::new(receiver),
         };
 
         Arc::new(assets)
     }
 
+    /// INTERNAL: creates [IntoAssetContext]
+    fn context(&self) -> IntoAssetContext {
+        IntoAssetContext {
+            backend: self.backend.clone(),
+        }
+    }
+
+    /// INTERNAL: reads source file and replaces its asset, retries if file content is invalid;
+    /// returns reference to reloaded asset or [None] if there is no such source or it failed to load
+    fn reload(&self, path: &Path) -> Option<AssetRef> {
+        const ATTEMPTS: usize = 5;
+        const RETRY_DELAY: Duration = Duration::from_millis(50);
+
+        // NOTE: source is cloned out, so sources are not locked while reload is retried
+        let (asset_ref, loader) = self
+            .sources
+            .read()
+            .unwrap()
+            .get(path)
+            .map(|source| (source.asset_ref.clone(), source.loader.clone()))?;
+
+        for attempt in 0..ATTEMPTS {
+            if attempt > 0 {
+                thread::sleep(RETRY_DELAY);
+            }
+
+            let asset = match fs::read(path) {
+                Ok(bytes) => loader(bytes, self.context()),
+
+                // NOTE: file may be created later, until then previously loaded asset is kept
+                Err(error) if error.kind() == io::ErrorKind::NotFound => return None,
+
+                Err(_) => None,
+            };
+
+            if let Some(asset) = asset {
+                let mut assets = self.assets.write().unwrap();
+
+                assets.insert(asset_ref.clone(), Arc::new(asset));
+
+                return Some(asset_ref);
+            }
+        }
+
+        diagnostics::warning(format_args!(
+            "failed to load asset {} from {}",
+            asset_ref,
+            path.display()
+        ));
+
+        None
+    }
+
     /// Gets asset by its reference
     pub fn get(&self, asset_ref: &AssetRef) -> Option<Arc<Asset>> {
         let assets = self.assets.read().unwrap();
@@ -177,26 +550,342 @@ impl Assets {
         assets.get(asset_ref).cloned()
     }
 
-    /// Loads asset
+    /// Loads asset: mesh with content identical to already loaded one shares its GPU buffers
     pub fn load<A>(&self, asset_ref: AssetRef, asset: A)
     where
         A: IntoAsset,
     {
+        let content_hash = asset.content_hash();
+
+        let mut contents = self.contents.lock().unwrap();
         let mut assets = self.assets.write().unwrap();
 
-        let context = IntoAssetContext {
-            backend: self.backend.clone(),
+        let shared = content_hash
+            .and_then(|hash| contents.get(&hash))
+            .and_then(Weak::upgrade)
+            .and_then(|shared| shared.as_mesh().cloned())
+            .map(Asset::from);
+
+        let asset = Arc::new(shared.unwrap_or_else(|| asset.into_asset(self.context())));
+
+        if let Some(hash) = content_hash {
+            contents.retain(|_, asset| asset.strong_count() > 0);
+            contents.insert(hash, Arc::downgrade(&asset));
+        }
+
+        assets.insert(asset_ref, asset);
+    }
+
+    /// Loads asset from file and reloads it each time the file changes; if file does not exist yet, asset is
+    /// loaded when it is created. Consumers, which hold previous version of asset, keep it until they get asset again
+    pub fn load_from_path<A, P>(&self, asset_ref: AssetRef, path: P)
+    where
+        A: FromFile + IntoAsset + 'static,
+        P: AsRef<Path>,
+    {
+        self.load_from_path_with(asset_ref, path, |definition: A| definition);
+    }
+
+    /// Same as [Assets::load_from_path], but asset definition is built from file content by `build`
+    pub fn load_from_path_with<D, A, F, P>(&self, asset_ref: AssetRef, path: P, build: F)
+    where
+        D: FromFile + 'static,
+        A: IntoAsset + 'static,
+        F: Fn(D) -> A + Send + Sync + 'static,
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref();
+
+        // NOTE: only directory is canonicalized, because file itself may not exist yet
+        let canonical = path
+            .parent()
+            .zip(path.file_name())
+            .ok_or_else(|| io::Error::from(io::ErrorKind::InvalidInput))
+            .and_then(|(directory, name)| {
+                let directory = match directory.as_os_str().is_empty() {
+                    true => Path::new("."),
+                    false => directory,
+                };
+
+                fs::canonicalize(directory).map(|directory| directory.join(name))
+            });
+
+        let path = match canonical {
+            Ok(path) => path,
+
+            Err(error) => {
+                diagnostics::warning(format_args!(
+                    "failed to load asset {} from {}: {}",
+                    asset_ref,
+                    path.display(),
+                    error
+                ));
+
+                return;
+            }
+        };
+
+        // NOTE: parent directory is watched, because editors usually replace file instead of modifying it
+        if let Some(watcher) = self.watcher.lock().unwrap().as_mut()
+            && let Some(directory) = path.parent()
+            && let Err(error) = watcher.watch(directory, notify::RecursiveMode::NonRecursive)
+        {
+            diagnostics::warning(format_args!(
+                "failed to watch {}: {}",
+                directory.display(),
+                error
+            ));
+        }
+
+        let source = Source {
+            asset_ref,
+            loader: Arc::new(move |bytes, context| {
+                D::from_file(bytes).map(|definition| build(definition).into_asset(context))
+            }),
         };
 
-        let asset = asset.into_asset(context);
+        self.sources.write().unwrap().insert(path.clone(), source);
 
-        assets.insert(asset_ref, Arc::new(asset));
+        self.reload(&path);
     }
 
-    /// Unloads asset
+    /// Unloads asset: asset is no longer reloaded from its source file
     pub fn unload(&self, asset_ref: &AssetRef) {
-        let mut assets = self.assets.write().unwrap();
+        self.sources
+            .write()
+            .unwrap()
+            .retain(|_, source| &source.asset_ref != asset_ref);
+
+        self.assets.write().unwrap().remove(asset_ref);
+    }
+}
+
+/// INTERNAL: Assets worker thread function
+fn worker_func(assets: &Assets) {
+    const TIMEOUT: Duration = Duration::from_millis(100);
+
+    let event = assets.receiver.lock().unwrap().recv_timeout(TIMEOUT);
+
+    match event {
+        Ok(Ok(event)) if event.kind.is_create() || event.kind.is_modify() => {
+            for path in event.paths {
+                if let Some(asset_ref) = assets.reload(&path) {
+                    diagnostics::info(format_args!(
+                        "asset {} reloaded from {}",
+                        asset_ref,
+                        path.display()
+                    ));
+                }
+            }
+        }
+
+        Ok(Err(error)) => {
+            diagnostics::warning(format_args!("asset watcher failure: {}", error));
+        }
+
+        _ => {}
+    }
+}
+
+/// Spawns assets worker thread, which reloads assets when their source files change
+pub fn spawn_worker(workers: &workers::Workers, assets: Arc<Assets>) -> handle::Handle {
+    workers.spawn("Assets", move |token| {
+        while !token.is_cancelled() {
+            worker_func(&assets);
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Encodes RGB pixels into PNG file content
+    fn encode_png(width: u32, height: u32, pixels: &[u8]) -> Vec<u8> {
+        let mut bytes = Vec::new();
+
+        ::image::RgbImage::from_raw(width, height, pixels.to_vec())
+            .unwrap()
+            .write_to(
+                &mut std::io::Cursor::new(&mut bytes),
+                ::image::ImageFormat::Png,
+            )
+            .unwrap();
+
+        bytes
+    }
+
+    #[test]
+    fn texture_decodes_extent_and_rgba_pixels() {
+        let bytes = encode_png(2, 1, &[255, 128, 0, 10, 200, 255]);
+
+        let texture = TextureAssetDef::from_file(bytes).expect("PNG should be accepted");
+        let (extent, pixels) = texture.decode();
+
+        assert_eq!(extent, [2.0, 1.0]);
+        assert_eq!(pixels, vec![[255, 128, 0, 255], [10, 200, 255, 255]]);
+    }
+
+    #[test]
+    fn texture_rejects_invalid_content() {
+        assert!(TextureAssetDef::from_file(vec![0, 1, 2, 3]).is_none());
+    }
+
+    #[test]
+    fn mesh_is_read_from_file() {
+        let bytes = br#"{ "vertices": [[0, 1, 0.5, 0], [1, 0, 0, 0], [0, 0, 0, 1]], "indices": [0, 1, 2] }"#;
+
+        let mesh = MeshAssetDef::from_file(bytes.to_vec()).expect("mesh should be accepted");
+
+        assert_eq!(mesh.vertices.len(), 3);
+        assert_eq!(mesh.vertices[0].position, Vec2::new(0.0, 1.0));
+        assert_eq!(mesh.vertices[0].uv, Vec2::new(0.5, 0.0));
+        assert_eq!(mesh.indices, [0, 1, 2]);
+    }
+
+    #[test]
+    fn mesh_rejects_invalid_content() {
+        let partial = br#"{ "vertices": [[0, 1, 0, 0], [1, 0,"#;
+        let out_of_bounds = br#"{ "vertices": [[0, 1, 0, 0]], "indices": [0, 1, 2] }"#;
+
+        assert!(MeshAssetDef::from_file(partial.to_vec()).is_none());
+        assert!(MeshAssetDef::from_file(out_of_bounds.to_vec()).is_none());
+    }
+
+    #[test]
+    fn mesh_files_match_built_in_models() {
+        let models: [(&str, &[Vertex], &[u32]); 5] = [
+            (
+                "spacecraft",
+                &models::spacecraft::VERTICES,
+                &models::spacecraft::INDICES,
+            ),
+            ("quad", &models::quad::VERTICES, &models::quad::INDICES),
+            (
+                "fullscreen",
+                &models::fullscreen::VERTICES,
+                &models::fullscreen::INDICES,
+            ),
+            (
+                "bullet",
+                &models::bullet::VERTICES,
+                &models::bullet::INDICES,
+            ),
+            (
+                "power_up",
+                &models::power_up::VERTICES,
+                &models::power_up::INDICES,
+            ),
+        ];
+
+        for (name, vertices, indices) in models {
+            let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+                .join("assets/meshes")
+                .join(format!("{}.json", name));
+
+            let mesh = MeshAssetDef::from_file(fs::read(path).unwrap())
+                .unwrap_or_else(|| panic!("mesh file of {} should be valid", name));
+
+            assert_eq!(mesh.vertices.len(), vertices.len(), "{}", name);
+            assert_eq!(mesh.indices, indices, "{}", name);
+
+            for (actual, expected) in mesh.vertices.iter().zip(vertices) {
+                assert_eq!(actual.position, expected.position, "{}", name);
+                assert_eq!(actual.uv, expected.uv, "{}", name);
+            }
+        }
+    }
+
+    #[test]
+    fn shader_rejects_invalid_content() {
+        assert!(ShaderDef::from_file(vec![0, 1, 2]).is_none());
+        assert!(ShaderDef::from_file(vec![0, 1, 2, 3]).is_none());
+    }
+
+    /// Creates [Assets] over headless backend, returns [None] if there is no Vulkan implementation
+    fn assets() -> Option<Arc<Assets>> {
+        backend::Backend::try_new_headless([1, 1]).map(Assets::new)
+    }
+
+    /// Creates definition of single triangle mesh
+    fn triangle() -> MeshAssetDef {
+        MeshAssetDef {
+            vertices: vec![Default::default(); 3],
+            indices: vec![0, 1, 2],
+        }
+    }
+
+    #[test]
+    fn unloaded_asset_is_removed() {
+        let Some(assets) = assets() else {
+            return;
+        };
+
+        let asset_ref: AssetRef = "triangle".into();
+
+        assets.load(asset_ref.clone(), triangle());
+        assert!(assets.get(&asset_ref).is_some());
+
+        assets.unload(&asset_ref);
+        assert!(assets.get(&asset_ref).is_none());
+    }
+
+    #[test]
+    fn asset_loaded_from_path_is_replaced_on_reload() {
+        let Some(assets) = assets() else {
+            return;
+        };
+
+        let path =
+            std::env::temp_dir().join(format!("asteroids_rs_texture_{}.png", std::process::id()));
+        let asset_ref: AssetRef = "texture".into();
+
+        fs::write(&path, encode_png(1, 1, &[255, 0, 0])).unwrap();
+        assets.load_from_path::<TextureAssetDef, _>(asset_ref.clone(), &path);
+
+        let loaded = assets.get(&asset_ref).expect("texture should be loaded");
+
+        fs::write(&path, encode_png(1, 1, &[0, 255, 0])).unwrap();
+        let reloaded = assets.reload(&fs::canonicalize(&path).unwrap());
+
+        let _ = fs::remove_file(&path);
+
+        assert_eq!(reloaded, Some(asset_ref.clone()));
+        assert!(!Arc::ptr_eq(&loaded, &assets.get(&asset_ref).unwrap()));
+    }
+
+    #[test]
+    fn asset_is_loaded_from_path_once_file_is_created() {
+        let Some(assets) = assets() else {
+            return;
+        };
+
+        let path =
+            std::env::temp_dir().join(format!("asteroids_rs_created_{}.png", std::process::id()));
+        let asset_ref: AssetRef = "texture".into();
+
+        assets.load_from_path::<TextureAssetDef, _>(asset_ref.clone(), &path);
+        assert!(assets.get(&asset_ref).is_none());
+
+        fs::write(&path, encode_png(1, 1, &[255, 0, 0])).unwrap();
+        let reloaded = assets.reload(&fs::canonicalize(&path).unwrap());
+
+        let _ = fs::remove_file(&path);
+
+        assert_eq!(reloaded, Some(asset_ref.clone()));
+        assert!(assets.get(&asset_ref).is_some());
+    }
+
+    #[test]
+    fn asset_is_not_loaded_from_missing_path() {
+        let Some(assets) = assets() else {
+            return;
+        };
+
+        let asset_ref: AssetRef = "texture".into();
+
+        assets.load_from_path::<TextureAssetDef, _>(asset_ref.clone(), "missing/texture.png");
 
-        assets.remove(asset_ref);
+        assert!(assets.get(&asset_ref).is_none());
     }
 }
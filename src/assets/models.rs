@@ -8,12 +8,15 @@ pub mod spacecraft {
     pub const VERTICES: [Vertex; 3] = [
         Vertex {
             position: Vec2::new(0.0, 0.5),
+            uv: Vec2::ZERO,
         },
         Vertex {
             position: Vec2::new(0.35355339, -0.35355339),
+            uv: Vec2::ZERO,
         },
         Vertex {
             position: Vec2::new(-0.35355339, -0.35355339),
+            uv: Vec2::ZERO,
         },
     ];
 
@@ -23,6 +26,103 @@ pub mod spacecraft {
     ];
 }
 
+/// Model of textured quad of unit size
+pub mod quad {
+    use glam::Vec2;
+
+    use crate::assets::types::Vertex;
+
+    /// List of vertices
+    pub const VERTICES: [Vertex; 4] = [
+        Vertex {
+            position: Vec2::new(-0.5, 0.5),
+            uv: Vec2::new(0.0, 0.0),
+        },
+        Vertex {
+            position: Vec2::new(0.5, 0.5),
+            uv: Vec2::new(1.0, 0.0),
+        },
+        Vertex {
+            position: Vec2::new(0.5, -0.5),
+            uv: Vec2::new(1.0, 1.0),
+        },
+        Vertex {
+            position: Vec2::new(-0.5, -0.5),
+            uv: Vec2::new(0.0, 1.0),
+        },
+    ];
+
+    /// List of indices
+    pub const INDICES: [u32; 6] = [
+        0, 1, 2, //
+        0, 2, 3, //
+    ];
+}
+
+/// Model of triangle covering entire screen in clip space, used by full-screen passes
+pub mod fullscreen {
+    use glam::Vec2;
+
+    use crate::assets::types::Vertex;
+
+    /// List of vertices
+    pub const VERTICES: [Vertex; 3] = [
+        Vertex {
+            position: Vec2::new(-1.0, -1.0),
+            uv: Vec2::new(0.0, 0.0),
+        },
+        Vertex {
+            position: Vec2::new(3.0, -1.0),
+            uv: Vec2::new(2.0, 0.0),
+        },
+        Vertex {
+            position: Vec2::new(-1.0, 3.0),
+            uv: Vec2::new(0.0, 2.0),
+        },
+    ];
+
+    /// List of indices
+    pub const INDICES: [u32; 3] = [
+        0, 1, 2, //
+    ];
+}
+
+/// Model of [crate::game::entities::PowerUp]
+pub mod power_up {
+    use glam::Vec2;
+
+    use crate::{assets::types::Vertex, game};
+
+    /// Radius of power-up
+    const RADIUS: f32 = game::entities::consts::POWER_UP_RADIUS;
+
+    /// List of vertices
+    pub const VERTICES: [Vertex; 4] = [
+        Vertex {
+            position: Vec2::new(0.0, RADIUS),
+            uv: Vec2::ZERO,
+        },
+        Vertex {
+            position: Vec2::new(RADIUS, 0.0),
+            uv: Vec2::ZERO,
+        },
+        Vertex {
+            position: Vec2::new(0.0, -RADIUS),
+            uv: Vec2::ZERO,
+        },
+        Vertex {
+            position: Vec2::new(-RADIUS, 0.0),
+            uv: Vec2::ZERO,
+        },
+    ];
+
+    /// List of indices
+    pub const INDICES: [u32; 6] = [
+        0, 1, 2, //
+        0, 2, 3, //
+    ];
+}
+
 /// Model of [crate::game::entities::Bullet]
 pub mod bullet {
     use std::f32::consts::PI;
@@ -39,30 +139,39 @@ pub mod bullet {
     pub const VERTICES: [Vertex; 9] = [
         Vertex {
             position: Vec2::new(0.0, 0.0),
+            uv: Vec2::ZERO,
         },
         Vertex {
             position: Vec2::new(0.0, RADIUS),
+            uv: Vec2::ZERO,
         },
         Vertex {
             position: Vec2::new(PI_4, PI_4),
+            uv: Vec2::ZERO,
         },
         Vertex {
             position: Vec2::new(RADIUS, 0.0),
+            uv: Vec2::ZERO,
         },
         Vertex {
             position: Vec2::new(PI_4, -PI_4),
+            uv: Vec2::ZERO,
         },
         Vertex {
             position: Vec2::new(0.0, -RADIUS),
+            uv: Vec2::ZERO,
         },
         Vertex {
             position: Vec2::new(-PI_4, -PI_4),
+            uv: Vec2::ZERO,
         },
         Vertex {
             position: Vec2::new(-RADIUS, 0.0),
+            uv: Vec2::ZERO,
         },
         Vertex {
             position: Vec2::new(-PI_4, PI_4),
+            uv: Vec2::ZERO,
         },
     ];
 
@@ -78,3 +187,50 @@ pub mod bullet {
         0, 8, 1, //
     ];
 }
+
+/// Model of [crate::game::entities::Asteroid]
+pub mod asteroid {
+    use std::iter::once;
+
+    use glam::Vec2;
+
+    use crate::{assets::types::Vertex, game::entities::consts::ASTEROID_SEGMENTS_COUNT};
+
+    /// Constructs list of vertices from asteroid body
+    pub fn vertices(body: &[Vec2; ASTEROID_SEGMENTS_COUNT]) -> Vec<Vertex> {
+        once(Vertex::default())
+            .chain(body.iter().copied().map(|vertex| Vertex {
+                position: vertex,
+                uv: Vec2::ZERO,
+            }))
+            .collect()
+    }
+
+    /// Constructs list of vertices from asteroid fragment triangle
+    pub fn fragment_vertices(triangle: &[Vec2; 3]) -> Vec<Vertex> {
+        triangle
+            .iter()
+            .copied()
+            .map(|vertex| Vertex {
+                position: vertex,
+                uv: Vec2::ZERO,
+            })
+            .collect()
+    }
+
+    /// Constructs list of indices of asteroid fragment
+    pub fn fragment_indices() -> Vec<u32> {
+        vec![0, 1, 2]
+    }
+
+    /// Constructs list of indices
+    pub fn indices() -> Vec<u32> {
+        (1..=ASTEROID_SEGMENTS_COUNT)
+            .flat_map(|index| {
+                let next_index = (index + 1) % ASTEROID_SEGMENTS_COUNT;
+
+                [0, index as u32, next_index.max(1) as u32]
+            })
+            .collect()
+    }
+}
@@ -12,15 +12,16 @@ layout (location = 0) in vec2 in_position;
 
 layout (binding = 0) uniform Model {
     vec3 color;
+    float alpha;
     mat4 matrix;
 } model;
 
-layout (location = 0) out vec3 out_color;
+layout (location = 0) out vec4 out_color;
 
 void main() {
     gl_Position = model.matrix * vec4(in_position, 0.0, 1.0);
 
-    out_color = model.color;
+    out_color = vec4(model.color, model.alpha);
 }
         "
         }
@@ -33,12 +34,233 @@ void main() {
             src: r"
 #version 460
 
-layout (location = 0) in vec3 in_color;
+layout (location = 0) in vec4 in_color;
+
+layout (location = 0) out vec4 out_color;
+
+void main() {
+    out_color = in_color;
+}
+        "
+        }
+    }
+}
+
+/// Instanced entity shader: model data is per-instance input instead of uniform
+pub mod instanced_entity {
+
+    /// Vertex shader
+    pub mod vs {
+        vulkano_shaders::shader! {
+            ty: "vertex",
+            src: r"
+#version 460
+
+layout (location = 0) in vec2 in_position;
+
+layout (location = 1) in vec3 in_color;
+layout (location = 2) in float in_alpha;
+layout (location = 3) in mat4 in_matrix;
+
+layout (location = 0) out vec4 out_color;
+
+void main() {
+    gl_Position = in_matrix * vec4(in_position, 0.0, 1.0);
+
+    out_color = vec4(in_color, in_alpha);
+}
+        "
+        }
+    }
+}
+
+/// Sprite shader: textured entity
+pub mod sprite {
+
+    /// Vertex shader
+    pub mod vs {
+        vulkano_shaders::shader! {
+            ty: "vertex",
+            src: r"
+#version 460
+
+layout (location = 0) in vec2 in_position;
+layout (location = 1) in vec2 in_uv;
+
+layout (binding = 0) uniform Model {
+    vec3 color;
+    float alpha;
+    mat4 matrix;
+} model;
+
+layout (location = 0) out vec4 out_color;
+layout (location = 1) out vec2 out_uv;
+
+void main() {
+    gl_Position = model.matrix * vec4(in_position, 0.0, 1.0);
+
+    out_color = vec4(model.color, model.alpha);
+    out_uv = in_uv;
+}
+        "
+        }
+    }
+
+    /// Fragment shader
+    pub mod fs {
+        vulkano_shaders::shader! {
+            ty: "fragment",
+            src: r"
+#version 460
+
+layout (location = 0) in vec4 in_color;
+layout (location = 1) in vec2 in_uv;
+
+layout (set = 1, binding = 0) uniform sampler2D texture_sampler;
+
+layout (location = 0) out vec4 out_color;
+
+void main() {
+    out_color = texture(texture_sampler, in_uv) * in_color;
+}
+        "
+        }
+    }
+}
+
+/// Post-processing shader: full-screen vignette over sampled scene image
+pub mod post_process {
+
+    /// Vertex shader
+    pub mod vs {
+        vulkano_shaders::shader! {
+            ty: "vertex",
+            src: r"
+#version 460
+
+layout (location = 0) in vec2 in_position;
+layout (location = 1) in vec2 in_uv;
+
+layout (location = 0) out vec2 out_uv;
+
+void main() {
+    gl_Position = vec4(in_position, 0.0, 1.0);
+
+    out_uv = in_uv;
+}
+        "
+        }
+    }
+
+    /// Fragment shader
+    pub mod fs {
+        vulkano_shaders::shader! {
+            ty: "fragment",
+            src: r"
+#version 460
+
+layout (location = 0) in vec2 in_uv;
+
+layout (set = 0, binding = 0) uniform sampler2D scene_sampler;
+
+layout (set = 0, binding = 1) uniform PostProcess {
+    float intensity;
+} post_process;
+
+layout (location = 0) out vec4 out_color;
+
+void main() {
+    vec4 color = texture(scene_sampler, in_uv);
+
+    vec2 offset = in_uv - vec2(0.5);
+    float vignette = clamp(1.0 - post_process.intensity * 2.0 * dot(offset, offset), 0.0, 1.0);
+
+    out_color = vec4(color.rgb * vignette, 1.0);
+}
+        "
+        }
+    }
+}
+
+/// Line shader: draws lines in clip space with solid color, used by debug overlays
+pub mod line {
+
+    /// Vertex shader
+    pub mod vs {
+        vulkano_shaders::shader! {
+            ty: "vertex",
+            src: r"
+#version 460
+
+layout (location = 0) in vec2 in_position;
+
+void main() {
+    gl_Position = vec4(in_position, 0.0, 1.0);
+}
+        "
+        }
+    }
+
+    /// Fragment shader
+    pub mod fs {
+        vulkano_shaders::shader! {
+            ty: "fragment",
+            src: r"
+#version 460
+
+layout (location = 0) out vec4 out_color;
+
+void main() {
+    out_color = vec4(0.0, 1.0, 0.0, 1.0);
+}
+        "
+        }
+    }
+}
+
+/// Text shader: draws glyph quads in clip space, sampled from font atlas and tinted with vertex color
+pub mod text {
+
+    /// Vertex shader
+    pub mod vs {
+        vulkano_shaders::shader! {
+            ty: "vertex",
+            src: r"
+#version 460
+
+layout (location = 0) in vec2 in_position;
+layout (location = 1) in vec2 in_uv;
+layout (location = 2) in vec4 in_color;
+
+layout (location = 0) out vec4 out_color;
+layout (location = 1) out vec2 out_uv;
+
+void main() {
+    gl_Position = vec4(in_position, 0.0, 1.0);
+
+    out_color = in_color;
+    out_uv = in_uv;
+}
+        "
+        }
+    }
+
+    /// Fragment shader
+    pub mod fs {
+        vulkano_shaders::shader! {
+            ty: "fragment",
+            src: r"
+#version 460
+
+layout (location = 0) in vec4 in_color;
+layout (location = 1) in vec2 in_uv;
+
+layout (set = 0, binding = 0) uniform sampler2D font_sampler;
 
 layout (location = 0) out vec4 out_color;
 
 void main() {
-    out_color = vec4(in_color, 1.0);
+    out_color = texture(font_sampler, in_uv) * in_color;
 }
         "
         }
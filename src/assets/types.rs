@@ -1,4 +1,4 @@
-use glam::{Mat4, Vec2, Vec3};
+use glam::{Mat4, Vec2, Vec3, Vec4};
 use vulkano::{buffer::BufferContents, pipeline::graphics::vertex_input::Vertex as VertexTrait};
 
 /// Type of vertex
@@ -9,6 +9,35 @@ pub struct Vertex {
     #[format(R32G32_SFLOAT)]
     #[name("in_position")]
     pub position: Vec2,
+    /// Texture coordinates of vertex
+    #[format(R32G32_SFLOAT)]
+    #[name("in_uv")]
+    pub uv: Vec2,
+}
+
+/// Type of text vertex: glyph quads of all texts are drawn at once, so color is per vertex
+#[derive(Clone, Default, BufferContents, VertexTrait)]
+#[repr(C)]
+pub struct TextVertex {
+    /// Position of vertex in clip space
+    #[format(R32G32_SFLOAT)]
+    #[name("in_position")]
+    pub position: Vec2,
+    /// Texture coordinates of vertex in font atlas
+    #[format(R32G32_SFLOAT)]
+    #[name("in_uv")]
+    pub uv: Vec2,
+    /// Color of vertex
+    #[format(R32G32B32A32_SFLOAT)]
+    #[name("in_color")]
+    pub color: Vec4,
+}
+
+/// Type of post-processing data
+#[derive(Clone, BufferContents)]
+#[repr(C)]
+pub struct PostProcess {
+    pub intensity: f32,
 }
 
 /// Type of model data
@@ -16,5 +45,6 @@ pub struct Vertex {
 #[repr(C)]
 pub struct Model {
     pub color: Vec3,
+    pub alpha: f32,
     pub matrix: Mat4,
 }
@@ -1,18 +1,21 @@
 use std::{
     collections::BTreeMap,
+    fmt,
     sync::{
         Arc, RwLock,
-        atomic::{AtomicUsize, Ordering},
+        atomic::{AtomicBool, AtomicUsize, Ordering},
     },
 };
 
-use crate::{handle, input};
+use crate::{diagnostics, handle, input};
 
 /// Enumeration of possible command arguments
 #[derive(Clone, Copy)]
 pub enum Arg {
     /// Argument is an input key
     Input(input::Key, input::State),
+    /// Argument is an input axis with its value
+    Axis(input::Axis, f32),
 }
 
 impl Arg {
@@ -20,6 +23,15 @@ impl Arg {
     pub fn to_input(&self) -> Option<(input::Key, input::State)> {
         match self {
             Arg::Input(key, state) => Some((*key, *state)),
+            _ => None,
+        }
+    }
+
+    /// Returns argument content if it is [Arg::Axis]
+    pub fn to_axis(self) -> Option<(input::Axis, f32)> {
+        match self {
+            Arg::Axis(axis, value) => Some((axis, value)),
+            _ => None,
         }
     }
 }
@@ -30,6 +42,12 @@ impl From<(input::Key, input::State)> for Arg {
     }
 }
 
+impl From<(input::Axis, f32)> for Arg {
+    fn from((axis, value): (input::Axis, f32)) -> Self {
+        Self::Axis(axis, value)
+    }
+}
+
 /// Trait of a command
 pub trait Command: Send + Sync {
     /// Invokes command with specified list of arguments
@@ -68,15 +86,33 @@ unsafe impl<S> Send for StatefulCommand<S> where S: Send + Sync {}
 
 unsafe impl<S> Sync for StatefulCommand<S> where S: Send + Sync {}
 
+/// Enumeration of [Commands::try_invoke] errors
+#[derive(Debug)]
+pub enum InvokeError {
+    /// There is no command registered with such name
+    Unknown(String),
+}
+
+impl fmt::Display for InvokeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InvokeError::Unknown(name) => write!(f, "unknown command: {}", name),
+        }
+    }
+}
+
+impl std::error::Error for InvokeError {}
+
 /// INTERNAL: command identifier type alias
 type CommandId = usize;
 /// INTERNAL: list of commands
-type CommandList = BTreeMap<CommandId, Box<dyn Command>>;
+type CommandList = BTreeMap<CommandId, Arc<dyn Command>>;
 
 /// INTERNAL: command infrastructure state
 #[derive(Default)]
 struct State {
     commands: RwLock<BTreeMap<String, CommandList>>,
+    aliases: RwLock<BTreeMap<String, String>>,
     id_counter: AtomicUsize,
 }
 
@@ -91,7 +127,7 @@ impl State {
 
         let id = self.id_counter.fetch_add(1, Ordering::Relaxed);
 
-        command_list.insert(id, Box::new(command));
+        command_list.insert(id, Arc::new(command));
 
         id
     }
@@ -105,24 +141,65 @@ impl State {
         }
     }
 
-    /// INTERNAL: invokes command
-    fn invoke(&self, name: &String, args: &[Arg]) {
-        let commands = self.commands.read().unwrap();
+    /// INTERNAL: adds alias of command
+    fn add_alias(&self, alias: String, name: String) {
+        let mut aliases = self.aliases.write().unwrap();
 
-        if let Some(command_list) = commands.get(name) {
-            for (_, command) in command_list.iter() {
-                if !command.invoke(&args) {
-                    return;
-                }
-            }
+        aliases.insert(alias, name);
+    }
+
+    /// INTERNAL: removes alias of command
+    fn remove_alias(&self, alias: &String) {
+        let mut aliases = self.aliases.write().unwrap();
+
+        aliases.remove(alias);
+    }
+
+    /// INTERNAL: invokes command, returns [None] if there is no such command or result of the chain
+    fn invoke(&self, name: &String, args: &[Arg]) -> Option<bool> {
+        let name = self
+            .aliases
+            .read()
+            .unwrap()
+            .get(name)
+            .cloned()
+            .unwrap_or_else(|| name.clone());
+
+        // NOTE: commands are cloned out and locks are released before invocation, so command may add or remove
+        // commands and aliases
+        let command_list: Vec<_> = self
+            .commands
+            .read()
+            .unwrap()
+            .get(&name)
+            .map(|command_list| command_list.values().cloned().collect())
+            .unwrap_or_default();
+
+        if command_list.is_empty() {
+            return None;
         }
+
+        let result = command_list.iter().all(|command| command.invoke(args));
+
+        Some(result)
+    }
+
+    /// INTERNAL: returns names of registered commands
+    fn list(&self) -> Vec<String> {
+        let commands = self.commands.read().unwrap();
+
+        commands
+            .iter()
+            .filter(|(_, command_list)| !command_list.is_empty())
+            .map(|(name, _)| name.clone())
+            .collect()
     }
 }
 
 /// Commands infrastructure
-#[derive(Default)]
 pub struct Commands {
     state: Arc<State>,
+    report_unknown: AtomicBool,
 }
 
 impl Commands {
@@ -144,11 +221,171 @@ impl Commands {
         drop.into()
     }
 
+    /// Adds alias: invocation of alias name invokes command with target name
+    #[must_use = "returned handle removes alias on drop"]
+    pub fn add_alias<A, N>(&self, alias: A, name: N) -> handle::Handle
+    where
+        A: Into<String>,
+        N: Into<String>,
+    {
+        let alias = alias.into();
+        self.state.add_alias(alias.clone(), name.into());
+
+        let state = self.state.clone();
+        let drop = move || {
+            state.remove_alias(&alias);
+        };
+
+        drop.into()
+    }
+
     /// Invokes command
     pub fn invoke<N>(&self, name: N, args: &[Arg])
     where
         N: Into<String>,
     {
-        self.state.invoke(&name.into(), args);
+        if let Err(error) = self.try_invoke(name, args)
+            && self.report_unknown.load(Ordering::Relaxed)
+        {
+            diagnostics::warning(error);
+        }
+    }
+
+    /// Returns names of registered commands in alphabetical order
+    pub fn list(&self) -> Vec<String> {
+        self.state.list()
+    }
+
+    /// Enables or disables reporting of unknown commands in [Commands::invoke]
+    pub fn set_report_unknown(&self, report_unknown: bool) {
+        self.report_unknown.store(report_unknown, Ordering::Relaxed);
+    }
+
+    /// Invokes command and returns result of the command chain: `false` if any command interrupted the chain
+    pub fn try_invoke<N>(&self, name: N, args: &[Arg]) -> Result<bool, InvokeError>
+    where
+        N: Into<String>,
+    {
+        let name = name.into();
+
+        self.state
+            .invoke(&name, args)
+            .ok_or(InvokeError::Unknown(name))
+    }
+}
+
+impl Default for Commands {
+    fn default() -> Self {
+        Self {
+            state: Default::default(),
+            report_unknown: AtomicBool::new(true),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn command_returning_false_interrupts_chain() {
+        let commands: Commands = Default::default();
+        let invoked: Arc<AtomicUsize> = Default::default();
+
+        let _handles = [
+            commands.add(
+                "fire",
+                StatefulCommand::new(invoked.clone(), |_, invoked| {
+                    invoked.fetch_add(1, Ordering::Relaxed);
+
+                    false
+                }),
+            ),
+            commands.add(
+                "fire",
+                StatefulCommand::new(invoked.clone(), |_, invoked| {
+                    invoked.fetch_add(1, Ordering::Relaxed);
+
+                    true
+                }),
+            ),
+        ];
+
+        assert!(!commands.try_invoke("fire", &[]).unwrap());
+        assert_eq!(invoked.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn list_contains_registered_commands_in_alphabetical_order() {
+        let commands: Commands = Default::default();
+
+        let restart = commands.add("restart", StatefulCommand::new((), |_, _| true));
+        let _pause = commands.add("pause", StatefulCommand::new((), |_, _| true));
+
+        assert_eq!(commands.list(), ["pause", "restart"]);
+
+        drop(restart);
+
+        assert_eq!(commands.list(), ["pause"]);
+    }
+
+    #[test]
+    fn unknown_command_is_reported_only_when_enabled() {
+        const COMMAND: &str = "report_unknown_test";
+
+        let commands: Commands = Default::default();
+        let reported: Arc<AtomicUsize> = Default::default();
+
+        let _hook = diagnostics::add_hook({
+            let reported = reported.clone();
+
+            move |level, message| {
+                if level == diagnostics::Level::Warning && message.contains(COMMAND) {
+                    reported.fetch_add(1, Ordering::Relaxed);
+                }
+            }
+        });
+
+        commands.invoke(COMMAND, &[]);
+        assert_eq!(reported.load(Ordering::Relaxed), 1);
+
+        commands.set_report_unknown(false);
+        commands.invoke(COMMAND, &[]);
+        assert_eq!(reported.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn command_adding_command_and_alias_during_invocation_does_not_deadlock() {
+        const TIMEOUT: std::time::Duration = std::time::Duration::from_secs(5);
+
+        let (tx, rx) = std::sync::mpsc::channel();
+
+        std::thread::spawn(move || {
+            let commands: Arc<Commands> = Default::default();
+            let handles: Arc<std::sync::Mutex<Vec<handle::Handle>>> = Default::default();
+
+            let _register = commands.add(
+                "register",
+                StatefulCommand::new(
+                    (commands.clone(), handles.clone()),
+                    |_, (commands, handles)| {
+                        let mut handles = handles.lock().unwrap();
+
+                        handles.push(
+                            commands.add("registered", StatefulCommand::new((), |_, _| true)),
+                        );
+                        handles.push(commands.add_alias("alias", "registered"));
+
+                        true
+                    },
+                ),
+            );
+
+            commands.invoke("register", &[]);
+
+            let _ = tx.send(commands.try_invoke("alias", &[]).is_ok());
+        });
+
+        assert!(rx.recv_timeout(TIMEOUT).unwrap());
     }
 }
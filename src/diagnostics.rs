@@ -0,0 +1,128 @@
+use std::{
+    collections::BTreeMap,
+    fmt,
+    sync::{
+        RwLock,
+        atomic::{AtomicUsize, Ordering},
+    },
+};
+
+use crate::handle;
+
+/// Severity of diagnostic message
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Level {
+    /// Message is informational
+    Info,
+    /// Something went wrong, but application is able to continue
+    Warning,
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Level::Info => write!(f, "info"),
+            Level::Warning => write!(f, "warning"),
+        }
+    }
+}
+
+/// INTERNAL: type of diagnostics hook
+type Hook = Box<dyn Fn(Level, &str) + Send + Sync>;
+
+/// INTERNAL: counter of hook identifiers
+static HOOK_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// INTERNAL: registered hooks by their identifiers
+static HOOKS: RwLock<BTreeMap<usize, Hook>> = RwLock::new(BTreeMap::new());
+
+/// Adds hook, which receives every reported message; while there are no hooks, messages are written to stderr
+#[must_use = "returned handle removes hook on drop"]
+pub fn add_hook<H>(hook: H) -> handle::Handle
+where
+    H: Fn(Level, &str) + Send + Sync + 'static,
+{
+    let hook_id = HOOK_COUNTER.fetch_add(1, Ordering::Relaxed);
+
+    HOOKS.write().unwrap().insert(hook_id, Box::new(hook));
+
+    let drop = move || {
+        HOOKS.write().unwrap().remove(&hook_id);
+    };
+
+    drop.into()
+}
+
+/// Reports message with specified [Level]
+pub fn report<M>(level: Level, message: M)
+where
+    M: fmt::Display,
+{
+    // NOTE: hooks must not report messages themselves, otherwise they may deadlock on pending hook removal
+    let hooks = HOOKS.read().unwrap_or_else(|error| error.into_inner());
+
+    if hooks.is_empty() {
+        eprintln!("{}: {}", level, message);
+
+        return;
+    }
+
+    let message = message.to_string();
+
+    for hook in hooks.values() {
+        hook(level, &message);
+    }
+}
+
+/// Reports message with [Level::Info]
+pub fn info<M>(message: M)
+where
+    M: fmt::Display,
+{
+    report(Level::Info, message);
+}
+
+/// Reports message with [Level::Warning]
+pub fn warning<M>(message: M)
+where
+    M: fmt::Display,
+{
+    report(Level::Warning, message);
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use super::*;
+
+    #[test]
+    fn hook_receives_messages_until_dropped() {
+        let messages: Arc<Mutex<Vec<(Level, String)>>> = Default::default();
+
+        let hook = add_hook({
+            let messages = messages.clone();
+
+            move |level, message| {
+                if message.starts_with("diagnostics test") {
+                    messages.lock().unwrap().push((level, message.to_string()));
+                }
+            }
+        });
+
+        info("diagnostics test 1");
+        warning(format_args!("diagnostics test {}", 2));
+
+        drop(hook);
+
+        warning("diagnostics test 3");
+
+        assert_eq!(
+            *messages.lock().unwrap(),
+            [
+                (Level::Info, String::from("diagnostics test 1")),
+                (Level::Warning, String::from("diagnostics test 2")),
+            ]
+        );
+    }
+}
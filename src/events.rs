@@ -1,13 +1,22 @@
 use std::{
-    collections::BTreeMap,
+    collections::{BTreeMap, BTreeSet},
+    iter,
+    mem::{self, Discriminant},
     sync::{
         Arc, Mutex,
         atomic::{AtomicUsize, Ordering},
         mpsc,
     },
+    time::Duration,
 };
 
-use crate::{game::entities::EntityId, handle, workers};
+use glam::{Vec2, Vec3};
+
+use crate::{
+    diagnostics,
+    game::{entities::EntityId, players::PlayerId},
+    handle, workers,
+};
 
 /// Enumeration of possible events
 #[derive(Clone, Debug, PartialEq)]
@@ -18,6 +27,80 @@ pub enum Event {
 
     /// Entity was destroyed
     EntityDestroyed(EntityId),
+
+    /// Asteroid was destroyed by player
+    AsteroidDestroyed {
+        /// Player who destroyed asteroid
+        by: PlayerId,
+        /// Size of destroyed asteroid
+        size: f32,
+    },
+
+    /// Player fired weapon
+    WeaponFired {
+        /// Player who fired weapon
+        by: PlayerId,
+        /// Count of fired bullets
+        count: u32,
+    },
+
+    /// Bullet of player hit asteroid or spacecraft
+    BulletHit {
+        /// Player who fired bullet
+        by: PlayerId,
+    },
+
+    /// Score of player was changed
+    ScoreChanged {
+        /// Player whose score was changed
+        player: PlayerId,
+        /// New score of player
+        new_score: u32,
+    },
+
+    /// Player reached score milestone and got bonus life
+    BonusLife {
+        /// Player who got bonus life
+        player: PlayerId,
+        /// Count of player lives after bonus
+        lives: u32,
+    },
+
+    /// Spacecraft of player was destroyed
+    PlayerDied {
+        /// Player whose spacecraft was destroyed
+        player: PlayerId,
+    },
+
+    /// All players are out of lives
+    GameOver,
+
+    /// Something hit hard: spacecraft was destroyed or asteroid was destroyed
+    Impact {
+        /// Position of impact
+        position: Vec2,
+        /// Strength of impact
+        strength: f32,
+    },
+
+    /// Entity was destroyed with explosion: spacecraft or asteroid
+    EntityExploded {
+        /// Position of exploded entity
+        position: Vec2,
+        /// Size of exploded entity, explosion fragments are scaled by it
+        size: f32,
+        /// Color of exploded entity
+        color: Vec3,
+    },
+
+    /// Entities started to collide, contains both collided entities
+    CollisionStarted(BTreeSet<EntityId>),
+
+    /// Entities finished to collide, contains both previously collided entities
+    CollisionFinished(BTreeSet<EntityId>),
+
+    /// Window was resized, contains new physical size of window
+    WindowResized([u32; 2]),
 }
 
 /// Event sender
@@ -27,18 +110,20 @@ pub struct Sender {
 }
 
 impl Sender {
-    /// Sends event
+    /// Sends event, event is dropped and reported if event bus is down (e.g. during shutdown)
     pub fn send(&self, event: Event) {
-        if let Err(_) = self.tx.send(event) {
-            // TODO: notify
+        if let Err(error) = self.tx.send(event) {
+            diagnostics::warning(format_args!(
+                "event {:?} is dropped, event bus is down",
+                error.0
+            ));
         }
     }
 }
 
-/// INTERNAL: event handler
-struct Handler(Box<dyn Fn(&Event)>);
-
-unsafe impl Send for Handler {}
+/// INTERNAL: event handler, shared so handlers can be invoked without holding handlers lock
+#[derive(Clone)]
+struct Handler(Arc<dyn Fn(&Event) + Send + Sync>);
 
 /// Events infrastructure
 pub struct Events {
@@ -60,12 +145,12 @@ impl Events {
     #[must_use = "returned handle removes handler delegate on drop"]
     pub fn add_handler<F>(&self, delegate: F) -> handle::Handle
     where
-        F: Fn(&Event) + 'static,
+        F: Fn(&Event) + Send + Sync + 'static,
     {
         let handler_id = self.handler_counter.fetch_add(1, Ordering::Relaxed);
 
         let mut handlers = self.handlers.lock().unwrap();
-        handlers.insert(handler_id, Handler(Box::new(delegate)));
+        handlers.insert(handler_id, Handler(Arc::new(delegate)));
 
         let handlers = self.handlers.clone();
         let drop = move || {
@@ -74,6 +159,49 @@ impl Events {
 
         drop.into()
     }
+
+    /// Adds handler delegate, which is invoked only for events of specific variant, see [mem::discriminant]
+    #[must_use = "returned handle removes handler delegate on drop"]
+    pub fn add_handler_for<F>(&self, variant: Discriminant<Event>, delegate: F) -> handle::Handle
+    where
+        F: Fn(&Event) + Send + Sync + 'static,
+    {
+        self.add_handler(move |event| {
+            if mem::discriminant(event) == variant {
+                delegate(event);
+            }
+        })
+    }
+
+    /// Delivers pending events to handlers on calling thread, used on shutdown after events worker is stopped;
+    /// events sent by handlers during flush are delivered too, up to limited count of rounds
+    pub fn flush(&self) {
+        const MAX_ROUNDS: usize = 16;
+
+        for _ in 0..MAX_ROUNDS {
+            let messages: Vec<_> = self.rx.lock().unwrap().try_iter().collect();
+
+            if messages.is_empty() {
+                return;
+            }
+
+            self.dispatch(messages);
+        }
+
+        diagnostics::warning("events are still pending after flush");
+    }
+
+    /// INTERNAL: delivers events to all handlers
+    fn dispatch(&self, messages: Vec<Event>) {
+        // NOTE: handlers are cloned out and lock is released before delivery, so handler may add or remove handlers during dispatch
+        let handlers: Vec<Handler> = self.handlers.lock().unwrap().values().cloned().collect();
+
+        for message in messages {
+            for Handler(delegate) in handlers.iter() {
+                delegate(&message);
+            }
+        }
+    }
 }
 
 impl Default for Events {
@@ -91,15 +219,21 @@ impl Default for Events {
 
 /// INTERNAL: events worker thread function
 fn worker_func(events: &Events) {
+    const TIMEOUT: Duration = Duration::from_millis(10);
+
     let rx = events.rx.lock().unwrap();
-    let messages: Vec<_> = rx.try_iter().collect();
 
-    let handlers = events.handlers.lock().unwrap();
-    for message in messages {
-        for (_, Handler(delegate)) in handlers.iter() {
-            delegate(&message);
-        }
-    }
+    // NOTE: worker blocks until first message arrives, so it does not spin while idle; timeout lets it observe cancellation
+    let first = match rx.recv_timeout(TIMEOUT) {
+        Ok(message) => message,
+        Err(_) => return,
+    };
+
+    let messages: Vec<_> = iter::once(first).chain(rx.try_iter()).collect();
+
+    drop(rx);
+
+    events.dispatch(messages);
 }
 
 /// Spawns events worker thread
@@ -110,3 +244,60 @@ pub fn spawn_worker(workers: &workers::Workers, events: Arc<Events>) -> handle::
         }
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::Weak, thread};
+
+    use super::*;
+
+    #[test]
+    fn handler_adding_handler_during_dispatch_does_not_deadlock() {
+        const TIMEOUT: Duration = Duration::from_secs(5);
+
+        let events: Arc<Events> = Default::default();
+        let added: Arc<Mutex<Vec<handle::Handle>>> = Default::default();
+        let delivered: Arc<AtomicUsize> = Default::default();
+
+        let _handler = events.add_handler({
+            let events: Weak<Events> = Arc::downgrade(&events);
+            let added = added.clone();
+            let delivered = delivered.clone();
+
+            move |_| {
+                let events = events.upgrade().unwrap();
+                let delivered = delivered.clone();
+
+                let handler = events.add_handler(move |_| {
+                    delivered.fetch_add(1, Ordering::Relaxed);
+                });
+
+                added.lock().unwrap().push(handler);
+            }
+        });
+
+        let (done_tx, done_rx) = mpsc::channel();
+
+        thread::spawn({
+            let events = events.clone();
+
+            move || {
+                events.get_sender().send(Event::GameOver);
+                events.flush();
+
+                let _ = done_tx.send(());
+            }
+        });
+
+        done_rx
+            .recv_timeout(TIMEOUT)
+            .expect("dispatch should not deadlock");
+
+        assert_eq!(added.lock().unwrap().len(), 1);
+
+        events.get_sender().send(Event::GameOver);
+        events.flush();
+
+        assert_eq!(delivered.load(Ordering::Relaxed), 1);
+    }
+}
@@ -0,0 +1,57 @@
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+
+/// Game clock: shared pause state and tick counter of game workers
+#[derive(Default)]
+pub struct Clock {
+    paused: AtomicBool,
+    tick: AtomicU64,
+}
+
+impl Clock {
+    /// Pauses game simulation
+    pub fn pause(&self) {
+        self.paused.store(true, Ordering::Relaxed);
+    }
+
+    /// Resumes game simulation
+    pub fn resume(&self) {
+        self.paused.store(false, Ordering::Relaxed);
+    }
+
+    /// Checks if game simulation is paused
+    pub fn is_paused(&self) -> bool {
+        self.paused.load(Ordering::Relaxed)
+    }
+
+    /// Returns current simulation tick: count of simulation updates since start
+    pub fn tick(&self) -> u64 {
+        self.tick.load(Ordering::Relaxed)
+    }
+
+    /// Advances simulation tick, unless game simulation is paused; returns current simulation tick
+    pub fn advance(&self) -> u64 {
+        if self.is_paused() {
+            self.tick()
+        } else {
+            self.tick.fetch_add(1, Ordering::Relaxed) + 1
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tick_does_not_advance_while_paused() {
+        let clock: Clock = Default::default();
+
+        assert_eq!(clock.advance(), 1);
+
+        clock.pause();
+        assert_eq!(clock.advance(), 1);
+
+        clock.resume();
+        assert_eq!(clock.advance(), 2);
+    }
+}
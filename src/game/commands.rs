@@ -1,9 +1,15 @@
 use std::sync::Arc;
 
 use crate::{
-    game::controller::{
-        CameraZoomDirection, Controller, SpacecraftAccelerationDirection,
-        SpacecraftInclineDirection,
+    diagnostics,
+    game::{
+        clock::Clock,
+        controller::{
+            CameraPanDirection, CameraZoomDirection, Controller, SpacecraftAccelerationDirection,
+            SpacecraftInclineDirection,
+        },
+        ecs::ECS,
+        metrics::MetricsExport,
     },
     input,
 };
@@ -15,6 +21,55 @@ pub fn camera_follow_command(_: &[crate::commands::Arg], controller: &Arc<Contro
     true
 }
 
+/// Dispatches `camera_detach` to controller
+pub fn camera_detach_command(_: &[crate::commands::Arg], controller: &Arc<Controller>) -> bool {
+    controller.camera_detach_toggle();
+
+    true
+}
+
+/// Dispatches `camera_pan_up` to controller
+pub fn camera_pan_up_command(args: &[crate::commands::Arg], controller: &Arc<Controller>) -> bool {
+    dispatch_camera_pan(args, controller, CameraPanDirection::Up)
+}
+
+/// Dispatches `camera_pan_down` to controller
+pub fn camera_pan_down_command(
+    args: &[crate::commands::Arg],
+    controller: &Arc<Controller>,
+) -> bool {
+    dispatch_camera_pan(args, controller, CameraPanDirection::Down)
+}
+
+/// Dispatches `camera_pan_left` to controller
+pub fn camera_pan_left_command(
+    args: &[crate::commands::Arg],
+    controller: &Arc<Controller>,
+) -> bool {
+    dispatch_camera_pan(args, controller, CameraPanDirection::Left)
+}
+
+/// Dispatches `camera_pan_right` to controller
+pub fn camera_pan_right_command(
+    args: &[crate::commands::Arg],
+    controller: &Arc<Controller>,
+) -> bool {
+    dispatch_camera_pan(args, controller, CameraPanDirection::Right)
+}
+
+/// INTERNAL: starts panning in direction on key press and stops it on key release
+fn dispatch_camera_pan(
+    args: &[crate::commands::Arg],
+    controller: &Arc<Controller>,
+    direction: CameraPanDirection,
+) -> bool {
+    let (_, state) = args[0].to_input().expect("invalid usage of camera_pan");
+
+    controller.camera_pan(direction, matches!(state, input::State::Pressed));
+
+    true
+}
+
 /// Dispatches `camera_zoom_out` to controller
 pub fn camera_zoom_out_command(_: &[crate::commands::Arg], controller: &Arc<Controller>) -> bool {
     controller.camera_zoom(CameraZoomDirection::Out);
@@ -29,6 +84,15 @@ pub fn camera_zoom_in_command(_: &[crate::commands::Arg], controller: &Arc<Contr
     true
 }
 
+/// Dispatches `camera_zoom` to controller: scrolling up zooms in
+pub fn camera_zoom_command(args: &[crate::commands::Arg], controller: &Arc<Controller>) -> bool {
+    let (_, value) = args[0].to_axis().expect("invalid usage of camera_zoom");
+
+    controller.camera_zoom_by(-value);
+
+    true
+}
+
 /// Dispatches `player_forward` to controller
 pub fn player_forward_command(args: &[crate::commands::Arg], controller: &Arc<Controller>) -> bool {
     let (_, state) = args[0].to_input().expect("invalid usage of player_forward");
@@ -127,3 +191,59 @@ pub fn player_weapon_fire_command(
 
     true
 }
+
+/// Dispatches `player_switch_weapon` to controller
+pub fn player_switch_weapon_command(
+    _: &[crate::commands::Arg],
+    controller: &Arc<Controller>,
+) -> bool {
+    controller.player_switch_weapon();
+
+    true
+}
+
+/// Pauses game simulation
+pub fn pause_command(_: &[crate::commands::Arg], clock: &Arc<Clock>) -> bool {
+    clock.pause();
+
+    true
+}
+
+/// Resumes game simulation
+pub fn resume_command(_: &[crate::commands::Arg], clock: &Arc<Clock>) -> bool {
+    clock.resume();
+
+    true
+}
+
+/// Dispatches `restart` to controller
+pub fn restart_command(_: &[crate::commands::Arg], controller: &Arc<Controller>) -> bool {
+    controller.restart();
+
+    true
+}
+
+/// Prints registered ECS systems in invocation order to stdout
+pub fn dump_systems_command(_: &[crate::commands::Arg], ecs: &Arc<ECS>) -> bool {
+    for (index, (priority, name)) in ecs.systems().iter().enumerate() {
+        println!("#{} {} (priority: {})", index, name, priority);
+    }
+
+    true
+}
+
+/// Toggles export of performance metrics of session
+pub fn toggle_metrics_export_command(
+    _: &[crate::commands::Arg],
+    export: &Arc<MetricsExport>,
+) -> bool {
+    let state = if export.toggle() {
+        "enabled"
+    } else {
+        "disabled"
+    };
+
+    diagnostics::info(format_args!("metrics export is {}", state));
+
+    true
+}
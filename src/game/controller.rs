@@ -1,7 +1,8 @@
 use std::{
+    collections::BTreeSet,
     f32::consts::PI,
     ops::{Div, Mul},
-    sync::{Arc, RwLock},
+    sync::{Arc, Mutex, RwLock},
 };
 
 use glam::Vec2;
@@ -10,7 +11,7 @@ use crate::{
     consts::VEC2_RIGHT,
     game::{
         ecs::ECS,
-        entities::EntityId,
+        entities::{CameraTarget, EntityId, consts},
         players::{PlayerId, Players},
     },
 };
@@ -21,6 +22,27 @@ pub enum CameraZoomDirection {
     Out,
 }
 
+/// [crate::game::entities::Camera] pan direction
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum CameraPanDirection {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl CameraPanDirection {
+    /// INTERNAL: unit vector of direction
+    fn vector(self) -> Vec2 {
+        match self {
+            CameraPanDirection::Up => Vec2::Y,
+            CameraPanDirection::Down => Vec2::NEG_Y,
+            CameraPanDirection::Left => Vec2::NEG_X,
+            CameraPanDirection::Right => Vec2::X,
+        }
+    }
+}
+
 /// [crate::game::entities::Spacecraft] acceleration direction
 pub enum SpacecraftAccelerationDirection {
     Forward,
@@ -40,6 +62,7 @@ pub struct Controller {
 
     player_id: RwLock<Option<PlayerId>>,
     camera_id: RwLock<Option<EntityId>>,
+    camera_pan: Mutex<BTreeSet<CameraPanDirection>>,
 }
 
 impl Controller {
@@ -50,6 +73,7 @@ impl Controller {
             players,
             player_id: Default::default(),
             camera_id: Default::default(),
+            camera_pan: Default::default(),
         };
 
         Arc::new(controller)
@@ -60,6 +84,11 @@ impl Controller {
         *self.player_id.write().unwrap() = player_id;
     }
 
+    /// Returns [PlayerId] of controllable player
+    pub fn player(&self) -> Option<PlayerId> {
+        *self.player_id.read().unwrap()
+    }
+
     /// Sets [EntityId] of controllable [crate::game::entities::Camera] entity
     pub fn set_camera(&self, camera_id: Option<EntityId>) {
         *self.camera_id.write().unwrap() = camera_id;
@@ -76,10 +105,48 @@ impl Controller {
         }
     }
 
+    /// Toggles current camera between following current player and free mode
+    pub fn camera_detach_toggle(&self) {
+        let player_id = *self.player_id.read().unwrap();
+
+        if let Some(camera_id) = *self.camera_id.read().unwrap() {
+            self.ecs.write().modify(camera_id, |entity| {
+                if let Some(camera) = entity.camera_mut() {
+                    camera.target = match (camera.target, player_id) {
+                        (CameraTarget::Free, Some(player_id)) => CameraTarget::Player(player_id),
+                        _ => CameraTarget::Free,
+                    };
+                }
+            });
+        }
+    }
+
+    /// Starts or stops panning of current camera in direction: camera is panned in combination of active
+    /// directions, and is moved only in free mode
+    pub fn camera_pan(&self, direction: CameraPanDirection, active: bool) {
+        let pan: Vec2 = {
+            let mut directions = self.camera_pan.lock().unwrap();
+
+            if active {
+                directions.insert(direction);
+            } else {
+                directions.remove(&direction);
+            }
+
+            directions.iter().map(|direction| direction.vector()).sum()
+        };
+
+        if let Some(camera_id) = *self.camera_id.read().unwrap() {
+            self.ecs.write().modify(camera_id, |entity| {
+                if let Some(camera) = entity.camera_mut() {
+                    camera.pan = pan;
+                }
+            });
+        }
+    }
+
     /// Controls zoom of current camera
     pub fn camera_zoom(&self, direction: CameraZoomDirection) {
-        const MIN_DISTANCE: f32 = 1.0;
-        const MAX_DISTANCE: f32 = 32.0;
         const DISTANCE_MULTIPLIER: f32 = 2.0;
 
         if let Some(camera_id) = self.camera_id.read().unwrap().clone() {
@@ -90,7 +157,40 @@ impl Controller {
                         CameraZoomDirection::Out => camera.distance.mul(DISTANCE_MULTIPLIER),
                     };
 
-                    camera.distance = camera.distance.clamp(MIN_DISTANCE, MAX_DISTANCE);
+                    camera.distance = camera
+                        .distance
+                        .clamp(consts::CAMERA_MIN_DISTANCE, consts::CAMERA_MAX_DISTANCE);
+                }
+            });
+        }
+    }
+
+    /// Continuously controls zoom of current camera: positive delta moves camera away
+    pub fn camera_zoom_by(&self, delta: f32) {
+        const SENSITIVITY: f32 = 0.25;
+
+        if let Some(camera_id) = *self.camera_id.read().unwrap() {
+            self.ecs.write().modify(camera_id, |entity| {
+                if let Some(camera) = entity.camera_mut() {
+                    camera.distance = (camera.distance * (delta * SENSITIVITY).exp())
+                        .clamp(consts::CAMERA_MIN_DISTANCE, consts::CAMERA_MAX_DISTANCE);
+                }
+            });
+        }
+    }
+
+    /// Shakes current camera by impact: shake is weakened by distance from camera to impact position
+    pub fn camera_shake(&self, position: Vec2, strength: f32) {
+        const IMPACT_RADIUS: f32 = 30.0;
+        const MAX_SHAKE: f32 = 1.0;
+
+        if let Some(camera_id) = *self.camera_id.read().unwrap() {
+            self.ecs.write().modify(camera_id, |entity| {
+                let distance = entity.transform().position.distance(position);
+                let falloff = (1.0 - distance / IMPACT_RADIUS).max(0.0);
+
+                if let Some(camera) = entity.camera_mut() {
+                    camera.shake = (camera.shake + falloff * strength).min(MAX_SHAKE);
                 }
             });
         }
@@ -216,4 +316,67 @@ impl Controller {
                 });
         }
     }
+
+    /// Switches current player's spacecraft to next weapon
+    pub fn player_switch_weapon(&self) {
+        if let Some(player_id) = *self.player_id.read().unwrap() {
+            self.players
+                .visit_player(&player_id, |player| player.spacecraft_id)
+                .flatten()
+                .and_then(|spacecraft_id| {
+                    self.ecs.write().modify(spacecraft_id, |entity| {
+                        if let Some(spacecraft) = entity.spacecraft_mut() {
+                            spacecraft.weapon = spacecraft.weapon.next();
+                        }
+                    })
+                });
+        }
+    }
+
+    /// Restarts game: destroys all entities except cameras and resets players
+    pub fn restart(&self) {
+        {
+            let mut entities = self.ecs.write();
+
+            let entity_ids: Vec<_> = entities
+                .iter()
+                .filter(|(_, entity)| entity.camera().is_none())
+                .map(|(entity_id, _)| entity_id)
+                .collect();
+
+            for entity_id in entity_ids {
+                entities.destroy(entity_id);
+            }
+        }
+
+        self.players.reset_players();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{events, game::entities::Camera};
+
+    #[test]
+    fn camera_pan_is_combined_from_active_directions() {
+        let events: Arc<events::Events> = Default::default();
+        let ecs = ECS::new(&events);
+        let controller = Controller::new(ecs.clone(), Players::new(&events, Default::default()));
+
+        let camera_id = ecs.write().create(Camera::default());
+        controller.set_camera(Some(camera_id));
+
+        let pan = || ecs.read().get(camera_id).unwrap().camera().unwrap().pan;
+
+        controller.camera_pan(CameraPanDirection::Up, true);
+        controller.camera_pan(CameraPanDirection::Up, true);
+        controller.camera_pan(CameraPanDirection::Right, true);
+        assert_eq!(pan(), Vec2::new(1.0, 1.0));
+
+        controller.camera_pan(CameraPanDirection::Up, false);
+        controller.camera_pan(CameraPanDirection::Right, false);
+        controller.camera_pan(CameraPanDirection::Right, false);
+        assert_eq!(pan(), Vec2::ZERO);
+    }
 }
@@ -1,23 +1,31 @@
 use std::{
     collections::BTreeMap,
     ptr::NonNull,
-    sync::{Arc, Mutex, RwLock, RwLockReadGuard, RwLockWriteGuard},
+    sync::{
+        Arc, Mutex, RwLock, RwLockReadGuard, RwLockWriteGuard,
+        atomic::{AtomicBool, Ordering},
+    },
     thread,
     time::{Duration, Instant},
 };
 
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
+
 use crate::{
     events,
-    game::entities::{Entity, EntityId},
+    game::{
+        clock::Clock,
+        entities::{Entity, EntityId},
+    },
     handle, workers,
 };
 
-/// INTERNAL: action over entity which ECS should perform, enqueued by system
+/// INTERNAL: action over entity which ECS should perform, enqueued by system or by [ECS::defer_modify]
 enum Action {
     /// Create entity
-    Create(Box<dyn FnOnce() -> Entity>),
+    Create(Box<dyn FnOnce() -> Entity + Send>),
     /// Modify entity
-    Modify(EntityId, Box<dyn FnOnce(&mut Entity)>),
+    Modify(EntityId, Box<dyn FnOnce(&mut Entity) + Send>),
     /// Destroy entity
     Destroy(EntityId),
 }
@@ -31,7 +39,7 @@ pub struct SystemArgs<'a> {
     /// Current entity
     pub entity: &'a Entity,
 
-    entities: &'a Vec<Option<Entity>>,
+    entities: &'a [Option<Entity>],
     actions: &'a Mutex<Vec<Action>>,
 }
 
@@ -41,9 +49,22 @@ impl<'a> SystemArgs<'a> {
         self.entities.get(entity_id).and_then(|slot| slot.as_ref())
     }
 
+    /// Counts entities which satisfy predicate, entities enqueued for creation are not counted
+    pub fn count_entities_where<F>(&self, mut predicate: F) -> usize
+    where
+        F: FnMut(EntityId, &Entity) -> bool,
+    {
+        self.entities
+            .iter()
+            .enumerate()
+            .filter_map(|(entity_id, slot)| slot.as_ref().map(|entity| (entity_id, entity)))
+            .filter(|(entity_id, entity)| predicate(*entity_id, entity))
+            .count()
+    }
+
     pub fn create<F>(&self, func: F)
     where
-        F: FnOnce() -> Entity + 'static,
+        F: FnOnce() -> Entity + Send + 'static,
     {
         let action = Action::Create(Box::new(func));
 
@@ -53,7 +74,7 @@ impl<'a> SystemArgs<'a> {
     /// Enqueues modification of the current entity
     pub fn modify<F>(&self, func: F)
     where
-        F: FnOnce(&mut Entity) + 'static,
+        F: FnOnce(&mut Entity) + Send + 'static,
     {
         let action = Action::Modify(self.entity_id, Box::new(func));
 
@@ -157,6 +178,7 @@ pub trait EntitiesRead {
 /// Read-only lock over entities collection in [ECS]
 pub struct EntitiesReadLock<'a> {
     entities: RwLockReadGuard<'a, Vec<Option<Entity>>>,
+    generations: &'a Mutex<Vec<u64>>,
 }
 
 impl<'a> EntitiesReadLock<'a> {
@@ -165,6 +187,14 @@ impl<'a> EntitiesReadLock<'a> {
         self.entities.get(entity_id).and_then(|slot| slot.as_ref())
     }
 
+    /// Gets generation of entity identifier: it changes each time identifier is reused by new entity,
+    /// so data cached by [EntityId] can be invalidated
+    pub fn generation(&self, entity_id: EntityId) -> u64 {
+        let generations = self.generations.lock().unwrap();
+
+        generations.get(entity_id).copied().unwrap_or_default()
+    }
+
     /// Iterates over all entities
     pub fn iter(&'a self) -> EntityIter<'a, EntitiesReadLock<'a>> {
         EntityIter {
@@ -172,6 +202,25 @@ impl<'a> EntitiesReadLock<'a> {
             entity_id: 0,
         }
     }
+
+    /// Finds first entity which satisfies predicate, stops iteration on match
+    pub fn find_entity<F>(&'a self, mut predicate: F) -> Option<(EntityId, &'a Entity)>
+    where
+        F: FnMut(EntityId, &Entity) -> bool,
+    {
+        self.iter()
+            .find(|(entity_id, entity)| predicate(*entity_id, entity))
+    }
+
+    /// Counts entities which satisfy predicate
+    pub fn count_where<F>(&'a self, mut predicate: F) -> usize
+    where
+        F: FnMut(EntityId, &Entity) -> bool,
+    {
+        self.iter()
+            .filter(|(entity_id, entity)| predicate(*entity_id, entity))
+            .count()
+    }
 }
 
 impl<'a> EntitiesRead for EntitiesReadLock<'a> {
@@ -187,6 +236,8 @@ impl<'a> EntitiesRead for EntitiesReadLock<'a> {
 /// Lock over entities collection in [ECS] with ability to modify data
 pub struct EntitiesWriteLock<'a> {
     entities: RwLockWriteGuard<'a, Vec<Option<Entity>>>,
+    generations: &'a Mutex<Vec<u64>>,
+    deferred: &'a Mutex<Vec<Action>>,
     event_sender: events::Sender,
 }
 
@@ -204,6 +255,16 @@ impl<'a> EntitiesWriteLock<'a> {
         }
     }
 
+    /// Counts entities which satisfy predicate
+    pub fn count_where<F>(&'a self, mut predicate: F) -> usize
+    where
+        F: FnMut(EntityId, &Entity) -> bool,
+    {
+        self.iter()
+            .filter(|(entity_id, entity)| predicate(*entity_id, entity))
+            .count()
+    }
+
     /// Creates new entity
     pub fn create<E>(&mut self, entity: E) -> EntityId
     where
@@ -218,6 +279,15 @@ impl<'a> EntitiesWriteLock<'a> {
 
         let entity = entity.into();
 
+        let mut generations = self.generations.lock().unwrap();
+
+        if generations.len() <= entity_id {
+            generations.resize(entity_id + 1, 0);
+        }
+
+        generations[entity_id] += 1;
+        drop(generations);
+
         if should_insert {
             self.entities.push(Some(entity));
         } else {
@@ -241,15 +311,33 @@ impl<'a> EntitiesWriteLock<'a> {
             .map(visitor)
     }
 
-    /// Destroys entity
+    /// Destroys entity, its deferred modifications are discarded
     pub fn destroy(&mut self, entity_id: EntityId) {
-        if let Some(slot) = self.entities.get_mut(entity_id) {
+        if let Some(slot) = self
+            .entities
+            .get_mut(entity_id)
+            .filter(|slot| slot.is_some())
+        {
             *slot = None;
 
+            // NOTE: identifiers of destroyed entities are reused, so deferred modifications of them must not reach new entities
+            self.deferred.lock().unwrap().retain(
+                |action| !matches!(action, Action::Modify(deferred_id, _) if *deferred_id == entity_id),
+            );
+
             self.event_sender
                 .send(events::Event::EntityDestroyed(entity_id));
         }
     }
+
+    /// Destroys all entities
+    pub fn clear(&mut self) {
+        for entity_id in 0..self.entities.len() {
+            if self.entities[entity_id].is_some() {
+                self.destroy(entity_id);
+            }
+        }
+    }
 }
 
 impl<'a> EntitiesRead for EntitiesWriteLock<'a> {
@@ -297,11 +385,31 @@ where
     }
 }
 
+/// Type alias for priority of [System]: systems with lower priority are invoked first
+pub type SystemPriority = i32;
+
+/// Priority of systems added with [ECS::add_system]
+pub const DEFAULT_SYSTEM_PRIORITY: SystemPriority = 0;
+
+/// INTERNAL: key of system in ECS, defines invocation order: by priority, then by name
+type SystemKey = (SystemPriority, String);
+
+/// INTERNAL: registered system
+struct SystemEntry {
+    system: Box<dyn System>,
+    /// System presents the world (e.g. camera and scene) and is invoked while game is paused too
+    presentation: bool,
+}
+
 /// Entity-Component-System infrastructure
 pub struct ECS {
     event_sender: events::Sender,
     entities: RwLock<Vec<Option<Entity>>>,
-    systems: Arc<Mutex<BTreeMap<String, Box<dyn System>>>>,
+    // NOTE: generations are changed under entities write lock only, so they are consistent with entities under read lock
+    generations: Mutex<Vec<u64>>,
+    systems: Arc<Mutex<BTreeMap<SystemKey, SystemEntry>>>,
+    parallel: AtomicBool,
+    deferred: Mutex<Vec<Action>>,
 }
 
 impl ECS {
@@ -310,37 +418,95 @@ impl ECS {
         let ecs = ECS {
             event_sender: events.get_sender(),
             entities: Default::default(),
+            generations: Default::default(),
             systems: Default::default(),
+            parallel: Default::default(),
+            deferred: Default::default(),
         };
 
         Arc::new(ecs)
     }
 
-    /// Adds system
+    /// Adds system with [DEFAULT_SYSTEM_PRIORITY]
     #[must_use = "returned handle removes system on drop"]
     pub fn add_system<N, S>(&self, name: N, system: S) -> handle::Handle
     where
         N: Into<String>,
         S: System + 'static,
     {
-        let name = name.into();
+        self.add_system_ordered(DEFAULT_SYSTEM_PRIORITY, name, system)
+    }
+
+    /// Adds system with explicit priority: systems are invoked in ascending order of priority,
+    /// systems with same priority are invoked in alphabetical order of their names
+    #[must_use = "returned handle removes system on drop"]
+    pub fn add_system_ordered<N, S>(
+        &self,
+        priority: SystemPriority,
+        name: N,
+        system: S,
+    ) -> handle::Handle
+    where
+        N: Into<String>,
+        S: System + 'static,
+    {
+        self.insert_system(priority, name.into(), Box::new(system), false)
+    }
+
+    /// Adds system with explicit priority, which presents the world (e.g. synchronizes camera or dispatches scene):
+    /// unlike other systems, it is invoked by [ECS::update_paused] too, so presentation is alive while game is paused
+    #[must_use = "returned handle removes system on drop"]
+    pub fn add_presentation_system_ordered<N, S>(
+        &self,
+        priority: SystemPriority,
+        name: N,
+        system: S,
+    ) -> handle::Handle
+    where
+        N: Into<String>,
+        S: System + 'static,
+    {
+        self.insert_system(priority, name.into(), Box::new(system), true)
+    }
+
+    /// INTERNAL: inserts system, returns handle which removes it
+    fn insert_system(
+        &self,
+        priority: SystemPriority,
+        name: String,
+        system: Box<dyn System>,
+        presentation: bool,
+    ) -> handle::Handle {
+        let key = (priority, name);
 
         let mut systems = self.systems.lock().unwrap();
-        systems.insert(name.clone(), Box::new(system));
+        systems.insert(
+            key.clone(),
+            SystemEntry {
+                system,
+                presentation,
+            },
+        );
 
         let systems = self.systems.clone();
         let drop = move || {
             let mut systems = systems.lock().unwrap();
-            systems.remove(&name);
+            systems.remove(&key);
         };
 
         drop.into()
     }
 
+    /// Returns priorities and names of registered systems in invocation order
+    pub fn systems(&self) -> Vec<(SystemPriority, String)> {
+        self.systems.lock().unwrap().keys().cloned().collect()
+    }
+
     /// Locks entities collection for reading
     pub fn read(&self) -> EntitiesReadLock {
         EntitiesReadLock {
             entities: self.entities.read().unwrap(),
+            generations: &self.generations,
         }
     }
 
@@ -348,34 +514,104 @@ impl ECS {
     pub fn write(&self) -> EntitiesWriteLock {
         EntitiesWriteLock {
             entities: self.entities.write().unwrap(),
+            generations: &self.generations,
+            deferred: &self.deferred,
             event_sender: self.event_sender.clone(),
         }
     }
+
+    /// Destroys all entities
+    pub fn clear(&self) {
+        self.write().clear();
+    }
+
+    /// Enqueues modification of entity from outside of ECS update (e.g. from other worker),
+    /// modification is applied at the beginning of next ECS update; it is discarded if entity is destroyed meanwhile
+    pub fn defer_modify<F>(&self, entity_id: EntityId, func: F)
+    where
+        F: FnOnce(&mut Entity) + Send + 'static,
+    {
+        let action = Action::Modify(entity_id, Box::new(func));
+
+        self.deferred.lock().unwrap().push(action);
+    }
+
+    /// Performs single synchronous ECS update: applies deferred modifications, invokes systems and applies their actions
+    pub fn update(&self, elapsed: f32) {
+        worker_func(self, elapsed, false);
+    }
+
+    /// Performs single synchronous ECS update while game is paused: same as [ECS::update], but only systems added
+    /// with [ECS::add_presentation_system_ordered] are invoked
+    pub fn update_paused(&self, elapsed: f32) {
+        worker_func(self, elapsed, true);
+    }
+
+    /// Enables or disables parallel invocation of systems across entities.
+    /// Enqueued actions are applied in the same order as in serial invocation.
+    pub fn set_parallel(&self, parallel: bool) {
+        self.parallel.store(parallel, Ordering::Relaxed);
+    }
+}
+
+/// INTERNAL: invokes all systems over single entity, returns enqueued actions
+fn invoke_systems(
+    systems: &[&dyn System],
+    entities: &[Option<Entity>],
+    elapsed: f32,
+    entity_id: EntityId,
+    entity: &Entity,
+) -> Vec<Action> {
+    let actions: Mutex<Vec<Action>> = Default::default();
+
+    for system in systems {
+        let args = SystemArgs {
+            elapsed,
+            entity_id,
+            entity,
+
+            entities,
+            actions: &actions,
+        };
+
+        system.invoke(args);
+    }
+
+    actions.into_inner().unwrap()
 }
 
 /// INTERNAL: ECS worker thread function
-fn worker_func(ecs: &ECS, elapsed: f32) {
+fn worker_func(ecs: &ECS, elapsed: f32, paused: bool) {
     let mut entities = ecs.write();
     let systems = ecs.systems.lock().unwrap();
 
-    let actions: Mutex<Vec<Action>> = Default::default();
+    let systems: Vec<&dyn System> = systems
+        .values()
+        .filter(|entry| !paused || entry.presentation)
+        .map(|entry| entry.system.as_ref())
+        .collect();
 
-    for (entity_id, entity) in entities.iter() {
-        for (_, system) in systems.iter() {
-            let args = SystemArgs {
-                elapsed,
-                entity_id,
-                entity,
+    let deferred: Vec<Action> = ecs.deferred.lock().unwrap().drain(..).collect();
+    apply_actions(&mut entities, deferred);
 
-                entities: &entities.entities,
-                actions: &actions,
-            };
+    let invoke = |(entity_id, entity)| {
+        invoke_systems(&systems, &entities.entities, elapsed, entity_id, entity)
+    };
 
-            system.invoke(args);
-        }
-    }
+    let actions: Vec<Action> = if ecs.parallel.load(Ordering::Relaxed) {
+        let targets: Vec<_> = entities.iter().collect();
+
+        // NOTE: collect of parallel iterator preserves order of targets
+        targets.into_par_iter().flat_map_iter(invoke).collect()
+    } else {
+        entities.iter().flat_map(invoke).collect()
+    };
 
-    let actions = actions.into_inner().unwrap();
+    apply_actions(&mut entities, actions);
+}
+
+/// INTERNAL: applies enqueued actions in order
+fn apply_actions(entities: &mut EntitiesWriteLock, actions: Vec<Action>) {
     for action in actions {
         match action {
             Action::Create(func) => {
@@ -396,24 +632,264 @@ fn worker_func(ecs: &ECS, elapsed: f32) {
 }
 
 /// Spawns ECS worker thread
-pub fn spawn_worker(workers: &workers::Workers, ecs: Arc<ECS>) -> handle::Handle {
+pub fn spawn_worker(
+    workers: &workers::Workers,
+    ecs: Arc<ECS>,
+    clock: Arc<Clock>,
+    config: workers::WorkerConfig,
+) -> handle::Handle {
     workers.spawn("ECS", move |token| {
-        const UPDATE_RATE: f32 = 1.0 / 120.0;
+        let update_rate = config.update_rate();
 
         let mut last_update = Instant::now();
 
         while !token.is_cancelled() {
             let elapsed = Instant::now().duration_since(last_update).as_secs_f32();
 
-            worker_func(&ecs, elapsed);
+            // NOTE: while game is paused the world is frozen, but it is still presented
+            if clock.is_paused() {
+                ecs.update_paused(elapsed);
+            } else {
+                clock.advance();
+                ecs.update(elapsed);
+            }
+
+            token.tick();
 
             last_update = Instant::now();
 
-            if elapsed < UPDATE_RATE {
-                let duration = Duration::from_secs_f32(UPDATE_RATE - elapsed);
+            if elapsed < update_rate {
+                let duration = Duration::from_secs_f32(update_rate - elapsed);
 
                 thread::sleep(duration);
             }
         }
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use std::mem;
+
+    use glam::Vec2;
+
+    use super::*;
+    use crate::game::entities::{Asteroid, Bullet, Camera};
+
+    /// Creates [ECS] with its events infrastructure
+    fn ecs() -> (Arc<events::Events>, Arc<ECS>) {
+        let events: Arc<events::Events> = Default::default();
+        let ecs = ECS::new(&events);
+
+        (events, ecs)
+    }
+
+    #[test]
+    fn deferred_modification_does_not_reach_entity_with_reused_id() {
+        let (_events, ecs) = ecs();
+
+        let entity_id = ecs.write().create(Camera::default());
+
+        ecs.defer_modify(entity_id, |entity| {
+            entity.transform_mut().position = Vec2::ONE;
+        });
+
+        ecs.clear();
+
+        let reused_id = ecs.write().create(Camera::default());
+        assert_eq!(reused_id, entity_id);
+
+        ecs.update(0.0);
+
+        let position = ecs.read().get(reused_id).unwrap().transform().position;
+        assert_eq!(position, Vec2::ZERO);
+    }
+
+    #[test]
+    fn systems_are_invoked_in_order_of_priority_then_name() {
+        let (_events, ecs) = ecs();
+        let invoked: Arc<Mutex<Vec<&'static str>>> = Default::default();
+
+        let system = |name: &'static str| {
+            StatefulSystem::new(invoked.clone(), move |_, invoked| {
+                invoked.lock().unwrap().push(name)
+            })
+        };
+
+        let _handles = [
+            ecs.add_system_ordered(10, "c", system("c")),
+            ecs.add_system_ordered(-10, "d", system("d")),
+            ecs.add_system("b", system("b")),
+            ecs.add_system("a", system("a")),
+        ];
+
+        ecs.write().create(Camera::default());
+        ecs.update(0.0);
+
+        assert_eq!(*invoked.lock().unwrap(), ["d", "a", "b", "c"]);
+    }
+
+    #[test]
+    fn find_entity_stops_on_first_match() {
+        let (_events, ecs) = ecs();
+
+        ecs.write().create(Camera::default());
+        let asteroid_id = ecs.write().create(Asteroid::default());
+        ecs.write().create(Asteroid::default());
+
+        let entities = ecs.read();
+        let mut visited = 0;
+
+        let found = entities.find_entity(|_, entity| {
+            visited += 1;
+
+            entity.asteroid().is_some()
+        });
+
+        assert_eq!(found.map(|(entity_id, _)| entity_id), Some(asteroid_id));
+        assert_eq!(visited, 2);
+    }
+
+    #[test]
+    fn queries_over_empty_collection() {
+        let (_events, ecs) = ecs();
+
+        assert!(ecs.read().find_entity(|_, _| true).is_none());
+        assert_eq!(ecs.read().count_where(|_, _| true), 0);
+        assert_eq!(ecs.write().count_where(|_, _| true), 0);
+    }
+
+    #[test]
+    fn queries_over_single_entity() {
+        let (_events, ecs) = ecs();
+
+        let camera_id = ecs.write().create(Camera::default());
+
+        let entities = ecs.read();
+
+        assert_eq!(
+            entities
+                .find_entity(|_, _| true)
+                .map(|(entity_id, _)| entity_id),
+            Some(camera_id)
+        );
+        assert!(
+            entities
+                .find_entity(|_, entity| entity.asteroid().is_some())
+                .is_none()
+        );
+        assert_eq!(entities.count_where(|_, _| true), 1);
+        assert_eq!(
+            entities.count_where(|_, entity| entity.asteroid().is_some()),
+            0
+        );
+    }
+
+    #[test]
+    fn queries_over_mixed_entities() {
+        let (_events, ecs) = ecs();
+
+        ecs.write().create(Camera::default());
+        let asteroid_id = ecs.write().create(Asteroid::default());
+        ecs.write().create(Asteroid::default());
+        let destroyed_id = ecs.write().create(Camera::default());
+        ecs.write().destroy(destroyed_id);
+
+        let is_asteroid = |_, entity: &Entity| entity.asteroid().is_some();
+
+        assert_eq!(ecs.write().count_where(is_asteroid), 2);
+
+        let entities = ecs.read();
+
+        assert_eq!(
+            entities
+                .find_entity(is_asteroid)
+                .map(|(entity_id, _)| entity_id),
+            Some(asteroid_id)
+        );
+        assert_eq!(entities.count_where(is_asteroid), 2);
+        assert_eq!(entities.count_where(|_, _| true), 3);
+    }
+
+    /// INTERNAL: runs single update of ECS with fixed entity set, returns resulting entities
+    fn update_fixed_entities(parallel: bool) -> Vec<(EntityId, mem::Discriminant<Entity>, Vec2)> {
+        let (_events, ecs) = ecs();
+        ecs.set_parallel(parallel);
+
+        let _system = ecs.add_system(
+            "test_system",
+            StatelessSystem::from(|args: SystemArgs| {
+                let position = Vec2::splat(args.entity_id as f32);
+
+                args.modify(move |entity| entity.transform_mut().position += position);
+
+                if args.entity.asteroid().is_some() {
+                    args.create(move || {
+                        let mut bullet = Bullet::default();
+                        bullet.transform.position = -position;
+
+                        bullet.into()
+                    });
+                }
+            }),
+        );
+
+        for index in 0..64 {
+            match index % 3 {
+                0 => ecs.write().create(Camera::default()),
+                _ => ecs.write().create(Asteroid::default()),
+            };
+        }
+
+        ecs.update(0.0);
+
+        ecs.read()
+            .iter()
+            .map(|(entity_id, entity)| {
+                (
+                    entity_id,
+                    mem::discriminant(entity),
+                    entity.transform().position,
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn parallel_update_matches_serial_update() {
+        assert_eq!(update_fixed_entities(false), update_fixed_entities(true));
+    }
+
+    #[test]
+    fn only_presentation_systems_are_invoked_while_paused() {
+        let (_events, ecs) = ecs();
+        let invoked: Arc<Mutex<Vec<&str>>> = Default::default();
+
+        let _systems = [
+            ecs.add_system_ordered(
+                0,
+                "world",
+                StatefulSystem::new(invoked.clone(), |_, invoked| {
+                    invoked.lock().unwrap().push("world")
+                }),
+            ),
+            ecs.add_presentation_system_ordered(
+                1,
+                "presentation",
+                StatefulSystem::new(invoked.clone(), |_, invoked| {
+                    invoked.lock().unwrap().push("presentation")
+                }),
+            ),
+        ];
+
+        ecs.write().create(Camera::default());
+
+        ecs.update_paused(0.0);
+        assert_eq!(*invoked.lock().unwrap(), ["presentation"]);
+
+        invoked.lock().unwrap().clear();
+
+        ecs.update(0.0);
+        assert_eq!(*invoked.lock().unwrap(), ["world", "presentation"]);
+    }
+}
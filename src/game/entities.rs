@@ -1,12 +1,13 @@
 use std::{collections::BTreeSet, f32::consts::PI, ops::RangeInclusive};
 
 use glam::{Mat4, Quat, Vec2, Vec3};
+use rand::{Rng, SeedableRng, rngs::StdRng};
 
 use crate::{
     assets::AssetRef,
     consts::VEC2_RIGHT,
     game::{
-        physics::{Collider, Collision, TriangleCollider},
+        physics::{Collider, Collision, PolygonCollider},
         players::PlayerId,
     },
 };
@@ -15,25 +16,37 @@ use crate::{
 pub type EntityId = usize;
 
 /// Transformation of an entity
-#[derive(Default)]
+#[derive(Clone, Copy)]
 pub struct TransformComponent {
     /// Vector with position
     pub position: Vec2,
     /// Rotation in radians
     pub rotation: f32,
+    /// Scale of entity model
+    pub scale: Vec2,
 }
 
 impl TransformComponent {
     /// Construct model matrix from [TransformComponent] data
     pub fn to_model_matrix(&self) -> Mat4 {
         Mat4::from_scale_rotation_translation(
-            Vec3::ONE,
+            Vec3::new(self.scale.x, self.scale.y, 1.0),
             Quat::from_rotation_z(-self.rotation),
             Vec3::new(self.position.x, self.position.y, 0.0),
         )
     }
 }
 
+impl Default for TransformComponent {
+    fn default() -> Self {
+        Self {
+            position: Default::default(),
+            rotation: Default::default(),
+            scale: Vec2::ONE,
+        }
+    }
+}
+
 /// Applicable movement to an entity
 #[derive(Default)]
 pub struct MovementComponent {
@@ -55,7 +68,7 @@ pub struct ColliderComponent {
 }
 
 /// Enumeration of possible targets of camera
-#[derive(Default)]
+#[derive(Clone, Copy, Default)]
 pub enum CameraTarget {
     /// Camera has no target
     #[default]
@@ -64,6 +77,43 @@ pub enum CameraTarget {
     Entity(EntityId),
     /// Target is a player
     Player(PlayerId),
+    /// Camera is detached and moved by panning
+    Free,
+}
+
+/// Rectangular bounds of the world area
+#[derive(Clone, Copy)]
+pub struct Bounds {
+    /// Minimal corner of area
+    pub min: Vec2,
+    /// Maximal corner of area
+    pub max: Vec2,
+}
+
+impl Bounds {
+    /// Clamps position to the bounds
+    pub fn clamp(&self, position: Vec2) -> Vec2 {
+        position.clamp(self.min, self.max)
+    }
+}
+
+/// Bounds of the play field, centered at the world origin: entities, which cross its edges, wrap around
+#[derive(Clone, Copy)]
+pub struct WorldBounds {
+    /// Half of play field size
+    pub half_extent: Vec2,
+}
+
+impl WorldBounds {
+    /// Checks if position is within the bounds
+    pub fn contains(&self, position: Vec2) -> bool {
+        position.abs().cmple(self.half_extent).all()
+    }
+
+    /// Wraps position around the bounds, e.g. position beyond `+x` edge reappears at `-x` edge
+    pub fn wrap(&self, position: Vec2) -> Vec2 {
+        (position + self.half_extent).rem_euclid(2.0 * self.half_extent) - self.half_extent
+    }
 }
 
 /// Component with camera data
@@ -74,6 +124,18 @@ pub struct CameraComponent {
     pub target: CameraTarget,
     /// Determines should camera follow target entity
     pub follow: bool,
+    /// Smoothing rate of following: `0.0` means camera snaps to target instantly
+    pub follow_smoothing: f32,
+    /// Optional distance to target, beyond which camera snaps to target regardless of smoothing (e.g. on respawn)
+    pub follow_snap_distance: Option<f32>,
+    /// Optional bounds, which camera position can not leave
+    pub bounds: Option<Bounds>,
+    /// Intensity of camera shake, decays over time
+    pub shake: f32,
+    /// Current offset of camera from its position, caused by shake
+    pub shake_offset: Vec2,
+    /// Direction of camera panning, applied only with [CameraTarget::Free]
+    pub pan: Vec2,
 }
 
 impl Default for CameraComponent {
@@ -82,6 +144,85 @@ impl Default for CameraComponent {
             distance: consts::CAMERA_INITIAL_DISTANCE,
             target: Default::default(),
             follow: true,
+            follow_smoothing: 0.0,
+            follow_snap_distance: None,
+            bounds: Default::default(),
+            shake: 0.0,
+            shake_offset: Vec2::ZERO,
+            pan: Vec2::ZERO,
+        }
+    }
+}
+
+/// Enumeration of spacecraft weapons
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub enum Weapon {
+    /// Regular weapon: small bullets with high rate of fire
+    #[default]
+    Regular,
+    /// Heavy weapon: large bullets with low rate of fire
+    Heavy,
+    /// Triple weapon: three small bullets at spread angles
+    Triple,
+    /// Rapid weapon: small bullets with very high rate of fire
+    Rapid,
+}
+
+impl Weapon {
+    /// Returns scale of bullet fired by weapon
+    pub fn bullet_scale(&self) -> f32 {
+        match self {
+            Weapon::Regular | Weapon::Triple | Weapon::Rapid => 1.0,
+            Weapon::Heavy => consts::HEAVY_BULLET_SCALE,
+        }
+    }
+
+    /// Returns reloading cooldown of weapon in seconds
+    pub fn cooldown(&self) -> f32 {
+        match self {
+            Weapon::Regular | Weapon::Triple => consts::REGULAR_WEAPON_COOLDOWN,
+            Weapon::Heavy => consts::HEAVY_WEAPON_COOLDOWN,
+            Weapon::Rapid => consts::RAPID_WEAPON_COOLDOWN,
+        }
+    }
+
+    /// Returns angles in radians relative to spacecraft heading, one bullet is fired at each angle
+    pub fn spread(&self) -> &'static [f32] {
+        match self {
+            Weapon::Regular | Weapon::Heavy | Weapon::Rapid => &[0.0],
+            Weapon::Triple => &[
+                -consts::TRIPLE_WEAPON_SPREAD,
+                0.0,
+                consts::TRIPLE_WEAPON_SPREAD,
+            ],
+        }
+    }
+
+    /// Returns next weapon in cycle
+    pub fn next(&self) -> Weapon {
+        match self {
+            Weapon::Regular => Weapon::Heavy,
+            Weapon::Heavy => Weapon::Triple,
+            Weapon::Triple => Weapon::Rapid,
+            Weapon::Rapid => Weapon::Regular,
+        }
+    }
+}
+
+/// Component with data of AI pilot, which steers spacecraft toward the nearest player spacecraft
+#[derive(Clone, Copy)]
+pub struct AiComponent {
+    /// Maximal angle between spacecraft heading and direction to target, within which spacecraft fires
+    pub fire_angle: f32,
+    /// Maximal distance to target, within which spacecraft fires
+    pub fire_distance: f32,
+}
+
+impl Default for AiComponent {
+    fn default() -> Self {
+        Self {
+            fire_angle: consts::AI_FIRE_ANGLE,
+            fire_distance: consts::AI_FIRE_DISTANCE,
         }
     }
 }
@@ -91,34 +232,90 @@ impl Default for CameraComponent {
 pub struct SpacecraftComponent {
     /// Identifier of player that controls the spacecraft
     pub owner: Option<PlayerId>,
+    /// Current weapon of spacecraft
+    pub weapon: Weapon,
     /// Determines should spacecraft fire a bullet
     pub weapon_fire: bool,
     /// Reloading cooldown
     pub weapon_cooldown: f32,
+    /// Cooldown before next afterimage is left while spacecraft is boosting
+    pub afterimage_cooldown: f32,
+    /// Cooldown before next thruster exhaust particle is emitted while spacecraft is accelerating
+    pub thruster_cooldown: f32,
     /// Rotation velocity
     pub rotation_velocity: f32,
+    /// AI pilot of spacecraft, [None] if spacecraft is controlled by player
+    pub ai: Option<AiComponent>,
+    /// Remaining time of shield in seconds, shielded spacecraft is not destroyed by collisions
+    pub shield: f32,
+}
+
+impl SpacecraftComponent {
+    /// Checks if spacecraft is protected by shield
+    pub fn is_shielded(&self) -> bool {
+        self.shield > 0.0
+    }
 }
 
 /// Component with asteroid data
 pub struct AsteroidComponent {
     /// Rotation velocity
     pub rotation_velocity: f32,
-    /// Size of asteroid
+    /// Size of asteroid, asteroid body is scaled by it
     pub size: f32,
-    /// Asteroid body
-    pub body: [Vec2; consts::ASTEROID_SEGMENTS_COUNT],
 }
 
 impl AsteroidComponent {
-    /// INTERNAL: generate body of an asteroid
-    fn generate_body_(size: f32) -> [Vec2; consts::ASTEROID_SEGMENTS_COUNT] {
+    /// Creates new instance of [AsteroidComponent] with specified size
+    pub fn with_size(size: f32) -> AsteroidComponent {
+        const ROTATION_VELOCITY_MULTIPLIER_RANGE: RangeInclusive<f32> = -2.0..=2.0;
+
+        let rotation_velocity = rand::random_range(ROTATION_VELOCITY_MULTIPLIER_RANGE);
+        Self {
+            rotation_velocity,
+            size,
+        }
+    }
+
+    /// Gets radius of asteroid body
+    pub fn radius(&self) -> f32 {
+        Self::body()
+            .iter()
+            .map(|segment| self.size * segment.distance(Vec2::ZERO))
+            .max_by(|l, r| l.total_cmp(r))
+            .expect("asteroid has no segments")
+    }
+
+    /// Splits body of unit size into triangular fragments, one per segment:
+    /// returns center of each fragment and its vertices relative to the center
+    pub fn fragments() -> [(Vec2, [Vec2; 3]); consts::ASTEROID_SEGMENTS_COUNT] {
+        let body = Self::body();
+
+        std::array::from_fn(|index| {
+            let next_index = (index + 1) % consts::ASTEROID_SEGMENTS_COUNT;
+
+            let triangle = [Vec2::ZERO, body[index], body[next_index]];
+            let center = triangle.iter().sum::<Vec2>() / 3.0;
+
+            (center, triangle.map(|vertex| vertex - center))
+        })
+    }
+
+    /// Gets reference to mesh asset of fragment, see [AsteroidComponent::fragments]
+    pub fn fragment_mesh_asset_ref(index: usize) -> AssetRef {
+        format!("{}/{}", consts::FRAGMENT_MESH_ASSET_REF, index).into()
+    }
+
+    /// Generates body of unit size, shared by all asteroids
+    pub fn body() -> [Vec2; consts::ASTEROID_SEGMENTS_COUNT] {
         const ANGULAR_STEP: f32 = 2.0 * PI / consts::ASTEROID_SEGMENTS_COUNT as f32;
-        const RADIUS_RANGE: RangeInclusive<f32> = 0.5..=1.0;
+        const RADIUS_RANGE: RangeInclusive<f32> = 1.0..=1.25;
 
+        let mut rng = StdRng::seed_from_u64(consts::ASTEROID_BODY_SEED);
         let mut body: [Vec2; consts::ASTEROID_SEGMENTS_COUNT] = Default::default();
 
         for segment_index in 0..consts::ASTEROID_SEGMENTS_COUNT {
-            let radius = size + rand::random_range(RADIUS_RANGE);
+            let radius = rng.random_range(RADIUS_RANGE);
 
             let angle = ANGULAR_STEP * segment_index as f32;
             let (sin, cos) = (angle).sin_cos();
@@ -139,18 +336,9 @@ impl AsteroidComponent {
 
 impl Default for AsteroidComponent {
     fn default() -> Self {
-        const ROTATION_VELOCITY_MULTIPLIER_RANGE: RangeInclusive<f32> = -2.0..=2.0;
         const SIZE_RANGE: RangeInclusive<u32> = 1..=4;
 
-        let rotation_velocity = rand::random_range(ROTATION_VELOCITY_MULTIPLIER_RANGE);
-        let size = rand::random_range(SIZE_RANGE) as f32;
-        let body = Self::generate_body_(size);
-
-        Self {
-            rotation_velocity,
-            size,
-            body,
-        }
+        Self::with_size(rand::random_range(SIZE_RANGE) as f32)
     }
 }
 
@@ -161,12 +349,94 @@ pub struct BulletComponent {
     pub owner: Option<PlayerId>,
 }
 
+impl BulletComponent {
+    /// Checks if bullet damages spacecraft: bullets of players hit AI spacecrafts and vice versa
+    pub fn is_hostile_to(&self, spacecraft: &SpacecraftComponent) -> bool {
+        self.owner.is_some() != spacecraft.owner.is_some()
+    }
+}
+
+/// Enumeration of power-up kinds
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub enum PowerUpKind {
+    /// Grants temporary shield to spacecraft
+    #[default]
+    Shield,
+    /// Replaces weapon of spacecraft
+    Weapon(Weapon),
+}
+
+impl PowerUpKind {
+    /// Returns color of power-up of this kind
+    pub fn color(&self) -> Vec3 {
+        match self {
+            PowerUpKind::Shield => consts::SHIELD_POWER_UP_COLOR,
+            PowerUpKind::Weapon(_) => consts::WEAPON_POWER_UP_COLOR,
+        }
+    }
+}
+
+/// Component with power-up data
+#[derive(Default)]
+pub struct PowerUpComponent {
+    /// Kind of power-up
+    pub kind: PowerUpKind,
+}
+
+/// Component with limited lifetime of an entity
+pub struct LifetimeComponent {
+    /// Remaining time before entity is destroyed
+    pub remaining: f32,
+}
+
+/// Component of blinking entity: entity toggles its visibility while blink is active
+pub struct BlinkComponent {
+    /// Period of visibility toggle in seconds
+    pub period: f32,
+    /// Remaining time of blinking in seconds
+    pub remaining: f32,
+}
+
+impl BlinkComponent {
+    /// Creates new instance of [BlinkComponent], which blinks for some duration
+    pub fn new(duration: f32) -> BlinkComponent {
+        BlinkComponent {
+            period: consts::BLINK_PERIOD,
+            remaining: duration,
+        }
+    }
+
+    /// Returns true if entity is blinking
+    pub fn is_active(&self) -> bool {
+        self.remaining > 0.0
+    }
+
+    /// Returns true if blinking entity should be visible now
+    pub fn is_visible(&self) -> bool {
+        !self.is_active() || ((self.remaining / self.period) as u32).is_multiple_of(2)
+    }
+}
+
+impl Default for BlinkComponent {
+    fn default() -> Self {
+        Self::new(0.0)
+    }
+}
+
 /// Component with data for [crate::rendering::renderer::Renderer]
 pub struct RenderComponent {
     /// Reference to mesh asset
     pub mesh: AssetRef,
     /// Reference to pipeline asset
     pub pipeline: AssetRef,
+    /// Optional reference to texture asset, requires pipeline with texture sampler
+    pub texture: Option<AssetRef>,
+    /// Color of entity model
+    pub color: Vec3,
+    /// Opacity of entity model
+    pub alpha: f32,
+    /// Entity is visible and should be rendered
+    pub visible: bool,
 }
 
 /// Camera entity
@@ -181,13 +451,11 @@ pub struct Camera {
 impl Camera {
     /// Constructs view matrix from [Camera] data
     pub fn to_view_matrix(&self) -> Mat4 {
+        let position = self.transform.position + self.camera.shake_offset;
+
         Mat4::look_at_lh(
-            Vec3::new(
-                self.transform.position.x,
-                self.transform.position.y,
-                self.camera.distance,
-            ),
-            Vec3::new(self.transform.position.x, self.transform.position.y, 0.0),
+            Vec3::new(position.x, position.y, self.camera.distance),
+            Vec3::new(position.x, position.y, 0.0),
             Vec3::new(0.0, 1.0, 0.0),
         )
     }
@@ -203,6 +471,8 @@ pub struct Spacecraft {
     pub collider: ColliderComponent,
     /// Spacecraft
     pub spacecraft: SpacecraftComponent,
+    /// Blink
+    pub blink: BlinkComponent,
     /// Render data
     pub render: RenderComponent,
 }
@@ -217,9 +487,14 @@ impl Default for Spacecraft {
                 ..Default::default()
             },
             spacecraft: Default::default(),
+            blink: Default::default(),
             render: RenderComponent {
                 mesh: consts::SPACECRAFT_MESH_ASSET_REF.into(),
                 pipeline: consts::ENTITY_PIPELINE_ASSET_REF.into(),
+                texture: None,
+                color: consts::SPACECRAFT_COLOR,
+                alpha: 1.0,
+                visible: true,
             },
         }
     }
@@ -255,66 +530,60 @@ impl Asteroid {
         }
     }
 
-    /// INTERNAL: generate collider component for asteroid
-    fn generate_collider_(asteroid: &AsteroidComponent) -> ColliderComponent {
-        let radius = asteroid
-            .body
-            .iter()
-            .map(|segment| segment.distance(Vec2::ZERO))
-            .max_by(|l, r| l.total_cmp(r))
-            .expect("asteroid has no segments");
+    /// INTERNAL: generate collider component for asteroid: single convex hull of asteroid body
+    fn generate_collider_() -> ColliderComponent {
+        // NOTE: collider is defined for unit asteroid, it is scaled by transform same as mesh
+        let body = AsteroidComponent::body();
 
         ColliderComponent {
-            colliders: (0..consts::ASTEROID_SEGMENTS_COUNT)
-                .map(|segment_index| {
-                    let next_segment_index = (segment_index + 1) % consts::ASTEROID_SEGMENTS_COUNT;
-
-                    TriangleCollider {
-                        center: Vec2::ZERO,
-                        vertices: [
-                            Vec2::ZERO,
-                            asteroid.body[segment_index],
-                            asteroid.body[next_segment_index],
-                        ],
-                        radius,
-                    }
-                    .into()
-                })
-                .collect(),
+            colliders: vec![PolygonCollider::from_points(&body).into()],
             ..Default::default()
         }
     }
 
     /// INTERNAL: generate render component for asteroid
     fn generate_render_() -> RenderComponent {
-        let random = rand::random::<u32>();
-
         RenderComponent {
-            mesh: format!("{}{}", consts::ASTEROID_MESH_ASSET_REF_PREFIX, random).into(),
+            mesh: consts::ASTEROID_MESH_ASSET_REF.into(),
             pipeline: consts::ENTITY_PIPELINE_ASSET_REF.into(),
+            texture: None,
+            color: consts::ASTEROID_COLOR,
+            alpha: 1.0,
+            visible: true,
         }
     }
 }
 
-impl Default for Asteroid {
-    fn default() -> Self {
-        const ROTATION_RANGE: RangeInclusive<f32> = 0.0..=2.0 * PI;
+impl Asteroid {
+    /// Creates new instance of [Asteroid] with specified size
+    pub fn with_size(size: f32) -> Asteroid {
+        Self::from_component_(AsteroidComponent::with_size(size))
+    }
 
-        let asteroid: AsteroidComponent = Default::default();
+    /// INTERNAL: creates new instance of [Asteroid] from [AsteroidComponent]
+    fn from_component_(asteroid: AsteroidComponent) -> Asteroid {
+        const ROTATION_RANGE: RangeInclusive<f32> = 0.0..=2.0 * PI;
 
         Self {
             transform: TransformComponent {
                 rotation: rand::random_range(ROTATION_RANGE),
+                scale: Vec2::splat(asteroid.size),
                 ..Default::default()
             },
             movement: Self::generate_movement_(),
-            collider: Self::generate_collider_(&asteroid),
-            asteroid,
+            collider: Self::generate_collider_(),
             render: Self::generate_render_(),
+            asteroid,
         }
     }
 }
 
+impl Default for Asteroid {
+    fn default() -> Self {
+        Self::from_component_(Default::default())
+    }
+}
+
 /// Bullet entity
 pub struct Bullet {
     /// Transform
@@ -325,10 +594,29 @@ pub struct Bullet {
     pub collider: ColliderComponent,
     /// Bullet data
     pub bullet: BulletComponent,
+    /// Lifetime
+    pub lifetime: LifetimeComponent,
     /// Render data
     pub render: RenderComponent,
 }
 
+impl Bullet {
+    /// Creates bullet of specified scale: both mesh and collider are scaled
+    pub fn with_scale(scale: f32) -> Bullet {
+        Bullet {
+            transform: TransformComponent {
+                scale: Vec2::splat(scale),
+                ..Default::default()
+            },
+            collider: ColliderComponent {
+                colliders: vec![consts::BULLET_COLLIDER],
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+}
+
 impl Default for Bullet {
     fn default() -> Self {
         Self {
@@ -339,9 +627,137 @@ impl Default for Bullet {
                 ..Default::default()
             },
             bullet: Default::default(),
+            lifetime: LifetimeComponent {
+                remaining: consts::BULLET_LIFETIME,
+            },
             render: RenderComponent {
                 mesh: consts::BULLET_MESH_ASSET_REF.into(),
                 pipeline: consts::ENTITY_PIPELINE_ASSET_REF.into(),
+                texture: None,
+                color: consts::BULLET_COLOR,
+                alpha: 1.0,
+                visible: true,
+            },
+        }
+    }
+}
+
+/// Power-up entity: picked up by spacecraft on collision
+pub struct PowerUp {
+    /// Transform
+    pub transform: TransformComponent,
+    /// Collider
+    pub collider: ColliderComponent,
+    /// Power-up data
+    pub power_up: PowerUpComponent,
+    /// Lifetime
+    pub lifetime: LifetimeComponent,
+    /// Render data
+    pub render: RenderComponent,
+}
+
+impl PowerUp {
+    /// Creates power-up of specified kind
+    pub fn with_kind(kind: PowerUpKind) -> PowerUp {
+        let power_up = PowerUp::default();
+
+        PowerUp {
+            power_up: PowerUpComponent { kind },
+            render: RenderComponent {
+                color: kind.color(),
+                ..power_up.render
+            },
+            ..power_up
+        }
+    }
+}
+
+impl Default for PowerUp {
+    fn default() -> Self {
+        Self {
+            transform: Default::default(),
+            collider: ColliderComponent {
+                colliders: vec![consts::POWER_UP_COLLIDER],
+                ..Default::default()
+            },
+            power_up: Default::default(),
+            lifetime: LifetimeComponent {
+                remaining: consts::POWER_UP_LIFETIME,
+            },
+            render: RenderComponent {
+                mesh: consts::POWER_UP_MESH_ASSET_REF.into(),
+                pipeline: consts::ENTITY_PIPELINE_ASSET_REF.into(),
+                texture: None,
+                color: PowerUpKind::default().color(),
+                alpha: 1.0,
+                visible: true,
+            },
+        }
+    }
+}
+
+/// Afterimage entity: fading copy of spacecraft left behind while boosting
+pub struct Afterimage {
+    /// Transform
+    pub transform: TransformComponent,
+    /// Lifetime
+    pub lifetime: LifetimeComponent,
+    /// Render data
+    pub render: RenderComponent,
+}
+
+impl Default for Afterimage {
+    fn default() -> Self {
+        Self {
+            transform: Default::default(),
+            lifetime: LifetimeComponent {
+                remaining: consts::AFTERIMAGE_LIFETIME,
+            },
+            render: RenderComponent {
+                mesh: consts::SPACECRAFT_MESH_ASSET_REF.into(),
+                pipeline: consts::ENTITY_PIPELINE_ASSET_REF.into(),
+                texture: None,
+                color: consts::SPACECRAFT_COLOR,
+                alpha: consts::AFTERIMAGE_ALPHA,
+                visible: true,
+            },
+        }
+    }
+}
+
+/// Particle entity: short-lived visual effect without collider, e.g. thruster exhaust
+pub struct Particle {
+    /// Transform
+    pub transform: TransformComponent,
+    /// Movement
+    pub movement: MovementComponent,
+    /// Lifetime
+    pub lifetime: LifetimeComponent,
+    /// Render data
+    pub render: RenderComponent,
+}
+
+impl Default for Particle {
+    fn default() -> Self {
+        Self {
+            transform: TransformComponent {
+                scale: Vec2::splat(consts::THRUSTER_PARTICLE_SCALE),
+                ..Default::default()
+            },
+            movement: MovementComponent {
+                const_velocity: true,
+                ..Default::default()
+            },
+            lifetime: LifetimeComponent {
+                remaining: consts::THRUSTER_PARTICLE_LIFETIME,
+            },
+            render: RenderComponent {
+                mesh: consts::BULLET_MESH_ASSET_REF.into(),
+                pipeline: consts::ENTITY_PIPELINE_ASSET_REF.into(),
+                texture: None,
+                color: consts::THRUSTER_PARTICLE_COLOR,
+                alpha: 1.0,
+                visible: true,
             },
         }
     }
@@ -354,6 +770,9 @@ impl Default for Bullet {
 /// * [Spacecraft]
 /// * [Asteroid]
 /// * [Bullet]
+/// * [Afterimage]
+/// * [Particle]
+/// * [PowerUp]
 #[non_exhaustive]
 pub enum Entity {
     /// Variant of entity with [Camera] entity data
@@ -364,6 +783,12 @@ pub enum Entity {
     Asteroid(Asteroid),
     /// Variant of entity with [Bullet] entity data
     Bullet(Bullet),
+    /// Variant of entity with [Afterimage] entity data
+    Afterimage(Afterimage),
+    /// Variant of entity with [Particle] entity data
+    Particle(Particle),
+    /// Variant of entity with [PowerUp] entity data
+    PowerUp(PowerUp),
 }
 
 impl Entity {
@@ -374,6 +799,9 @@ impl Entity {
             Entity::Spacecraft(spacecraft) => &spacecraft.transform,
             Entity::Asteroid(asteroid) => &asteroid.transform,
             Entity::Bullet(bullet) => &bullet.transform,
+            Entity::Afterimage(afterimage) => &afterimage.transform,
+            Entity::Particle(particle) => &particle.transform,
+            Entity::PowerUp(power_up) => &power_up.transform,
         }
     }
 
@@ -384,6 +812,9 @@ impl Entity {
             Entity::Spacecraft(spacecraft) => &mut spacecraft.transform,
             Entity::Asteroid(asteroid) => &mut asteroid.transform,
             Entity::Bullet(bullet) => &mut bullet.transform,
+            Entity::Afterimage(afterimage) => &mut afterimage.transform,
+            Entity::Particle(particle) => &mut particle.transform,
+            Entity::PowerUp(power_up) => &mut power_up.transform,
         }
     }
 
@@ -393,6 +824,7 @@ impl Entity {
             Entity::Spacecraft(spacecraft) => Some(&spacecraft.movement),
             Entity::Asteroid(asteroid) => Some(&asteroid.movement),
             Entity::Bullet(bullet) => Some(&bullet.movement),
+            Entity::Particle(particle) => Some(&particle.movement),
 
             _ => None,
         }
@@ -404,6 +836,7 @@ impl Entity {
             Entity::Spacecraft(spacecraft) => Some(&mut spacecraft.movement),
             Entity::Asteroid(asteroid) => Some(&mut asteroid.movement),
             Entity::Bullet(bullet) => Some(&mut bullet.movement),
+            Entity::Particle(particle) => Some(&mut particle.movement),
 
             _ => None,
         }
@@ -415,6 +848,7 @@ impl Entity {
             Entity::Spacecraft(spacecraft) => Some(&spacecraft.collider),
             Entity::Asteroid(asteroid) => Some(&asteroid.collider),
             Entity::Bullet(bullet) => Some(&bullet.collider),
+            Entity::PowerUp(power_up) => Some(&power_up.collider),
 
             _ => None,
         }
@@ -426,6 +860,53 @@ impl Entity {
             Entity::Spacecraft(spacecraft) => Some(&mut spacecraft.collider),
             Entity::Asteroid(asteroid) => Some(&mut asteroid.collider),
             Entity::Bullet(bullet) => Some(&mut bullet.collider),
+            Entity::PowerUp(power_up) => Some(&mut power_up.collider),
+
+            _ => None,
+        }
+    }
+
+    /// Gets immutable reference to [BlinkComponent]
+    pub fn blink(&self) -> Option<&BlinkComponent> {
+        if let Entity::Spacecraft(spacecraft) = self {
+            Some(&spacecraft.blink)
+        } else {
+            None
+        }
+    }
+
+    /// Gets mutable reference to [BlinkComponent]
+    pub fn blink_mut(&mut self) -> Option<&mut BlinkComponent> {
+        if let Entity::Spacecraft(spacecraft) = self {
+            Some(&mut spacecraft.blink)
+        } else {
+            None
+        }
+    }
+
+    /// Gets immutable reference to [RenderComponent]
+    pub fn render(&self) -> Option<&RenderComponent> {
+        match self {
+            Entity::Spacecraft(spacecraft) => Some(&spacecraft.render),
+            Entity::Asteroid(asteroid) => Some(&asteroid.render),
+            Entity::Bullet(bullet) => Some(&bullet.render),
+            Entity::Afterimage(afterimage) => Some(&afterimage.render),
+            Entity::Particle(particle) => Some(&particle.render),
+            Entity::PowerUp(power_up) => Some(&power_up.render),
+
+            _ => None,
+        }
+    }
+
+    /// Gets mutable reference to [RenderComponent]
+    pub fn render_mut(&mut self) -> Option<&mut RenderComponent> {
+        match self {
+            Entity::Spacecraft(spacecraft) => Some(&mut spacecraft.render),
+            Entity::Asteroid(asteroid) => Some(&mut asteroid.render),
+            Entity::Bullet(bullet) => Some(&mut bullet.render),
+            Entity::Afterimage(afterimage) => Some(&mut afterimage.render),
+            Entity::Particle(particle) => Some(&mut particle.render),
+            Entity::PowerUp(power_up) => Some(&mut power_up.render),
 
             _ => None,
         }
@@ -476,6 +957,30 @@ impl Entity {
         }
     }
 
+    /// Gets immutable reference to [LifetimeComponent]
+    pub fn lifetime(&self) -> Option<&LifetimeComponent> {
+        match self {
+            Entity::Bullet(bullet) => Some(&bullet.lifetime),
+            Entity::Afterimage(afterimage) => Some(&afterimage.lifetime),
+            Entity::Particle(particle) => Some(&particle.lifetime),
+            Entity::PowerUp(power_up) => Some(&power_up.lifetime),
+
+            _ => None,
+        }
+    }
+
+    /// Gets mutable reference to [LifetimeComponent]
+    pub fn lifetime_mut(&mut self) -> Option<&mut LifetimeComponent> {
+        match self {
+            Entity::Bullet(bullet) => Some(&mut bullet.lifetime),
+            Entity::Afterimage(afterimage) => Some(&mut afterimage.lifetime),
+            Entity::Particle(particle) => Some(&mut particle.lifetime),
+            Entity::PowerUp(power_up) => Some(&mut power_up.lifetime),
+
+            _ => None,
+        }
+    }
+
     /// Gets immutable reference to [BulletComponent]
     pub fn bullet(&self) -> Option<&BulletComponent> {
         if let Entity::Bullet(bullet) = self {
@@ -510,18 +1015,71 @@ impl From<Bullet> for Entity {
     }
 }
 
+impl From<Afterimage> for Entity {
+    fn from(value: Afterimage) -> Self {
+        Self::Afterimage(value)
+    }
+}
+
+impl From<Particle> for Entity {
+    fn from(value: Particle) -> Self {
+        Self::Particle(value)
+    }
+}
+
+impl From<PowerUp> for Entity {
+    fn from(value: PowerUp) -> Self {
+        Self::PowerUp(value)
+    }
+}
+
 /// Constants
 pub mod consts {
-    use glam::Vec2;
+    use std::time::Duration;
+
+    use glam::{Vec2, Vec3};
 
-    use crate::game::physics::{Collider, PointCollider, TriangleCollider};
+    use crate::game::physics::{CircleCollider, Collider, PointCollider, TriangleCollider};
 
     /// Reference to general entity pipeline asset
     pub const ENTITY_PIPELINE_ASSET_REF: &str = "pipelines/entity";
 
+    /// Reference to instanced entity pipeline asset, used to batch entities with same mesh
+    pub const INSTANCED_ENTITY_PIPELINE_ASSET_REF: &str = "pipelines/entity_instanced";
+
+    /// Reference to textured entity pipeline asset
+    pub const SPRITE_PIPELINE_ASSET_REF: &str = "pipelines/sprite";
+
+    /// Reference to textured quad mesh asset
+    pub const QUAD_MESH_ASSET_REF: &str = "meshes/quad";
+
+    /// Reference to post-processing pipeline asset
+    pub const POST_PROCESS_PIPELINE_ASSET_REF: &str = "pipelines/post_process";
+
+    /// Reference to line pipeline asset, used by debug overlays
+    pub const LINE_PIPELINE_ASSET_REF: &str = "pipelines/line";
+
+    /// Reference to full-screen triangle mesh asset
+    pub const FULLSCREEN_MESH_ASSET_REF: &str = "meshes/fullscreen";
+
+    /// Reference to text pipeline asset, used by HUD overlay
+    pub const TEXT_PIPELINE_ASSET_REF: &str = "pipelines/text";
+
+    /// Reference to built-in bitmap font atlas texture asset
+    pub const FONT_TEXTURE_ASSET_REF: &str = "textures/font";
+
     /// Initial distance from object to camera center
     pub const CAMERA_INITIAL_DISTANCE: f32 = 4.0;
 
+    /// Minimal distance from object to camera center
+    pub const CAMERA_MIN_DISTANCE: f32 = 1.0;
+
+    /// Maximal distance from object to camera center
+    pub const CAMERA_MAX_DISTANCE: f32 = 32.0;
+
+    /// Speed of detached camera panning per unit of camera distance
+    pub const CAMERA_PAN_SPEED: f32 = 1.5;
+
     /// Default collider of spacecraft
     pub const SPACECRAFT_COLLIDER: Collider = Collider::Triangle(TriangleCollider {
         center: Vec2::ZERO,
@@ -533,21 +1091,171 @@ pub mod consts {
         radius: 0.5,
     });
 
+    /// Default color of spacecraft
+    pub const SPACECRAFT_COLOR: Vec3 = Vec3::new(0.1, 0.8, 0.1);
+
+    /// Color of AI spacecraft
+    pub const AI_SPACECRAFT_COLOR: Vec3 = Vec3::new(0.8, 0.1, 0.1);
+
+    /// Default angle in radians between AI spacecraft heading and its target, within which it fires
+    pub const AI_FIRE_ANGLE: f32 = 0.15;
+
+    /// Default distance to target, within which AI spacecraft fires
+    pub const AI_FIRE_DISTANCE: f32 = 20.0;
+
+    /// Default color of asteroid
+    pub const ASTEROID_COLOR: Vec3 = Vec3::new(0.6, 0.6, 0.6);
+
+    /// Default color of bullet
+    pub const BULLET_COLOR: Vec3 = Vec3::new(1.0, 1.0, 1.0);
+
     /// Reference to spacecraft mesh asset
     pub const SPACECRAFT_MESH_ASSET_REF: &str = "meshes/spacecraft";
 
-    /// Prefix of reference to asteroid mesh asset
-    pub const ASTEROID_MESH_ASSET_REF_PREFIX: &str = "meshes/asteroids/";
+    /// Reference to asteroid mesh asset
+    pub const ASTEROID_MESH_ASSET_REF: &str = "meshes/asteroid";
 
     /// Reference to bullet mesh asset
     pub const BULLET_MESH_ASSET_REF: &str = "meshes/bullet";
 
+    /// Prefix of references to explosion fragment mesh assets
+    pub const FRAGMENT_MESH_ASSET_REF: &str = "meshes/fragment";
+
+    /// Lifetime of explosion fragment in seconds
+    pub const FRAGMENT_LIFETIME: f32 = 0.8;
+
     /// Count of segments in single asteroid
     pub const ASTEROID_SEGMENTS_COUNT: usize = 8;
 
+    /// Seed of random generator of asteroid body
+    pub const ASTEROID_BODY_SEED: u64 = 0;
+
+    /// Asteroids of this size or smaller are not split when destroyed
+    pub const ASTEROID_SPLIT_MIN_SIZE: f32 = 1.0;
+
     /// Radius of bullet
     pub const BULLET_RADIUS: f32 = 0.1;
 
+    /// Scale of bullet fired by heavy weapon
+    pub const HEAVY_BULLET_SCALE: f32 = 3.0;
+
+    /// Reloading cooldown of regular weapon in seconds
+    pub const REGULAR_WEAPON_COOLDOWN: f32 = 0.2;
+
+    /// Reloading cooldown of heavy weapon in seconds
+    pub const HEAVY_WEAPON_COOLDOWN: f32 = 0.6;
+
+    /// Reloading cooldown of rapid weapon in seconds
+    pub const RAPID_WEAPON_COOLDOWN: f32 = 0.07;
+
+    /// Angle in radians between bullets of triple weapon
+    pub const TRIPLE_WEAPON_SPREAD: f32 = 0.2;
+
+    /// Lifetime of bullet in seconds
+    pub const BULLET_LIFETIME: f32 = 2.0;
+
+    /// Reference to power-up mesh asset
+    pub const POWER_UP_MESH_ASSET_REF: &str = "meshes/power_up";
+
+    /// Radius of power-up
+    pub const POWER_UP_RADIUS: f32 = 0.4;
+
+    /// Lifetime of power-up in seconds, power-up vanishes if it is not picked up
+    pub const POWER_UP_LIFETIME: f32 = 20.0;
+
+    /// Color of shield power-up
+    pub const SHIELD_POWER_UP_COLOR: Vec3 = Vec3::new(0.2, 0.6, 1.0);
+
+    /// Color of weapon power-up
+    pub const WEAPON_POWER_UP_COLOR: Vec3 = Vec3::new(1.0, 0.8, 0.2);
+
+    /// Duration of shield granted by power-up in seconds
+    pub const SHIELD_DURATION: f32 = 5.0;
+
+    /// Default collider of power-up
+    pub const POWER_UP_COLLIDER: Collider = Collider::Circle(CircleCollider {
+        center: Vec2::ZERO,
+        radius: POWER_UP_RADIUS,
+    });
+
+    /// Period in seconds between afterimages left by boosting spacecraft
+    pub const AFTERIMAGE_PERIOD: f32 = 0.05;
+
+    /// Lifetime of afterimage in seconds, afterimage fades out meanwhile
+    pub const AFTERIMAGE_LIFETIME: f32 = 0.4;
+
+    /// Initial opacity of afterimage
+    pub const AFTERIMAGE_ALPHA: f32 = 0.5;
+
+    /// Period in seconds between thruster exhaust particles emitted by accelerating spacecraft
+    pub const THRUSTER_PARTICLE_PERIOD: f32 = 0.03;
+
+    /// Lifetime of thruster exhaust particle in seconds
+    pub const THRUSTER_PARTICLE_LIFETIME: f32 = 0.3;
+
+    /// Scale of thruster exhaust particle mesh
+    pub const THRUSTER_PARTICLE_SCALE: f32 = 0.5;
+
+    /// Color of thruster exhaust particle
+    pub const THRUSTER_PARTICLE_COLOR: Vec3 = Vec3::new(1.0, 0.6, 0.1);
+
+    /// Maximal count of particles in the world, particles are not emitted beyond it
+    pub const MAX_PARTICLES_COUNT: usize = 256;
+
+    /// Period of visibility toggle of blinking entity in seconds
+    pub const BLINK_PERIOD: f32 = 0.1;
+
+    /// Duration of spacecraft invulnerability after spawn in seconds, spacecraft blinks meanwhile
+    pub const SPACECRAFT_SPAWN_INVULNERABILITY: f32 = 2.0;
+
+    /// Initial count of player lives
+    pub const PLAYER_INITIAL_LIVES: u32 = 3;
+
+    /// Background color of the scene
+    pub const BACKGROUND_COLOR: [f32; 4] = [0.0, 0.0, 0.0, 1.0];
+
+    /// Background color of the scene at the start of flash on player death
+    pub const DEATH_FLASH_COLOR: [f32; 4] = [0.6, 0.0, 0.0, 1.0];
+
+    /// Duration in seconds of background flash on player death
+    pub const DEATH_FLASH_DURATION: f32 = 0.3;
+
+    /// Delay in seconds before player spacecraft is respawned
+    pub const PLAYER_RESPAWN_DELAY: f32 = 2.0;
+
+    /// Interval of score milestones, player gets bonus life at every milestone
+    pub const BONUS_LIFE_SCORE_INTERVAL: u32 = 1000;
+
+    /// Path to JSON file with scoring table, loaded at startup
+    pub const SCORING_TABLE_PATH: &str = "scoring.json";
+
+    /// Path to JSON file with HUD layout, loaded at startup
+    pub const HUD_LAYOUT_PATH: &str = "hud.json";
+
+    /// Time window in seconds in which next kill continues combo
+    pub const COMBO_WINDOW: f32 = 2.0;
+
+    /// Increase of score multiplier per consecutive kill in combo
+    pub const COMBO_MULTIPLIER_STEP: f32 = 0.25;
+
+    /// Maximal score multiplier of combo
+    pub const COMBO_MAX_MULTIPLIER: f32 = 4.0;
+
+    /// Determines whether entities wrap around play field edges instead of being despawned far away from players
+    pub const WORLD_WRAPAROUND: bool = false;
+
+    /// Half of play field size, used when [WORLD_WRAPAROUND] is enabled
+    pub const WORLD_HALF_EXTENT: Vec2 = Vec2::new(100.0, 100.0);
+
+    /// Count of physics updates per second, independent of ECS update rate
+    pub const PHYSICS_TARGET_HZ: f32 = 120.0;
+
+    /// Path to CSV file with performance metrics of session, exported when `toggle_metrics_export` is invoked
+    pub const METRICS_PATH: &str = "metrics.csv";
+
+    /// Interval between rows of performance metrics
+    pub const METRICS_INTERVAL: Duration = Duration::from_millis(250);
+
     /// Default collider of bullet
     pub const BULLET_COLLIDER: Collider = Collider::Point(PointCollider {
         center: Vec2::ZERO,
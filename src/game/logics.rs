@@ -1,22 +1,33 @@
 use std::{
     f32::consts::PI,
-    iter::once,
+    fmt, fs, io,
     ops::RangeInclusive,
+    path::{Path, PathBuf},
+    str::FromStr,
     sync::{
         Arc, Mutex,
         atomic::{AtomicBool, Ordering},
     },
 };
 
-use glam::Vec2;
+use glam::{Vec2, Vec3};
 use rand::seq::IteratorRandom;
+use serde::Deserialize;
 
 use crate::{
     assets::{self, types},
     consts::VEC2_RIGHT,
-    events,
-    game::{controller::Controller, ecs::ECS, entities, players::Players},
-    rendering::{backend, pipeline, render_graph, render_graph_operation, renderer},
+    diagnostics, events,
+    game::{
+        controller::Controller,
+        ecs::{ECS, EntitiesRead},
+        entities,
+        players::{self, Players},
+    },
+    handle,
+    rendering::{
+        backend, image, pipeline, render_graph, render_graph_operation, renderer, sampler,
+    },
     scene,
 };
 
@@ -61,18 +72,29 @@ impl InitGameLogicState {
 
 /// Game logic for single time initialization
 pub fn init_game_logic(_: f32, state: &InitGameLogicState) {
+    const SAMPLES: image::SampleCount = image::SampleCount::Sample4;
+    const MAX_CACHED_MODELS: usize = 4096;
+    const CAMERA_FOLLOW_SMOOTHING: f32 = 8.0;
+    const CAMERA_FOLLOW_SNAP_DISTANCE: f32 = 20.0;
+    const POST_PROCESS_INTENSITY: f32 = 0.35;
+    const DEBUG_GRAPH_FRAMES: usize = 240;
+    // NOTE: debug graphs are placed in bottom-left corner of the screen, so they do not overlap HUD texts
+    const FRAME_TIME_GRAPH_ORIGIN: [f32; 2] = [-0.95, 0.5];
+    const ENTITY_COUNT_GRAPH_ORIGIN: [f32; 2] = [-0.95, 0.9];
+
     if state.initialized.load(Ordering::Relaxed) {
         return;
     }
 
     state.initialized.store(true, Ordering::Relaxed);
 
-    state.assets.load(
-        entities::consts::ENTITY_PIPELINE_ASSET_REF.into(),
+    load_pipeline(
+        &state.assets,
+        entities::consts::ENTITY_PIPELINE_ASSET_REF,
         assets::PipelineAssetDef {
             shaders: vec![
-                Box::new(assets::shaders::entity::vs::load),
-                Box::new(assets::shaders::entity::fs::load),
+                Arc::new(assets::shaders::entity::vs::load),
+                Arc::new(assets::shaders::entity::fs::load),
             ],
             bindings: vec![pipeline::InputDataBinding {
                 stride: std::mem::size_of::<types::Vertex>(),
@@ -82,30 +104,248 @@ pub fn init_game_logic(_: f32, state: &InitGameLogicState) {
                     format: pipeline::InputDataFormat::Vec2,
                 }],
             }],
+            samples: SAMPLES,
+            topology: pipeline::PipelineTopology::TriangleList,
+            depth_test: true,
+        },
+    );
+
+    load_pipeline(
+        &state.assets,
+        entities::consts::INSTANCED_ENTITY_PIPELINE_ASSET_REF,
+        assets::PipelineAssetDef {
+            shaders: vec![
+                Arc::new(assets::shaders::instanced_entity::vs::load),
+                Arc::new(assets::shaders::entity::fs::load),
+            ],
+            bindings: vec![
+                pipeline::InputDataBinding {
+                    stride: std::mem::size_of::<types::Vertex>(),
+                    rate: pipeline::InputDataRate::PerVertex,
+                    attributes: vec![pipeline::InputDataAttribute {
+                        offset: 0,
+                        format: pipeline::InputDataFormat::Vec2,
+                    }],
+                },
+                pipeline::InputDataBinding {
+                    stride: std::mem::size_of::<types::Model>(),
+                    rate: pipeline::InputDataRate::PerInstance,
+                    attributes: [
+                        pipeline::InputDataAttribute {
+                            offset: std::mem::offset_of!(types::Model, color),
+                            format: pipeline::InputDataFormat::Vec3,
+                        },
+                        pipeline::InputDataAttribute {
+                            offset: std::mem::offset_of!(types::Model, alpha),
+                            format: pipeline::InputDataFormat::Float,
+                        },
+                    ]
+                    .into_iter()
+                    .chain((0..4).map(|column| pipeline::InputDataAttribute {
+                        offset: std::mem::offset_of!(types::Model, matrix)
+                            + column * std::mem::size_of::<glam::Vec4>(),
+                        format: pipeline::InputDataFormat::Vec4,
+                    }))
+                    .collect(),
+                },
+            ],
+            samples: SAMPLES,
+            topology: pipeline::PipelineTopology::TriangleList,
+            depth_test: true,
+        },
+    );
+
+    load_pipeline(
+        &state.assets,
+        entities::consts::SPRITE_PIPELINE_ASSET_REF,
+        assets::PipelineAssetDef {
+            shaders: vec![
+                Arc::new(assets::shaders::sprite::vs::load),
+                Arc::new(assets::shaders::sprite::fs::load),
+            ],
+            bindings: vec![pipeline::InputDataBinding {
+                stride: std::mem::size_of::<types::Vertex>(),
+                rate: pipeline::InputDataRate::PerVertex,
+                attributes: vec![
+                    pipeline::InputDataAttribute {
+                        offset: std::mem::offset_of!(types::Vertex, position),
+                        format: pipeline::InputDataFormat::Vec2,
+                    },
+                    pipeline::InputDataAttribute {
+                        offset: std::mem::offset_of!(types::Vertex, uv),
+                        format: pipeline::InputDataFormat::Vec2,
+                    },
+                ],
+            }],
+            samples: SAMPLES,
+            topology: pipeline::PipelineTopology::TriangleList,
+            depth_test: true,
+        },
+    );
+
+    load_pipeline(
+        &state.assets,
+        entities::consts::POST_PROCESS_PIPELINE_ASSET_REF,
+        assets::PipelineAssetDef {
+            shaders: vec![
+                Arc::new(assets::shaders::post_process::vs::load),
+                Arc::new(assets::shaders::post_process::fs::load),
+            ],
+            bindings: vec![pipeline::InputDataBinding {
+                stride: std::mem::size_of::<types::Vertex>(),
+                rate: pipeline::InputDataRate::PerVertex,
+                attributes: vec![
+                    pipeline::InputDataAttribute {
+                        offset: std::mem::offset_of!(types::Vertex, position),
+                        format: pipeline::InputDataFormat::Vec2,
+                    },
+                    pipeline::InputDataAttribute {
+                        offset: std::mem::offset_of!(types::Vertex, uv),
+                        format: pipeline::InputDataFormat::Vec2,
+                    },
+                ],
+            }],
+            samples: image::SampleCount::Sample1,
+            topology: pipeline::PipelineTopology::TriangleList,
+            depth_test: false,
+        },
+    );
+
+    load_pipeline(
+        &state.assets,
+        entities::consts::LINE_PIPELINE_ASSET_REF,
+        assets::PipelineAssetDef {
+            shaders: vec![
+                Arc::new(assets::shaders::line::vs::load),
+                Arc::new(assets::shaders::line::fs::load),
+            ],
+            bindings: vec![pipeline::InputDataBinding {
+                stride: std::mem::size_of::<types::Vertex>(),
+                rate: pipeline::InputDataRate::PerVertex,
+                attributes: vec![pipeline::InputDataAttribute {
+                    offset: std::mem::offset_of!(types::Vertex, position),
+                    format: pipeline::InputDataFormat::Vec2,
+                }],
+            }],
+            samples: image::SampleCount::Sample1,
+            topology: pipeline::PipelineTopology::LineStrip,
+            depth_test: false,
+        },
+    );
+
+    load_pipeline(
+        &state.assets,
+        entities::consts::TEXT_PIPELINE_ASSET_REF,
+        assets::PipelineAssetDef {
+            shaders: vec![
+                Arc::new(assets::shaders::text::vs::load),
+                Arc::new(assets::shaders::text::fs::load),
+            ],
+            bindings: vec![pipeline::InputDataBinding {
+                stride: std::mem::size_of::<types::TextVertex>(),
+                rate: pipeline::InputDataRate::PerVertex,
+                attributes: vec![
+                    pipeline::InputDataAttribute {
+                        offset: std::mem::offset_of!(types::TextVertex, position),
+                        format: pipeline::InputDataFormat::Vec2,
+                    },
+                    pipeline::InputDataAttribute {
+                        offset: std::mem::offset_of!(types::TextVertex, uv),
+                        format: pipeline::InputDataFormat::Vec2,
+                    },
+                    pipeline::InputDataAttribute {
+                        offset: std::mem::offset_of!(types::TextVertex, color),
+                        format: pipeline::InputDataFormat::Vec4,
+                    },
+                ],
+            }],
+            samples: image::SampleCount::Sample1,
+            topology: pipeline::PipelineTopology::TriangleList,
+            depth_test: false,
         },
     );
 
+    let (font_extent, font_pixels) = assets::font::atlas();
+
     state.assets.load(
-        entities::consts::SPACECRAFT_MESH_ASSET_REF.into(),
+        entities::consts::FONT_TEXTURE_ASSET_REF.into(),
+        assets::RawTextureAssetDef {
+            extent: font_extent,
+            pixels: font_pixels,
+            filter: sampler::SamplerFilter::Nearest,
+        },
+    );
+
+    load_mesh(
+        &state.assets,
+        entities::consts::FULLSCREEN_MESH_ASSET_REF,
+        assets::MeshAssetDef {
+            vertices: assets::models::fullscreen::VERTICES.into(),
+            indices: assets::models::fullscreen::INDICES.into(),
+        },
+    );
+
+    load_mesh(
+        &state.assets,
+        entities::consts::QUAD_MESH_ASSET_REF,
+        assets::MeshAssetDef {
+            vertices: assets::models::quad::VERTICES.into(),
+            indices: assets::models::quad::INDICES.into(),
+        },
+    );
+
+    load_mesh(
+        &state.assets,
+        entities::consts::SPACECRAFT_MESH_ASSET_REF,
         assets::MeshAssetDef {
             vertices: assets::models::spacecraft::VERTICES.into(),
             indices: assets::models::spacecraft::INDICES.into(),
         },
     );
 
-    state.assets.load(
-        entities::consts::BULLET_MESH_ASSET_REF.into(),
+    load_mesh(
+        &state.assets,
+        entities::consts::BULLET_MESH_ASSET_REF,
         assets::MeshAssetDef {
             vertices: assets::models::bullet::VERTICES.into(),
             indices: assets::models::bullet::INDICES.into(),
         },
     );
 
+    state.assets.load(
+        entities::consts::ASTEROID_MESH_ASSET_REF.into(),
+        assets::MeshAssetDef {
+            vertices: assets::models::asteroid::vertices(&entities::AsteroidComponent::body()),
+            indices: assets::models::asteroid::indices(),
+        },
+    );
+
+    load_mesh(
+        &state.assets,
+        entities::consts::POWER_UP_MESH_ASSET_REF,
+        assets::MeshAssetDef {
+            vertices: assets::models::power_up::VERTICES.into(),
+            indices: assets::models::power_up::INDICES.into(),
+        },
+    );
+
+    for (index, (_, triangle)) in entities::AsteroidComponent::fragments().iter().enumerate() {
+        state.assets.load(
+            entities::AsteroidComponent::fragment_mesh_asset_ref(index),
+            assets::MeshAssetDef {
+                vertices: assets::models::asteroid::fragment_vertices(triangle),
+                indices: assets::models::asteroid::fragment_indices(),
+            },
+        );
+    }
+
     let player_id = state.players.new_player();
 
     let camera = entities::Camera {
         camera: entities::CameraComponent {
             target: entities::CameraTarget::Player(player_id),
+            follow_smoothing: CAMERA_FOLLOW_SMOOTHING,
+            follow_snap_distance: Some(CAMERA_FOLLOW_SNAP_DISTANCE),
             ..Default::default()
         },
         ..Default::default()
@@ -119,48 +359,214 @@ pub fn init_game_logic(_: f32, state: &InitGameLogicState) {
         "default",
         render_graph::RenderGraphBuilder::default()
             .add_target("swapchain", render_graph::Target::Swapchain)
+            .add_target(
+                "scene",
+                render_graph::Target::Image {
+                    usage: image::ImageUsage::COLOR_ATTACHMENT | image::ImageUsage::SAMPLED,
+                    format: image::ImageFormat::Swapchain,
+                },
+            )
+            .add_target(
+                "depth",
+                render_graph::Target::Image {
+                    usage: image::ImageUsage::DEPTH_ATTACHMENT,
+                    format: image::ImageFormat::Depth,
+                },
+            )
             .add_pass(|pass_builder| {
                 pass_builder
                     .add_color(render_graph::Attachment {
-                        target: "swapchain".into(),
+                        target: "scene".into(),
                         load_op: render_graph::AttachmentLoadOp::Clear(
-                            render_graph::ClearValue::Float([0.0, 0.0, 0.0, 1.0]),
+                            render_graph::ClearValue::Float(entities::consts::BACKGROUND_COLOR),
                         ),
                         store_op: render_graph::AttachmentStoreOp::Store,
                     })
+                    .set_samples(SAMPLES)
+                    .set_depth(render_graph::Attachment {
+                        target: "depth".into(),
+                        load_op: render_graph::AttachmentLoadOp::Clear(
+                            render_graph::ClearValue::Depth(1.0),
+                        ),
+                        store_op: render_graph::AttachmentStoreOp::Ignore,
+                    })
                     .set_operation(render_graph::StatefulOperation::new(
                         render_graph_operation::SceneRenderingOperationState::new(
                             &state.events,
                             state.backend.clone(),
                             state.assets.clone(),
                             state.scene.clone(),
+                            MAX_CACHED_MODELS,
+                        )
+                        .with_instancing(
+                            entities::consts::ENTITY_PIPELINE_ASSET_REF.into(),
+                            entities::consts::INSTANCED_ENTITY_PIPELINE_ASSET_REF.into(),
                         ),
                         render_graph_operation::scene_rendering_operation,
                     ))
             })
+            .add_pass(|pass_builder| {
+                pass_builder
+                    .add_color(render_graph::Attachment {
+                        target: "swapchain".into(),
+                        load_op: render_graph::AttachmentLoadOp::Ignore,
+                        store_op: render_graph::AttachmentStoreOp::Store,
+                    })
+                    .set_samples(image::SampleCount::Sample1)
+                    .add_input("scene")
+                    .set_operation(render_graph::StatefulOperation::new(
+                        render_graph_operation::PostProcessOperationState::new(
+                            state.backend.clone(),
+                            state.assets.clone(),
+                            entities::consts::POST_PROCESS_PIPELINE_ASSET_REF.into(),
+                            entities::consts::FULLSCREEN_MESH_ASSET_REF.into(),
+                        ),
+                        render_graph_operation::post_process_operation,
+                    ))
+            })
+            // NOTE: HUD is drawn after post-processing, so it is not affected by vignette
+            .add_pass(|pass_builder| {
+                pass_builder
+                    .add_color(render_graph::Attachment {
+                        target: "swapchain".into(),
+                        load_op: render_graph::AttachmentLoadOp::Load,
+                        store_op: render_graph::AttachmentStoreOp::Store,
+                    })
+                    .set_samples(image::SampleCount::Sample1)
+                    .set_operation(render_graph::StatefulOperation::new(
+                        render_graph_operation::TextRenderingOperationState::new(
+                            state.backend.clone(),
+                            state.assets.clone(),
+                            state.scene.clone(),
+                            entities::consts::TEXT_PIPELINE_ASSET_REF.into(),
+                            entities::consts::FONT_TEXTURE_ASSET_REF.into(),
+                        ),
+                        render_graph_operation::text_rendering_operation,
+                    ))
+            })
             .build(),
-        [("view_entity_id", render_graph::Arg::EntityId(camera_id))],
+        [
+            ("view_entity_id", render_graph::Arg::EntityId(camera_id)),
+            (
+                "post_process_intensity",
+                render_graph::Arg::Float(POST_PROCESS_INTENSITY),
+            ),
+        ],
     );
+
+    // NOTE: frame time and entity count graphs are debug overlays, they are drawn over default graph in debug builds only
+    if cfg!(debug_assertions) {
+        let ecs = state.ecs.clone();
+
+        state.renderer.add_graph_ordered(
+            renderer::DEFAULT_RENDER_GRAPH_ORDER + 1,
+            "debug_overlay",
+            render_graph::RenderGraphBuilder::default()
+                .add_target("swapchain", render_graph::Target::Swapchain)
+                .add_pass(|pass_builder| {
+                    pass_builder
+                        .add_color(render_graph::Attachment {
+                            target: "swapchain".into(),
+                            load_op: render_graph::AttachmentLoadOp::Load,
+                            store_op: render_graph::AttachmentStoreOp::Store,
+                        })
+                        .set_samples(image::SampleCount::Sample1)
+                        .set_operation(render_graph::StatefulOperation::new(
+                            render_graph_operation::FrameTimeGraphOperationState::new(
+                                state.backend.clone(),
+                                state.assets.clone(),
+                                entities::consts::LINE_PIPELINE_ASSET_REF.into(),
+                                DEBUG_GRAPH_FRAMES,
+                            ),
+                            render_graph_operation::frame_time_graph_operation,
+                        ))
+                })
+                .add_pass(|pass_builder| {
+                    pass_builder
+                        .add_color(render_graph::Attachment {
+                            target: "swapchain".into(),
+                            load_op: render_graph::AttachmentLoadOp::Load,
+                            store_op: render_graph::AttachmentStoreOp::Store,
+                        })
+                        .set_samples(image::SampleCount::Sample1)
+                        .set_operation(render_graph::StatefulOperation::new(
+                            render_graph_operation::EntityCountGraphOperationState::new(
+                                state.backend.clone(),
+                                state.assets.clone(),
+                                entities::consts::LINE_PIPELINE_ASSET_REF.into(),
+                                DEBUG_GRAPH_FRAMES,
+                                move || ecs.read().len(),
+                            ),
+                            render_graph_operation::entity_count_graph_operation,
+                        ))
+                })
+                .build(),
+            [
+                (
+                    "frame_time_graph_origin",
+                    render_graph::Arg::Vec2(FRAME_TIME_GRAPH_ORIGIN),
+                ),
+                (
+                    "entity_count_graph_origin",
+                    render_graph::Arg::Vec2(ENTITY_COUNT_GRAPH_ORIGIN),
+                ),
+            ],
+        );
+    }
+}
+
+/// INTERNAL: path to file of built-in asset in assets directory of the crate
+fn asset_path(asset_ref: &str, extension: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("assets")
+        .join(format!("{}.{}", asset_ref, extension))
+}
+
+/// INTERNAL: loads built-in pipeline; in debug builds its fragment shader is replaced by SPIR-V file
+/// `assets/<asset ref>.frag.spv` and reloaded each time the file changes
+fn load_pipeline(assets: &assets::Assets, asset_ref: &str, definition: assets::PipelineAssetDef) {
+    const FRAGMENT_SHADER_INDEX: usize = 1;
+
+    assets.load(asset_ref.into(), definition.clone());
+
+    if cfg!(debug_assertions) {
+        assets.load_from_path_with(
+            asset_ref.into(),
+            asset_path(asset_ref, "frag.spv"),
+            move |shader| {
+                definition
+                    .clone()
+                    .with_shader(FRAGMENT_SHADER_INDEX, shader)
+            },
+        );
+    }
+}
+
+/// INTERNAL: loads built-in mesh; in debug builds it is replaced by file `assets/<asset ref>.json` and reloaded each
+/// time the file changes
+fn load_mesh(assets: &assets::Assets, asset_ref: &str, definition: assets::MeshAssetDef) {
+    assets.load(asset_ref.into(), definition);
+
+    if cfg!(debug_assertions) {
+        assets.load_from_path::<assets::MeshAssetDef, _>(
+            asset_ref.into(),
+            asset_path(asset_ref, "json"),
+        );
+    }
 }
 
 /// State for [asteroids_respawn_game_logic]
 pub struct AsteroidsRespawnGameLogicState {
     passed: Mutex<f32>,
-    assets: Arc<assets::Assets>,
     ecs: Arc<ECS>,
     players: Arc<Players>,
 }
 
 impl AsteroidsRespawnGameLogicState {
     /// Creates new instance of [AsteroidsRespawnGameLogicState]
-    pub fn new(
-        assets: Arc<assets::Assets>,
-        ecs: Arc<ECS>,
-        players: Arc<Players>,
-    ) -> AsteroidsRespawnGameLogicState {
+    pub fn new(ecs: Arc<ECS>, players: Arc<Players>) -> AsteroidsRespawnGameLogicState {
         AsteroidsRespawnGameLogicState {
             passed: Default::default(),
-            assets,
             ecs,
             players,
         }
@@ -186,10 +592,7 @@ pub fn asteroids_respawn_game_logic(elapsed: f32, state: &AsteroidsRespawnGameLo
 
     let mut entities = state.ecs.write();
 
-    let count = entities
-        .iter()
-        .filter_map(|(_, entity)| entity.asteroid())
-        .count();
+    let count = entities.count_where(|_, entity| entity.asteroid().is_some());
 
     if count >= MAX_ASTEROIDS_COUNT {
         return;
@@ -220,54 +623,328 @@ pub fn asteroids_respawn_game_logic(elapsed: f32, state: &AsteroidsRespawnGameLo
         ..Default::default()
     };
 
-    let asteroid_mesh_def = assets::MeshAssetDef {
-        vertices: once(types::Vertex::default())
-            .chain(
-                asteroid
-                    .asteroid
-                    .body
-                    .iter()
-                    .copied()
-                    .map(|vertex| types::Vertex { position: vertex }),
-            )
-            .collect(),
+    entities.create(asteroid);
+}
 
-        indices: (1..=entities::consts::ASTEROID_SEGMENTS_COUNT)
-            .flat_map(|index| {
-                let next_index = (index + 1) % entities::consts::ASTEROID_SEGMENTS_COUNT;
+/// State for [power_ups_spawn_game_logic]
+pub struct PowerUpsSpawnGameLogicState {
+    passed: Mutex<f32>,
+    ecs: Arc<ECS>,
+    players: Arc<Players>,
+}
+
+impl PowerUpsSpawnGameLogicState {
+    /// Creates new instance of [PowerUpsSpawnGameLogicState]
+    pub fn new(ecs: Arc<ECS>, players: Arc<Players>) -> PowerUpsSpawnGameLogicState {
+        PowerUpsSpawnGameLogicState {
+            passed: Default::default(),
+            ecs,
+            players,
+        }
+    }
+}
+
+/// Game logic for occasional spawning of power-ups near players
+pub fn power_ups_spawn_game_logic(elapsed: f32, state: &PowerUpsSpawnGameLogicState) {
+    const SPAWN_THRESHOLD: f32 = 15.0;
+    const MAX_POWER_UPS_COUNT: usize = 3;
+    const DISTANCE_RANGE: RangeInclusive<f32> = 10.0..=30.0;
+    const ROTATION_RANGE: RangeInclusive<f32> = 0.0..=2.0 * PI;
+    const KINDS: [entities::PowerUpKind; 3] = [
+        entities::PowerUpKind::Shield,
+        entities::PowerUpKind::Weapon(entities::Weapon::Triple),
+        entities::PowerUpKind::Weapon(entities::Weapon::Rapid),
+    ];
 
-                [0, index as u32, next_index.max(1) as u32]
+    let mut passed = state.passed.lock().unwrap();
+
+    *passed += elapsed;
+
+    if *passed < SPAWN_THRESHOLD {
+        return;
+    }
+
+    *passed = 0.0;
+
+    let mut entities = state.ecs.write();
+
+    let count = entities.count_where(|_, entity| matches!(entity, entities::Entity::PowerUp(_)));
+
+    if count >= MAX_POWER_UPS_COUNT {
+        return;
+    }
+
+    let position = state
+        .players
+        .iter()
+        .filter_map(|(_, player)| {
+            player.spacecraft_id.and_then(|spacecraft_id| {
+                entities
+                    .get(spacecraft_id)
+                    .map(|entity| entity.transform().position)
             })
-            .collect(),
+        })
+        .choose(&mut rand::rng());
+
+    let position = match position {
+        Some(position) => position,
+        None => return,
     };
 
-    state
-        .assets
-        .load(asteroid.render.mesh.clone(), asteroid_mesh_def);
+    let distance = rand::random_range(DISTANCE_RANGE);
+    let rotation = rand::random_range(ROTATION_RANGE);
+    let position = position + distance * VEC2_RIGHT.rotate(rotation.sin_cos().into());
 
-    entities.create(asteroid);
+    let kind = KINDS[rand::random_range(0..KINDS.len())];
+
+    let power_up = entities::PowerUp::with_kind(kind);
+    let power_up = entities::PowerUp {
+        transform: entities::TransformComponent {
+            position,
+            ..power_up.transform
+        },
+        ..power_up
+    };
+
+    entities.create(power_up);
+}
+
+/// State for [ai_spawn_game_logic]
+pub struct AiSpawnGameLogicState {
+    passed: Mutex<f32>,
+    ecs: Arc<ECS>,
+    players: Arc<Players>,
+}
+
+impl AiSpawnGameLogicState {
+    /// Creates new instance of [AiSpawnGameLogicState]
+    pub fn new(ecs: Arc<ECS>, players: Arc<Players>) -> AiSpawnGameLogicState {
+        AiSpawnGameLogicState {
+            passed: Default::default(),
+            ecs,
+            players,
+        }
+    }
+}
+
+/// Game logic for spawning AI spacecrafts near players
+pub fn ai_spawn_game_logic(elapsed: f32, state: &AiSpawnGameLogicState) {
+    const SPAWN_THRESHOLD: f32 = 10.0;
+    const MAX_AI_SPACECRAFTS_COUNT: usize = 2;
+    const DISTANCE_RANGE: RangeInclusive<f32> = 30.0..=50.0;
+    const ROTATION_RANGE: RangeInclusive<f32> = 0.0..=2.0 * PI;
+
+    let mut passed = state.passed.lock().unwrap();
+
+    *passed += elapsed;
+
+    if *passed < SPAWN_THRESHOLD {
+        return;
+    }
+
+    *passed = 0.0;
+
+    let mut entities = state.ecs.write();
+
+    let count = entities.count_where(|_, entity| {
+        entity
+            .spacecraft()
+            .is_some_and(|spacecraft| spacecraft.ai.is_some())
+    });
+
+    if count >= MAX_AI_SPACECRAFTS_COUNT {
+        return;
+    }
+
+    let position = state
+        .players
+        .iter()
+        .filter_map(|(_, player)| {
+            player.spacecraft_id.and_then(|spacecraft_id| {
+                entities
+                    .get(spacecraft_id)
+                    .map(|entity| entity.transform().position)
+            })
+        })
+        .choose(&mut rand::rng());
+
+    let position = match position {
+        Some(position) => position,
+        None => return,
+    };
+
+    let distance = rand::random_range(DISTANCE_RANGE);
+    let rotation = rand::random_range(ROTATION_RANGE);
+    let position = position + distance * VEC2_RIGHT.rotate(rotation.sin_cos().into());
+
+    let spacecraft = entities::Spacecraft::default();
+    let spacecraft = entities::Spacecraft {
+        transform: entities::TransformComponent {
+            position,
+            ..Default::default()
+        },
+        spacecraft: entities::SpacecraftComponent {
+            ai: Some(Default::default()),
+            ..Default::default()
+        },
+        render: entities::RenderComponent {
+            color: entities::consts::AI_SPACECRAFT_COLOR,
+            ..spacecraft.render
+        },
+        ..spacecraft
+    };
+
+    entities.create(spacecraft);
+}
+
+/// INTERNAL: explosion, which is not yet turned into fragments
+struct PendingExplosion {
+    position: Vec2,
+    size: f32,
+    color: Vec3,
+}
+
+/// State for [explosions_game_logic]
+pub struct ExplosionsGameLogicState {
+    pending: Arc<Mutex<Vec<PendingExplosion>>>,
+    ecs: Arc<ECS>,
+
+    _handler: handle::Handle,
+}
+
+impl ExplosionsGameLogicState {
+    /// Creates new instance of [ExplosionsGameLogicState]
+    pub fn new(events: &events::Events, ecs: Arc<ECS>) -> ExplosionsGameLogicState {
+        let pending: Arc<Mutex<Vec<PendingExplosion>>> = Default::default();
+
+        ExplosionsGameLogicState {
+            pending: pending.clone(),
+            ecs,
+
+            _handler: events.add_handler(move |event| {
+                if let events::Event::EntityExploded {
+                    position,
+                    size,
+                    color,
+                } = event
+                {
+                    pending.lock().unwrap().push(PendingExplosion {
+                        position: *position,
+                        size: *size,
+                        color: *color,
+                    });
+                }
+            }),
+        }
+    }
+}
+
+/// Game logic for explosions: bursts destroyed entities into fragments, which fly outward and vanish.
+/// Fragments are particles without colliders, so they are purely visual
+pub fn explosions_game_logic(_: f32, state: &ExplosionsGameLogicState) {
+    const VELOCITY_RANGE: RangeInclusive<f32> = 1.5..=3.0;
+    const ROTATION_RANGE: RangeInclusive<f32> = 0.0..=2.0 * PI;
+
+    let pending: Vec<_> = state.pending.lock().unwrap().drain(..).collect();
+
+    if pending.is_empty() {
+        return;
+    }
+
+    let fragments = entities::AsteroidComponent::fragments();
+    let mut entities = state.ecs.write();
+
+    for explosion in pending {
+        let particles_count =
+            entities.count_where(|_, entity| matches!(entity, entities::Entity::Particle(_)));
+
+        if particles_count + fragments.len() > entities::consts::MAX_PARTICLES_COUNT {
+            continue;
+        }
+
+        let rotation = rand::random_range(ROTATION_RANGE);
+        let sin_cos = Vec2::from_angle(rotation);
+
+        for (index, (center, _)) in fragments.iter().enumerate() {
+            let offset = explosion.size * center.rotate(sin_cos);
+            let velocity = rand::random_range(VELOCITY_RANGE) * offset.normalize_or_zero();
+
+            let particle = entities::Particle::default();
+            let particle = entities::Particle {
+                transform: entities::TransformComponent {
+                    position: explosion.position + offset,
+                    rotation,
+                    scale: Vec2::splat(explosion.size),
+                },
+                movement: entities::MovementComponent {
+                    velocity,
+                    ..particle.movement
+                },
+                lifetime: entities::LifetimeComponent {
+                    remaining: entities::consts::FRAGMENT_LIFETIME,
+                },
+                render: entities::RenderComponent {
+                    mesh: entities::AsteroidComponent::fragment_mesh_asset_ref(index),
+                    color: explosion.color,
+                    ..particle.render
+                },
+            };
+
+            entities.create(particle);
+        }
+    }
 }
 
 /// State for [players_respawn_game_logic]
 pub struct PlayersRespawnGameLogicState {
+    event_sender: events::Sender,
     ecs: Arc<ECS>,
     players: Arc<Players>,
+    game_over: AtomicBool,
 }
 
 impl PlayersRespawnGameLogicState {
     /// Creates new instance for [PlayersRespawnGameLogicState]
-    pub fn new(ecs: Arc<ECS>, players: Arc<Players>) -> PlayersRespawnGameLogicState {
-        PlayersRespawnGameLogicState { ecs, players }
+    pub fn new(
+        event_sender: events::Sender,
+        ecs: Arc<ECS>,
+        players: Arc<Players>,
+    ) -> PlayersRespawnGameLogicState {
+        PlayersRespawnGameLogicState {
+            event_sender,
+            ecs,
+            players,
+            game_over: Default::default(),
+        }
     }
 }
 
 /// Game logic for respawning players
 pub fn players_respawn_game_logic(elapsed: f32, state: &PlayersRespawnGameLogicState) {
+    state
+        .players
+        .iter_mut()
+        .filter(|(_, player)| !player.is_out())
+        .for_each(|(_, player)| {
+            player.stats.time_survived += elapsed;
+            player.decay_combo(elapsed);
+        });
+
     state
         .players
         .iter_mut()
         .filter(|(_, player)| player.spacecraft_id.is_none())
         .for_each(|(player_id, player)| {
+            if player.spacecraft_destroyed {
+                player.spacecraft_destroyed = false;
+                player.lives = player.lives.saturating_sub(1);
+                player.respawn_timer = entities::consts::PLAYER_RESPAWN_DELAY;
+            }
+
+            if player.lives == 0 {
+                return;
+            }
+
             player.respawn_timer -= elapsed;
 
             if player.respawn_timer > 0.0 {
@@ -279,6 +956,9 @@ pub fn players_respawn_game_logic(elapsed: f32, state: &PlayersRespawnGameLogicS
                     owner: Some(player_id),
                     ..Default::default()
                 },
+                blink: entities::BlinkComponent::new(
+                    entities::consts::SPACECRAFT_SPAWN_INVULNERABILITY,
+                ),
                 ..Default::default()
             };
 
@@ -286,4 +966,313 @@ pub fn players_respawn_game_logic(elapsed: f32, state: &PlayersRespawnGameLogicS
 
             player.spacecraft_id = Some(spacecraft_id);
         });
+
+    let mut players = state.players.iter().peekable();
+    let game_over = players.peek().is_some() && players.all(|(_, player)| player.is_out());
+
+    if game_over && !state.game_over.swap(true, Ordering::Relaxed) {
+        state.event_sender.send(events::Event::GameOver);
+    } else if !game_over {
+        state.game_over.store(false, Ordering::Relaxed);
+    }
+}
+
+/// State for [death_flash_game_logic]
+pub struct DeathFlashGameLogicState {
+    remaining: Arc<Mutex<Option<f32>>>,
+    renderer: Arc<renderer::Renderer>,
+
+    _handler: handle::Handle,
+}
+
+impl DeathFlashGameLogicState {
+    /// Creates new instance of [DeathFlashGameLogicState]
+    pub fn new(
+        events: &events::Events,
+        renderer: Arc<renderer::Renderer>,
+    ) -> DeathFlashGameLogicState {
+        let remaining: Arc<Mutex<Option<f32>>> = Default::default();
+
+        DeathFlashGameLogicState {
+            remaining: remaining.clone(),
+            renderer,
+
+            _handler: events.add_handler_for(
+                std::mem::discriminant(&events::Event::PlayerDied {
+                    player: Default::default(),
+                }),
+                move |_| {
+                    *remaining.lock().unwrap() = Some(entities::consts::DEATH_FLASH_DURATION);
+                },
+            ),
+        }
+    }
+}
+
+/// Game logic for flashing background of default render graph on player death: background is set to flash color
+/// and fades back to background color
+pub fn death_flash_game_logic(elapsed: f32, state: &DeathFlashGameLogicState) {
+    let mut remaining = state.remaining.lock().unwrap();
+
+    let Some(time) = remaining.as_mut() else {
+        return;
+    };
+
+    *time -= elapsed;
+
+    let factor = (*time / entities::consts::DEATH_FLASH_DURATION).max(0.0);
+    let color = glam::Vec4::from(entities::consts::BACKGROUND_COLOR)
+        .lerp(entities::consts::DEATH_FLASH_COLOR.into(), factor);
+
+    state.renderer.set_clear_color("default", color.into());
+
+    if factor == 0.0 {
+        *remaining = None;
+    }
+}
+
+/// Placement of HUD element: corner of the screen and offset from it in pixels
+#[derive(Clone, Copy, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct HudElementLayout {
+    /// Corner of the screen, which element is anchored to
+    pub anchor: scene::TextAnchor,
+    /// Offset in pixels from anchor corner towards the screen center
+    pub offset: Vec2,
+}
+
+/// Layout of HUD elements, by default score is in top-left corner, lives are in top-right corner and session
+/// statistics are below score
+#[derive(Clone, Copy, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct HudLayout {
+    /// Placement of score
+    pub score: HudElementLayout,
+    /// Placement of lives
+    pub lives: HudElementLayout,
+    /// Placement of session statistics, shown when game is over
+    pub stats: HudElementLayout,
+}
+
+impl Default for HudLayout {
+    fn default() -> Self {
+        const MARGIN: Vec2 = Vec2::new(16.0, 16.0);
+
+        Self {
+            score: HudElementLayout {
+                anchor: scene::TextAnchor::TopLeft,
+                offset: MARGIN,
+            },
+            lives: HudElementLayout {
+                anchor: scene::TextAnchor::TopRight,
+                offset: MARGIN,
+            },
+            stats: HudElementLayout {
+                anchor: scene::TextAnchor::TopLeft,
+                offset: Vec2::new(MARGIN.x, 4.0 * MARGIN.y),
+            },
+        }
+    }
+}
+
+impl HudLayout {
+    /// Loads layout from JSON file, default layout is used if file does not exist or is invalid
+    pub fn load<P>(path: P) -> HudLayout
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref();
+
+        match fs::read_to_string(path) {
+            Ok(content) => content.parse().unwrap_or_else(|error| {
+                diagnostics::warning(format_args!(
+                    "{} in {}, default is used",
+                    error,
+                    path.display()
+                ));
+
+                Default::default()
+            }),
+
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Default::default(),
+
+            Err(error) => {
+                diagnostics::warning(format_args!("failed to read {}: {}", path.display(), error));
+
+                Default::default()
+            }
+        }
+    }
+}
+
+impl FromStr for HudLayout {
+    type Err = HudLayoutParseError;
+
+    /// Parses layout from JSON map of element name to its anchor and offset, omitted elements keep default placement
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        serde_json::from_str(s).map_err(HudLayoutParseError)
+    }
+}
+
+/// Error of [HudLayout] parsing
+#[derive(Debug)]
+pub struct HudLayoutParseError(serde_json::Error);
+
+impl fmt::Display for HudLayoutParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid HUD layout: {}", self.0)
+    }
+}
+
+impl std::error::Error for HudLayoutParseError {}
+
+/// State for [hud_game_logic]
+pub struct HudGameLogicState {
+    players: Arc<Players>,
+    controller: Arc<Controller>,
+    scene: Arc<scene::Scene>,
+    layout: HudLayout,
+}
+
+impl HudGameLogicState {
+    /// Creates new instance of [HudGameLogicState]
+    pub fn new(
+        players: Arc<Players>,
+        controller: Arc<Controller>,
+        scene: Arc<scene::Scene>,
+        layout: HudLayout,
+    ) -> HudGameLogicState {
+        HudGameLogicState {
+            players,
+            controller,
+            scene,
+            layout,
+        }
+    }
+}
+
+/// INTERNAL: formats session statistics of player for HUD
+fn format_session_stats(stats: &players::SessionStats) -> String {
+    format!(
+        "GAME OVER\nASTEROIDS {}\nHITS {} OF {}\nACCURACY {:.0} PCT\nSURVIVED {:.0} S",
+        stats.asteroids_destroyed,
+        stats.hits,
+        stats.shots_fired,
+        100.0 * stats.accuracy(),
+        stats.time_survived,
+    )
+}
+
+/// Game logic for HUD: pushes score and lives of controllable player into the scene as texts placed according to
+/// [HudLayout], and session statistics once player is out; texts are removed if there is no controllable player
+pub fn hud_game_logic(_: f32, state: &HudGameLogicState) {
+    const SCORE_TEXT_NAME: &str = "hud_score";
+    const LIVES_TEXT_NAME: &str = "hud_lives";
+    const STATS_TEXT_NAME: &str = "hud_stats";
+    const HUD_SCALE: f32 = 3.0;
+    const HUD_COLOR: Vec3 = Vec3::ONE;
+
+    let player_id = state.controller.player();
+
+    let texts = player_id.and_then(|player_id| {
+        state.players.visit_player(&player_id, |player| {
+            let texts = [
+                (
+                    SCORE_TEXT_NAME,
+                    state.layout.score,
+                    format!("SCORE {}", players::format_score(player.score)),
+                ),
+                (
+                    LIVES_TEXT_NAME,
+                    state.layout.lives,
+                    format!("LIVES {}", player.lives),
+                ),
+            ];
+
+            (texts, player.is_out())
+        })
+    });
+
+    let Some((texts, out)) = texts else {
+        state.scene.remove_text(SCORE_TEXT_NAME);
+        state.scene.remove_text(LIVES_TEXT_NAME);
+        state.scene.remove_text(STATS_TEXT_NAME);
+
+        return;
+    };
+
+    let stats = player_id
+        .filter(|_| out)
+        .and_then(|player_id| state.players.session_stats(&player_id))
+        .map(|stats| {
+            (
+                STATS_TEXT_NAME,
+                state.layout.stats,
+                format_session_stats(&stats),
+            )
+        });
+
+    if stats.is_none() {
+        state.scene.remove_text(STATS_TEXT_NAME);
+    }
+
+    for (name, layout, text) in texts.into_iter().chain(stats) {
+        state.scene.dispatch_text(
+            name,
+            scene::TextSceneEntity {
+                text,
+                anchor: layout.anchor,
+                position: layout.offset,
+                scale: HUD_SCALE,
+                color: HUD_COLOR,
+                alpha: 1.0,
+            },
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hud_layout_is_parsed_with_defaults_for_omitted_elements() {
+        let layout: HudLayout = r#"{ "score": { "anchor": "top_right", "offset": [8, 24] } }"#
+            .parse()
+            .unwrap();
+
+        assert_eq!(layout.score.anchor, scene::TextAnchor::TopRight);
+        assert_eq!(layout.score.offset, Vec2::new(8.0, 24.0));
+        assert_eq!(layout.lives.anchor, HudLayout::default().lives.anchor);
+        assert_eq!(layout.lives.offset, HudLayout::default().lives.offset);
+    }
+
+    #[test]
+    fn session_stats_are_formatted_with_font_glyphs() {
+        let stats = players::SessionStats {
+            asteroids_destroyed: 12,
+            shots_fired: 40,
+            hits: 10,
+            time_survived: 93.4,
+        };
+
+        let text = format_session_stats(&stats);
+
+        assert_eq!(
+            text,
+            "GAME OVER\nASTEROIDS 12\nHITS 10 OF 40\nACCURACY 25 PCT\nSURVIVED 93 S"
+        );
+        assert!(
+            text.chars()
+                .all(|character| character == '\n' || assets::font::glyph_uv(character).is_some())
+        );
+    }
+
+    #[test]
+    fn hud_layout_rejects_unknown_anchor() {
+        let layout =
+            r#"{ "score": { "anchor": "center", "offset": [0, 0] } }"#.parse::<HudLayout>();
+
+        assert!(layout.is_err());
+    }
 }
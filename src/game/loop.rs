@@ -5,7 +5,7 @@ use std::{
     time::{Duration, Instant},
 };
 
-use crate::{handle, workers};
+use crate::{game::clock::Clock, handle, workers};
 
 /// Trait of a game logic, which handles some high-level game logic
 pub trait GameLogic: Send + Sync {
@@ -91,21 +91,30 @@ fn worker_func(game_loop: &Loop, elapsed: f32) {
 }
 
 /// Spawns game loop worker thread
-pub fn spawn_worker(workers: &workers::Workers, r#loop: Arc<Loop>) -> handle::Handle {
+pub fn spawn_worker(
+    workers: &workers::Workers,
+    r#loop: Arc<Loop>,
+    clock: Arc<Clock>,
+    config: workers::WorkerConfig,
+) -> handle::Handle {
     workers.spawn("GameLoop", move |token| {
-        const UPDATE_RATE: f32 = 1.0 / 120.0;
+        let update_rate = config.update_rate();
 
         let mut last_update = Instant::now();
 
         while !token.is_cancelled() {
             let elapsed = Instant::now().duration_since(last_update).as_secs_f32();
 
-            worker_func(&r#loop, elapsed);
+            if !clock.is_paused() {
+                worker_func(&r#loop, elapsed);
+            }
+
+            token.tick();
 
             last_update = Instant::now();
 
-            if elapsed < UPDATE_RATE {
-                let duration = Duration::from_secs_f32(UPDATE_RATE - elapsed);
+            if elapsed < update_rate {
+                let duration = Duration::from_secs_f32(update_rate - elapsed);
 
                 thread::sleep(duration);
             }
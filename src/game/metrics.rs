@@ -0,0 +1,222 @@
+use std::{
+    fs::File,
+    io::{BufWriter, Write},
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    diagnostics,
+    game::{
+        ecs::{ECS, EntitiesRead},
+        physics::Physics,
+    },
+    handle, workers,
+};
+
+/// INTERNAL: names of workers, whose timings are exported, and their CSV columns
+const WORKER_COLUMNS: [(&str, &str); 4] = [
+    ("Renderer", "frame_time_ms"),
+    ("ECS", "ecs_tick_ms"),
+    ("GameLoop", "loop_tick_ms"),
+    ("Physics", "physics_tick_ms"),
+];
+
+/// INTERNAL: writes CSV header
+fn write_header<W>(writer: &mut W) -> std::io::Result<()>
+where
+    W: Write,
+{
+    let columns: Vec<_> = WORKER_COLUMNS.iter().map(|(_, column)| *column).collect();
+
+    writeln!(writer, "time_s,{},entities,collisions", columns.join(","))?;
+    writer.flush()
+}
+
+/// INTERNAL: writes CSV row with average timings of workers in milliseconds, count of entities and count of
+/// collided pairs of entities, timing of missing worker is left empty
+fn write_row<W>(
+    writer: &mut W,
+    time: f32,
+    stats: &workers::WorkersStatsReader,
+    ecs: &ECS,
+    physics: &Physics,
+) -> std::io::Result<()>
+where
+    W: Write,
+{
+    let stats = stats.stats().unwrap_or_default();
+    let timings: Vec<_> = WORKER_COLUMNS
+        .iter()
+        .map(|(name, _)| {
+            stats
+                .get(*name)
+                .map(|stats| format!("{:.3}", 1000.0 * stats.average_elapsed))
+                .unwrap_or_default()
+        })
+        .collect();
+
+    let entities = ecs.read().len();
+    let collisions = physics.current_collisions().len();
+
+    writeln!(
+        writer,
+        "{:.3},{},{},{}",
+        time,
+        timings.join(","),
+        entities,
+        collisions
+    )?;
+    writer.flush()
+}
+
+/// Switch of metrics export, shared between metrics worker and command, which toggles export
+#[derive(Default)]
+pub struct MetricsExport {
+    enabled: AtomicBool,
+}
+
+impl MetricsExport {
+    /// Checks if metrics export is enabled
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::Relaxed)
+    }
+
+    /// Enables or disables metrics export
+    pub fn set_enabled(&self, enabled: bool) {
+        self.enabled.store(enabled, Ordering::Relaxed);
+    }
+
+    /// Toggles metrics export, returns whether it is enabled now
+    pub fn toggle(&self) -> bool {
+        !self.enabled.fetch_xor(true, Ordering::Relaxed)
+    }
+}
+
+/// INTERNAL: creates CSV file at `path` and writes its header
+fn create_writer(path: &str) -> std::io::Result<BufWriter<File>> {
+    let mut writer = BufWriter::new(File::create(path)?);
+
+    write_header(&mut writer)?;
+
+    Ok(writer)
+}
+
+/// Spawns metrics worker thread: while export is enabled, periodically writes frame time, tick times of game
+/// workers, count of entities and count of collided pairs into CSV file at `path` for offline analysis of session; file is recreated each
+/// time export is enabled, export is disabled if file can not be written
+pub fn spawn_worker(
+    workers: &workers::Workers,
+    ecs: Arc<ECS>,
+    physics: Arc<Physics>,
+    export: Arc<MetricsExport>,
+    path: &'static str,
+    interval: Duration,
+) -> handle::Handle {
+    let stats = workers.stats_reader();
+
+    workers.spawn("Metrics", move |token| {
+        let mut writer = None;
+        let mut start = Instant::now();
+
+        while !token.is_cancelled() {
+            thread::sleep(interval);
+
+            if !export.is_enabled() {
+                writer = None;
+                continue;
+            }
+
+            let writer = match writer.as_mut() {
+                Some(writer) => writer,
+
+                None => match create_writer(path) {
+                    Ok(created) => {
+                        start = Instant::now();
+                        writer.insert(created)
+                    }
+
+                    Err(error) => {
+                        diagnostics::warning(format_args!(
+                            "failed to create metrics file {}: {}",
+                            path, error
+                        ));
+                        export.set_enabled(false);
+                        continue;
+                    }
+                },
+            };
+
+            let time = start.elapsed().as_secs_f32();
+
+            if let Err(error) = write_row(writer, time, &stats, &ecs, &physics) {
+                diagnostics::warning(format_args!(
+                    "failed to write metrics file {}: {}",
+                    path, error
+                ));
+                export.set_enabled(false);
+            }
+
+            token.tick();
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::events;
+
+    use super::*;
+
+    #[test]
+    fn toggle_switches_export() {
+        let export: MetricsExport = Default::default();
+
+        assert!(!export.is_enabled());
+        assert!(export.toggle());
+        assert!(export.is_enabled());
+        assert!(!export.toggle());
+        assert!(!export.is_enabled());
+    }
+
+    #[test]
+    fn metrics_are_written_only_while_export_is_enabled() {
+        const INTERVAL: Duration = Duration::from_millis(1);
+        const WAIT: Duration = Duration::from_millis(50);
+
+        let path: &'static str = Box::leak(
+            std::env::temp_dir()
+                .join(format!("asteroids_rs_metrics_{}.csv", std::process::id()))
+                .display()
+                .to_string()
+                .into_boxed_str(),
+        );
+
+        let workers: workers::Workers = Default::default();
+        let events: Arc<events::Events> = Default::default();
+        let ecs = ECS::new(&events);
+        let physics = Physics::new(&events, ecs.clone());
+        let export: Arc<MetricsExport> = Default::default();
+
+        let worker = spawn_worker(&workers, ecs, physics, export.clone(), path, INTERVAL);
+
+        thread::sleep(WAIT);
+        assert!(std::fs::metadata(path).is_err());
+
+        export.set_enabled(true);
+        thread::sleep(WAIT);
+        drop(worker);
+
+        let content = std::fs::read_to_string(path).unwrap();
+        std::fs::remove_file(path).unwrap();
+
+        let mut lines = content.lines();
+
+        assert!(lines.next().unwrap().starts_with("time_s,"));
+        assert!(lines.next().is_some());
+    }
+}
@@ -8,21 +8,25 @@ use crate::{
 
 pub mod ecs;
 pub mod entities;
+pub mod players;
 
+mod clock;
 mod commands;
 mod controller;
 mod logics;
 mod r#loop;
+mod metrics;
 mod physics;
-mod players;
 mod systems;
 
 /// Game infrastructure
 pub struct Game {
-    _systems: [handle::Handle; 9],
-    _logics: [handle::Handle; 3],
-    _commands: [handle::Handle; 8],
-    _workers: [handle::Handle; 3],
+    _systems: [handle::Handle; 19],
+    _logics: [handle::Handle; 8],
+    _commands: [handle::Handle; 20],
+    _aliases: [handle::Handle; 1],
+    _handlers: [handle::Handle; 1],
+    _workers: [handle::Handle; 4],
 }
 
 impl Game {
@@ -35,59 +39,154 @@ impl Game {
         assets: Arc<assets::Assets>,
         renderer: Arc<renderer::Renderer>,
     ) -> Arc<Game> {
+        // NOTE: systems enqueue actions, which are applied in order of invocation: timers are updated before
+        // actions, which reset them; movement goes before wraparound, which wraps moved position; systems, which
+        // destroy entities, go last, so no modifications follow destruction of entity, whose slot may be reused
+        const CONTROL_PRIORITY: ecs::SystemPriority = 0;
+        const TIMERS_PRIORITY: ecs::SystemPriority = 10;
+        const ACTIONS_PRIORITY: ecs::SystemPriority = 20;
+        const MOVEMENT_PRIORITY: ecs::SystemPriority = 30;
+        const WRAPAROUND_PRIORITY: ecs::SystemPriority = 40;
+        const EFFECTS_PRIORITY: ecs::SystemPriority = 50;
+        const DESTRUCTION_PRIORITY: ecs::SystemPriority = 60;
+        const SCENE_PRIORITY: ecs::SystemPriority = 70;
+
         let ecs = ecs::ECS::new(&events);
         let r#loop: Arc<r#loop::Loop> = Default::default();
-        let players = players::Players::new(&events);
+        let players = players::Players::new(
+            &events,
+            players::ScoringTable::load(entities::consts::SCORING_TABLE_PATH),
+        );
         let controller = controller::Controller::new(ecs.clone(), players.clone());
-        let physics = physics::Physics::new(ecs.clone());
+        let physics = physics::Physics::new(&events, ecs.clone());
         let scene = scene::Scene::new(&events);
+        let clock: Arc<clock::Clock> = Default::default();
+        let metrics_export: Arc<metrics::MetricsExport> = Default::default();
+
+        let world_bounds = entities::consts::WORLD_WRAPAROUND.then_some(entities::WorldBounds {
+            half_extent: entities::consts::WORLD_HALF_EXTENT,
+        });
 
         let game = Game {
             _systems: [
-                ecs.add_system(
+                ecs.add_system_ordered(
+                    CONTROL_PRIORITY,
+                    "ai_system",
+                    ecs::StatefulSystem::new(
+                        systems::AiSystemState::new(players.clone()),
+                        systems::ai_system,
+                    ),
+                ),
+                ecs.add_presentation_system_ordered(
+                    CONTROL_PRIORITY,
                     "camera_sync_system",
                     ecs::StatefulSystem::new(
                         systems::CameraSyncSystemState::new(players.clone()),
                         systems::camera_sync_system,
                     ),
                 ),
-                ecs.add_system(
-                    "movement_system",
-                    Into::<ecs::StatelessSystem>::into(systems::movement_system),
+                ecs.add_system_ordered(
+                    TIMERS_PRIORITY,
+                    "blink_system",
+                    Into::<ecs::StatelessSystem>::into(systems::blink_system),
                 ),
-                ecs.add_system(
-                    "spacecraft_weapon_fire_system",
-                    Into::<ecs::StatelessSystem>::into(systems::spacecraft_weapon_fire_system),
+                ecs.add_system_ordered(
+                    TIMERS_PRIORITY,
+                    "spacecraft_shield_system",
+                    Into::<ecs::StatelessSystem>::into(systems::spacecraft_shield_system),
                 ),
-                ecs.add_system(
+                ecs.add_system_ordered(
+                    TIMERS_PRIORITY,
                     "spacecraft_weapon_cooldown_system",
                     Into::<ecs::StatelessSystem>::into(systems::spacecraft_weapon_cooldown_system),
                 ),
-                ecs.add_system(
-                    "spacecraft_rotation_system",
-                    Into::<ecs::StatelessSystem>::into(systems::spacecraft_rotation_system),
+                ecs.add_system_ordered(
+                    ACTIONS_PRIORITY,
+                    "power_up_pickup_system",
+                    Into::<ecs::StatelessSystem>::into(systems::power_up_pickup_system),
+                ),
+                ecs.add_system_ordered(
+                    ACTIONS_PRIORITY,
+                    "spacecraft_weapon_fire_system",
+                    ecs::StatefulSystem::new(
+                        systems::SpacecraftWeaponFireSystemState::new(events.get_sender()),
+                        systems::spacecraft_weapon_fire_system,
+                    ),
                 ),
-                ecs.add_system(
+                ecs.add_system_ordered(
+                    MOVEMENT_PRIORITY,
                     "asteroid_rotation_system",
                     Into::<ecs::StatelessSystem>::into(systems::asteroid_rotation_system),
                 ),
-                ecs.add_system(
-                    "scene_dispatch_system",
+                ecs.add_system_ordered(
+                    MOVEMENT_PRIORITY,
+                    "movement_system",
+                    Into::<ecs::StatelessSystem>::into(systems::movement_system),
+                ),
+                ecs.add_system_ordered(
+                    MOVEMENT_PRIORITY,
+                    "spacecraft_rotation_system",
+                    Into::<ecs::StatelessSystem>::into(systems::spacecraft_rotation_system),
+                ),
+                ecs.add_system_ordered(
+                    WRAPAROUND_PRIORITY,
+                    "wraparound_system",
                     ecs::StatefulSystem::new(
-                        systems::SceneDispatchSystemState::new(scene.clone()),
-                        systems::scene_dispatch_system,
+                        systems::WraparoundSystemState::new(world_bounds),
+                        systems::wraparound_system,
+                    ),
+                ),
+                ecs.add_system_ordered(
+                    EFFECTS_PRIORITY,
+                    "afterimage_fade_system",
+                    Into::<ecs::StatelessSystem>::into(systems::afterimage_fade_system),
+                ),
+                ecs.add_system_ordered(
+                    EFFECTS_PRIORITY,
+                    "camera_shake_system",
+                    Into::<ecs::StatelessSystem>::into(systems::camera_shake_system),
+                ),
+                ecs.add_system_ordered(
+                    EFFECTS_PRIORITY,
+                    "spacecraft_afterimage_system",
+                    Into::<ecs::StatelessSystem>::into(systems::spacecraft_afterimage_system),
+                ),
+                ecs.add_system_ordered(
+                    EFFECTS_PRIORITY,
+                    "thruster_particle_system",
+                    Into::<ecs::StatelessSystem>::into(systems::thruster_particle_system),
+                ),
+                ecs.add_system_ordered(
+                    DESTRUCTION_PRIORITY,
+                    "entity_collision_system",
+                    ecs::StatefulSystem::new(
+                        systems::EntityCollisionSystemState::new(events.get_sender()),
+                        systems::entity_collision_system,
                     ),
                 ),
-                ecs.add_system(
+                ecs.add_system_ordered(
+                    DESTRUCTION_PRIORITY,
                     "entity_despawn_system",
                     ecs::StatefulSystem::new(
-                        systems::EntityDespawnSystemState::new(players.clone()),
+                        systems::EntityDespawnSystemState::new(
+                            players.clone(),
+                            world_bounds.is_some(),
+                        ),
                         systems::entity_despawn_system,
                     ),
                 ),
-                ecs.add_system(
-                    "entity_collision_system",
-                    Into::<ecs::StatelessSystem>::into(systems::entity_collision_system),
+                ecs.add_system_ordered(
+                    DESTRUCTION_PRIORITY,
+                    "lifetime_system",
+                    Into::<ecs::StatelessSystem>::into(systems::lifetime_system),
+                ),
+                ecs.add_presentation_system_ordered(
+                    SCENE_PRIORITY,
+                    "scene_dispatch_system",
+                    ecs::StatefulSystem::new(
+                        systems::SceneDispatchSystemState::new(scene.clone()),
+                        systems::scene_dispatch_system,
+                    ),
                 ),
             ],
 
@@ -111,21 +210,61 @@ impl Game {
                 r#loop.add_logic(
                     "asteroids_respawn_game_logic",
                     r#loop::StatefulGameLogic::new(
-                        logics::AsteroidsRespawnGameLogicState::new(
-                            assets.clone(),
-                            ecs.clone(),
-                            players.clone(),
-                        ),
+                        logics::AsteroidsRespawnGameLogicState::new(ecs.clone(), players.clone()),
                         logics::asteroids_respawn_game_logic,
                     ),
                 ),
                 r#loop.add_logic(
                     "players_respawn_game_logic",
                     r#loop::StatefulGameLogic::new(
-                        logics::PlayersRespawnGameLogicState::new(ecs.clone(), players.clone()),
+                        logics::PlayersRespawnGameLogicState::new(
+                            events.get_sender(),
+                            ecs.clone(),
+                            players.clone(),
+                        ),
                         logics::players_respawn_game_logic,
                     ),
                 ),
+                r#loop.add_logic(
+                    "explosions_game_logic",
+                    r#loop::StatefulGameLogic::new(
+                        logics::ExplosionsGameLogicState::new(&events, ecs.clone()),
+                        logics::explosions_game_logic,
+                    ),
+                ),
+                r#loop.add_logic(
+                    "death_flash_game_logic",
+                    r#loop::StatefulGameLogic::new(
+                        logics::DeathFlashGameLogicState::new(&events, renderer.clone()),
+                        logics::death_flash_game_logic,
+                    ),
+                ),
+                r#loop.add_logic(
+                    "hud_game_logic",
+                    r#loop::StatefulGameLogic::new(
+                        logics::HudGameLogicState::new(
+                            players.clone(),
+                            controller.clone(),
+                            scene.clone(),
+                            logics::HudLayout::load(entities::consts::HUD_LAYOUT_PATH),
+                        ),
+                        logics::hud_game_logic,
+                    ),
+                ),
+                r#loop.add_logic(
+                    "power_ups_spawn_game_logic",
+                    r#loop::StatefulGameLogic::new(
+                        logics::PowerUpsSpawnGameLogicState::new(ecs.clone(), players.clone()),
+                        logics::power_ups_spawn_game_logic,
+                    ),
+                ),
+                r#loop.add_logic(
+                    "ai_spawn_game_logic",
+                    r#loop::StatefulGameLogic::new(
+                        logics::AiSpawnGameLogicState::new(ecs.clone(), players.clone()),
+                        logics::ai_spawn_game_logic,
+                    ),
+                ),
             ],
 
             _commands: [
@@ -136,6 +275,41 @@ impl Game {
                         commands::camera_follow_command,
                     ),
                 ),
+                commands.add(
+                    "camera_detach",
+                    app_commands::StatefulCommand::new(
+                        controller.clone(),
+                        commands::camera_detach_command,
+                    ),
+                ),
+                commands.add(
+                    "camera_pan_up",
+                    app_commands::StatefulCommand::new(
+                        controller.clone(),
+                        commands::camera_pan_up_command,
+                    ),
+                ),
+                commands.add(
+                    "camera_pan_down",
+                    app_commands::StatefulCommand::new(
+                        controller.clone(),
+                        commands::camera_pan_down_command,
+                    ),
+                ),
+                commands.add(
+                    "camera_pan_left",
+                    app_commands::StatefulCommand::new(
+                        controller.clone(),
+                        commands::camera_pan_left_command,
+                    ),
+                ),
+                commands.add(
+                    "camera_pan_right",
+                    app_commands::StatefulCommand::new(
+                        controller.clone(),
+                        commands::camera_pan_right_command,
+                    ),
+                ),
                 commands.add(
                     "camera_zoom_out",
                     app_commands::StatefulCommand::new(
@@ -143,6 +317,13 @@ impl Game {
                         commands::camera_zoom_out_command,
                     ),
                 ),
+                commands.add(
+                    "camera_zoom",
+                    app_commands::StatefulCommand::new(
+                        controller.clone(),
+                        commands::camera_zoom_command,
+                    ),
+                ),
                 commands.add(
                     "camera_zoom_in",
                     app_commands::StatefulCommand::new(
@@ -185,12 +366,72 @@ impl Game {
                         commands::player_weapon_fire_command,
                     ),
                 ),
+                commands.add(
+                    "player_switch_weapon",
+                    app_commands::StatefulCommand::new(
+                        controller.clone(),
+                        commands::player_switch_weapon_command,
+                    ),
+                ),
+                commands.add(
+                    "pause",
+                    app_commands::StatefulCommand::new(clock.clone(), commands::pause_command),
+                ),
+                commands.add(
+                    "resume",
+                    app_commands::StatefulCommand::new(clock.clone(), commands::resume_command),
+                ),
+                commands.add(
+                    "restart",
+                    app_commands::StatefulCommand::new(
+                        controller.clone(),
+                        commands::restart_command,
+                    ),
+                ),
+                commands.add(
+                    "dump_systems",
+                    app_commands::StatefulCommand::new(ecs.clone(), commands::dump_systems_command),
+                ),
+                commands.add(
+                    "toggle_metrics_export",
+                    app_commands::StatefulCommand::new(
+                        metrics_export.clone(),
+                        commands::toggle_metrics_export_command,
+                    ),
+                ),
             ],
 
+            _aliases: [commands.add_alias("fire", "player_weapon_fire")],
+
+            _handlers: [events.add_handler({
+                let controller = controller.clone();
+
+                move |event| {
+                    if let events::Event::Impact { position, strength } = event {
+                        controller.camera_shake(*position, *strength);
+                    }
+                }
+            })],
+
             _workers: [
-                ecs::spawn_worker(workers, ecs),
-                r#loop::spawn_worker(workers, r#loop),
-                physics::spawn_worker(workers, physics),
+                metrics::spawn_worker(
+                    workers,
+                    ecs.clone(),
+                    physics.clone(),
+                    metrics_export,
+                    entities::consts::METRICS_PATH,
+                    entities::consts::METRICS_INTERVAL,
+                ),
+                ecs::spawn_worker(workers, ecs, clock.clone(), Default::default()),
+                r#loop::spawn_worker(workers, r#loop, clock.clone(), Default::default()),
+                physics::spawn_worker(
+                    workers,
+                    physics,
+                    clock,
+                    crate::workers::WorkerConfig {
+                        target_hz: entities::consts::PHYSICS_TARGET_HZ,
+                    },
+                ),
             ],
         };
 
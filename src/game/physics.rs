@@ -1,6 +1,6 @@
 use std::{
     collections::{BTreeMap, BTreeSet},
-    sync::Arc,
+    sync::{Arc, Mutex, RwLock},
     thread,
     time::{Duration, Instant},
 };
@@ -8,7 +8,8 @@ use std::{
 use glam::Vec2;
 
 use crate::{
-    game::{ecs::ECS, entities::EntityId},
+    events,
+    game::{clock::Clock, ecs::ECS, entities::EntityId},
     handle, workers,
 };
 
@@ -22,10 +23,28 @@ pub struct PointCollider {
 
 impl PointCollider {
     /// INTERNAL: applies transformation to [PointCollider]
-    fn transform(&self, position: Vec2) -> PointCollider {
+    fn transform(&self, position: Vec2, scale: Vec2) -> PointCollider {
         PointCollider {
-            center: position + self.center,
-            radius: self.radius,
+            center: position + self.center * scale,
+            radius: self.radius * scale.max_element(),
+        }
+    }
+}
+
+/// Circle (disc) collider data
+pub struct CircleCollider {
+    /// Center of the circle collider, relative to entity position
+    pub center: Vec2,
+    /// Radius of the circle
+    pub radius: f32,
+}
+
+impl CircleCollider {
+    /// INTERNAL: applies transformation to [CircleCollider], non-uniformly scaled circle is covered by larger radius
+    fn transform(&self, position: Vec2, rotation: f32, scale: Vec2) -> CircleCollider {
+        CircleCollider {
+            center: position + (self.center * scale).rotate(rotation.sin_cos().into()),
+            radius: self.radius * scale.max_element(),
         }
     }
 }
@@ -42,17 +61,133 @@ pub struct TriangleCollider {
 
 impl TriangleCollider {
     /// INTERNAL: applies transformation to [TriangleCollider]
-    fn transform(&self, position: Vec2, rotation: f32) -> TriangleCollider {
+    fn transform(&self, position: Vec2, rotation: f32, scale: Vec2) -> TriangleCollider {
         TriangleCollider {
-            center: position + self.center,
+            center: position + self.center * scale,
             vertices: [
-                position + self.vertices[0].rotate(rotation.sin_cos().into()),
-                position + self.vertices[1].rotate(rotation.sin_cos().into()),
-                position + self.vertices[2].rotate(rotation.sin_cos().into()),
+                position + (self.vertices[0] * scale).rotate(rotation.sin_cos().into()),
+                position + (self.vertices[1] * scale).rotate(rotation.sin_cos().into()),
+                position + (self.vertices[2] * scale).rotate(rotation.sin_cos().into()),
             ],
-            radius: self.radius,
+            radius: self.radius * scale.max_element(),
+        }
+    }
+}
+
+/// Convex polygon collider data
+pub struct PolygonCollider {
+    /// Origin of collider
+    pub center: Vec2,
+    /// Vertices of convex polygon in counter-clockwise order
+    pub vertices: Vec<Vec2>,
+    /// Activation radius
+    pub radius: f32,
+}
+
+impl PolygonCollider {
+    /// Creates collider from convex hull of points, activation radius covers all points
+    pub fn from_points(points: &[Vec2]) -> PolygonCollider {
+        let vertices = convex_hull(points);
+
+        let radius = vertices
+            .iter()
+            .map(|vertex| vertex.length())
+            .max_by(|l, r| l.total_cmp(r))
+            .unwrap_or_default();
+
+        PolygonCollider {
+            center: Vec2::ZERO,
+            vertices,
+            radius,
+        }
+    }
+
+    /// INTERNAL: applies transformation to [PolygonCollider]
+    fn transform(&self, position: Vec2, rotation: f32, scale: Vec2) -> PolygonCollider {
+        let rotation: Vec2 = rotation.sin_cos().into();
+
+        PolygonCollider {
+            center: position + self.center * scale,
+            vertices: self
+                .vertices
+                .iter()
+                .map(|vertex| position + (*vertex * scale).rotate(rotation))
+                .collect(),
+            radius: self.radius * scale.max_element(),
+        }
+    }
+}
+
+/// INTERNAL: builds convex hull of points with monotone chain algorithm, vertices are in counter-clockwise order
+fn convex_hull(points: &[Vec2]) -> Vec<Vec2> {
+    fn cross(origin: Vec2, a: Vec2, b: Vec2) -> f32 {
+        (a - origin).perp_dot(b - origin)
+    }
+
+    let mut points = points.to_vec();
+    points.sort_by(|l, r| l.x.total_cmp(&r.x).then(l.y.total_cmp(&r.y)));
+    points.dedup();
+
+    if points.len() < 3 {
+        return points;
+    }
+
+    let mut hull: Vec<Vec2> = Vec::with_capacity(2 * points.len());
+
+    for pass in [points.as_slice(), &points[..points.len() - 1]] {
+        let start = hull.len();
+
+        let iter: Box<dyn Iterator<Item = &Vec2>> = if start == 0 {
+            Box::new(pass.iter())
+        } else {
+            Box::new(pass.iter().rev())
+        };
+
+        for point in iter {
+            while hull.len() >= start + 2
+                && cross(hull[hull.len() - 2], hull[hull.len() - 1], *point) <= 0.0
+            {
+                hull.pop();
+            }
+
+            hull.push(*point);
         }
+
+        hull.pop();
     }
+
+    hull
+}
+
+/// INTERNAL: tests that two convex shapes overlap using separating axis theorem,
+/// shape with single vertex is a point
+fn separating_axis_test(left: &[Vec2], right: &[Vec2]) -> bool {
+    fn project(vertices: &[Vec2], axis: Vec2) -> (f32, f32) {
+        vertices
+            .iter()
+            .map(|vertex| vertex.dot(axis))
+            .fold((f32::MAX, f32::MIN), |(min, max), value| {
+                (min.min(value), max.max(value))
+            })
+    }
+
+    fn edge_normals(vertices: &[Vec2]) -> impl Iterator<Item = Vec2> + '_ {
+        let count = if vertices.len() > 1 {
+            vertices.len()
+        } else {
+            0
+        };
+
+        (0..count)
+            .map(move |index| (vertices[(index + 1) % vertices.len()] - vertices[index]).perp())
+    }
+
+    edge_normals(left).chain(edge_normals(right)).all(|axis| {
+        let (left_min, left_max) = project(left, axis);
+        let (right_min, right_max) = project(right, axis);
+
+        left_max >= right_min && right_max >= left_min
+    })
 }
 
 /// INTERNAL: checks that point lies inside triangle using barycentric coordinates
@@ -91,6 +226,109 @@ fn triangle_point_collision_test(left: &TriangleCollider, right: &PointCollider)
     barycentric_triangle_point_test(right.center, &left.vertices)
 }
 
+/// INTERNAL: calculates distance from point to segment
+fn point_segment_distance(point: Vec2, start: Vec2, end: Vec2) -> f32 {
+    let segment = end - start;
+    let length_squared = segment.length_squared();
+
+    if length_squared == 0.0 {
+        return point.distance(start);
+    }
+
+    let t = ((point - start).dot(segment) / length_squared).clamp(0.0, 1.0);
+
+    point.distance(start + t * segment)
+}
+
+/// INTERNAL: tests collision of two circles
+fn circle_circle_collision_test(left: &CircleCollider, right: &CircleCollider) -> bool {
+    let distance = left.center.distance(right.center);
+
+    distance <= left.radius + right.radius
+}
+
+/// INTERNAL: tests collision of circle and point
+fn circle_point_collision_test(left: &CircleCollider, right: &PointCollider) -> bool {
+    let distance = left.center.distance(right.center);
+
+    distance <= left.radius + right.radius
+}
+
+/// INTERNAL: tests collision of triangle and circle: circle collides if its center lies inside triangle
+/// or any triangle edge is closer to its center than its radius
+fn triangle_circle_collision_test(left: &TriangleCollider, right: &CircleCollider) -> bool {
+    let distance = left.center.distance(right.center);
+
+    if distance > left.radius + right.radius {
+        return false;
+    }
+
+    if barycentric_triangle_point_test(right.center, &left.vertices) {
+        return true;
+    }
+
+    (0..3).any(|index| {
+        let start = left.vertices[index];
+        let end = left.vertices[(index + 1) % 3];
+
+        point_segment_distance(right.center, start, end) <= right.radius
+    })
+}
+
+/// INTERNAL: tests collision of polygon and point
+fn polygon_point_collision_test(left: &PolygonCollider, right: &PointCollider) -> bool {
+    let distance = left.center.distance(right.center);
+
+    if distance > left.radius + right.radius {
+        return false;
+    }
+
+    separating_axis_test(&left.vertices, &[right.center])
+}
+
+/// INTERNAL: tests collision of polygon and circle: circle collides if its center lies inside polygon
+/// or any polygon edge is closer to its center than its radius
+fn polygon_circle_collision_test(left: &PolygonCollider, right: &CircleCollider) -> bool {
+    let distance = left.center.distance(right.center);
+
+    if distance > left.radius + right.radius {
+        return false;
+    }
+
+    if separating_axis_test(&left.vertices, &[right.center]) {
+        return true;
+    }
+
+    (0..left.vertices.len()).any(|index| {
+        let start = left.vertices[index];
+        let end = left.vertices[(index + 1) % left.vertices.len()];
+
+        point_segment_distance(right.center, start, end) <= right.radius
+    })
+}
+
+/// INTERNAL: tests collision of polygon and triangle
+fn polygon_triangle_collision_test(left: &PolygonCollider, right: &TriangleCollider) -> bool {
+    let distance = left.center.distance(right.center);
+
+    if distance > left.radius + right.radius {
+        return false;
+    }
+
+    separating_axis_test(&left.vertices, &right.vertices)
+}
+
+/// INTERNAL: tests collision of two polygons
+fn polygon_polygon_collision_test(left: &PolygonCollider, right: &PolygonCollider) -> bool {
+    let distance = left.center.distance(right.center);
+
+    if distance > left.radius + right.radius {
+        return false;
+    }
+
+    separating_axis_test(&left.vertices, &right.vertices)
+}
+
 /// INTERNAL: tests collision of two triangle
 fn triangle_triangle_collision_test(left: &TriangleCollider, right: &TriangleCollider) -> bool {
     fn test(left: &TriangleCollider, right: &TriangleCollider) -> bool {
@@ -113,34 +351,72 @@ fn triangle_triangle_collision_test(left: &TriangleCollider, right: &TriangleCol
 ///
 /// See next structures for specific details:
 /// * [PointCollider]
+/// * [CircleCollider]
 /// * [TriangleCollider]
+/// * [PolygonCollider]
 pub enum Collider {
     /// Variant with [PointCollider] data
     Point(PointCollider),
+    /// Variant with [CircleCollider] data
+    Circle(CircleCollider),
     /// Variant with [TriangleCollider] data
     Triangle(TriangleCollider),
+    /// Variant with [PolygonCollider] data
+    Polygon(PolygonCollider),
 }
 
 impl Collider {
-    /// INTERNAL: applies transformation to [Collider]
-    fn transform(&self, position: Vec2, rotation: f32) -> Collider {
+    /// INTERNAL: applies transformation to [Collider]: collider is scaled, then rotated and moved, same as model
+    fn transform(&self, position: Vec2, rotation: f32, scale: Vec2) -> Collider {
         match self {
-            Collider::Point(collider) => collider.transform(position).into(),
-            Collider::Triangle(collider) => collider.transform(position, rotation).into(),
+            Collider::Point(collider) => collider.transform(position, scale).into(),
+            Collider::Circle(collider) => collider.transform(position, rotation, scale).into(),
+            Collider::Triangle(collider) => collider.transform(position, rotation, scale).into(),
+            Collider::Polygon(collider) => collider.transform(position, rotation, scale).into(),
         }
     }
 
+    /// INTERNAL: returns axis-aligned bounding box of collider activation area as min and max corners
+    fn bounds(&self) -> (Vec2, Vec2) {
+        let (center, radius) = match self {
+            Collider::Point(collider) => (collider.center, collider.radius),
+            Collider::Circle(collider) => (collider.center, collider.radius),
+            Collider::Triangle(collider) => (collider.center, collider.radius),
+            Collider::Polygon(collider) => (collider.center, collider.radius),
+        };
+
+        (center - radius, center + radius)
+    }
+
     /// INTERNAL: performs collision test of two colliders
     fn collision_test(&self, collider: &Collider) -> bool {
         match self {
             Collider::Point(left) => match collider {
                 Collider::Point(right) => point_point_collision_test(left, right),
+                Collider::Circle(right) => circle_point_collision_test(right, left),
                 Collider::Triangle(right) => triangle_point_collision_test(right, left),
+                Collider::Polygon(right) => polygon_point_collision_test(right, left),
+            },
+
+            Collider::Circle(left) => match collider {
+                Collider::Point(right) => circle_point_collision_test(left, right),
+                Collider::Circle(right) => circle_circle_collision_test(left, right),
+                Collider::Triangle(right) => triangle_circle_collision_test(right, left),
+                Collider::Polygon(right) => polygon_circle_collision_test(right, left),
             },
 
             Collider::Triangle(left) => match collider {
                 Collider::Point(right) => triangle_point_collision_test(left, right),
+                Collider::Circle(right) => triangle_circle_collision_test(left, right),
                 Collider::Triangle(right) => triangle_triangle_collision_test(left, right),
+                Collider::Polygon(right) => polygon_triangle_collision_test(right, left),
+            },
+
+            Collider::Polygon(left) => match collider {
+                Collider::Point(right) => polygon_point_collision_test(left, right),
+                Collider::Circle(right) => polygon_circle_collision_test(left, right),
+                Collider::Triangle(right) => polygon_triangle_collision_test(left, right),
+                Collider::Polygon(right) => polygon_polygon_collision_test(left, right),
             },
         }
     }
@@ -152,6 +428,18 @@ impl From<PointCollider> for Collider {
     }
 }
 
+impl From<CircleCollider> for Collider {
+    fn from(value: CircleCollider) -> Self {
+        Collider::Circle(value)
+    }
+}
+
+impl From<PolygonCollider> for Collider {
+    fn from(value: PolygonCollider) -> Self {
+        Collider::Polygon(value)
+    }
+}
+
 impl From<TriangleCollider> for Collider {
     fn from(value: TriangleCollider) -> Self {
         Collider::Triangle(value)
@@ -162,48 +450,141 @@ impl From<TriangleCollider> for Collider {
 #[derive(PartialEq, Eq, PartialOrd, Ord)]
 pub struct Collision(pub EntityId);
 
+/// INTERNAL: world-space colliders of entity with their bounds, cached until entity transform changes
+/// or its identifier is reused by new entity
+struct CachedColliders {
+    generation: u64,
+    position: Vec2,
+    rotation: f32,
+    scale: Vec2,
+    colliders: Vec<((Vec2, Vec2), Collider)>,
+}
+
 /// Physics infrastructure
 pub struct Physics {
     ecs: Arc<ECS>,
+    event_sender: events::Sender,
+    current: RwLock<Vec<(EntityId, EntityId)>>,
+    colliding: Mutex<BTreeSet<EntityId>>,
+    cache: Mutex<BTreeMap<EntityId, CachedColliders>>,
 }
 
 impl Physics {
     /// Creates new instance of [Physics]
-    pub fn new(ecs: Arc<ECS>) -> Physics {
-        Physics { ecs }
+    pub fn new(events: &events::Events, ecs: Arc<ECS>) -> Arc<Physics> {
+        let physics = Physics {
+            ecs,
+            event_sender: events.get_sender(),
+            current: Default::default(),
+            colliding: Default::default(),
+            cache: Default::default(),
+        };
+
+        Arc::new(physics)
     }
 
-    /// INTERNAL: collects all occurred collisions
-    fn collect_collisions(&self) -> BTreeMap<EntityId, BTreeSet<Collision>> {
+    /// Returns snapshot of collided pairs of entities, detected by last physics update.
+    /// Each pair is listed once, with lower [EntityId] first
+    pub fn current_collisions(&self) -> Vec<(EntityId, EntityId)> {
+        self.current.read().unwrap().clone()
+    }
+
+    /// Performs single synchronous physics step: detects collisions, updates snapshot and defers collisions to ECS
+    pub fn step(&self) {
+        let collisions = self.collect_collisions();
+
+        self.store_current(&collisions);
+        self.store_collisions(collisions);
+    }
+
+    /// INTERNAL: updates cached world-space colliders: entries are recomputed only for new entities and entities
+    /// with changed transform, entries of entities without colliders are removed
+    fn update_cache(&self, cache: &mut BTreeMap<EntityId, CachedColliders>) {
         let entities = self.ecs.read();
+        let mut visited = BTreeSet::new();
+
+        for (entity_id, entity) in entities.iter() {
+            let Some(collider) = entity.collider() else {
+                continue;
+            };
+
+            let transform = entity.transform();
+            let generation = entities.generation(entity_id);
+            visited.insert(entity_id);
+
+            let outdated = cache.get(&entity_id).is_none_or(|cached| {
+                cached.generation != generation
+                    || cached.position != transform.position
+                    || cached.rotation != transform.rotation
+                    || cached.scale != transform.scale
+            });
 
-        let iter_entities = || {
-            entities
-                .iter()
-                .filter_map(|(entity_id, entity)| {
-                    entity
-                        .collider()
-                        .map(|collider| (entity_id, entity.transform(), collider))
-                })
-                .flat_map(|(entity_id, transform, collider)| {
-                    collider.colliders.iter().map(move |collider| {
-                        (
-                            entity_id,
-                            collider.transform(transform.position, transform.rotation),
-                        )
+            if outdated {
+                let colliders = collider
+                    .colliders
+                    .iter()
+                    .map(|collider| {
+                        let collider = collider.transform(
+                            transform.position,
+                            transform.rotation,
+                            transform.scale,
+                        );
+
+                        (collider.bounds(), collider)
                     })
-                })
-        };
+                    .collect();
+
+                cache.insert(
+                    entity_id,
+                    CachedColliders {
+                        generation,
+                        position: transform.position,
+                        rotation: transform.rotation,
+                        scale: transform.scale,
+                        colliders,
+                    },
+                );
+            }
+        }
+
+        cache.retain(|entity_id, _| visited.contains(entity_id));
+    }
+
+    /// INTERNAL: collects all occurred collisions; broadphase is sweep-and-prune along X axis,
+    /// so only colliders with overlapping bounds spans are tested
+    fn collect_collisions(&self) -> BTreeMap<EntityId, BTreeSet<Collision>> {
+        let mut cache = self.cache.lock().unwrap();
+        self.update_cache(&mut cache);
+
+        let mut colliders: Vec<_> = cache
+            .iter()
+            .flat_map(|(entity_id, cached)| {
+                cached
+                    .colliders
+                    .iter()
+                    .map(move |((min, max), collider)| ((min.x, max.x), *entity_id, collider))
+            })
+            .collect();
+
+        colliders.sort_by(|((left, _), _, _), ((right, _), _, _)| left.total_cmp(right));
 
-        let collisions = iter_entities()
-            .flat_map(|(left_entity_id, left_collider)| {
-                iter_entities()
-                    .filter(move |(right_entity_id, _)| left_entity_id > *right_entity_id)
-                    .filter(move |(_, right_collider)| left_collider.collision_test(right_collider))
-                    .flat_map(move |(right_entity_id, _)| {
+        let colliders = &colliders;
+
+        let collisions = colliders
+            .iter()
+            .enumerate()
+            .flat_map(|(index, ((_, left_max), left_entity_id, left_collider))| {
+                colliders[index + 1..]
+                    .iter()
+                    .take_while(move |((right_min, _), _, _)| right_min <= left_max)
+                    .filter(move |(_, right_entity_id, _)| left_entity_id != right_entity_id)
+                    .filter(move |(_, _, right_collider)| {
+                        left_collider.collision_test(right_collider)
+                    })
+                    .flat_map(move |(_, right_entity_id, _)| {
                         [
-                            (left_entity_id, Collision(right_entity_id)),
-                            (right_entity_id, Collision(left_entity_id)),
+                            (*left_entity_id, Collision(*right_entity_id)),
+                            (*right_entity_id, Collision(*left_entity_id)),
                         ]
                     })
             })
@@ -219,15 +600,55 @@ impl Physics {
         collisions
     }
 
-    /// INTERNAL: stores all collisions in [crate::game::entities::ColliderComponent]
-    fn store_collisions(&self, collisions: BTreeMap<EntityId, BTreeSet<Collision>>) {
-        let mut entities = self.ecs.write();
+    /// INTERNAL: stores snapshot of collided pairs, see [Physics::current_collisions];
+    /// emits [events::Event::CollisionStarted] and [events::Event::CollisionFinished] for pairs that differ from previous snapshot
+    fn store_current(&self, collisions: &BTreeMap<EntityId, BTreeSet<Collision>>) {
+        let current: Vec<_> = collisions
+            .iter()
+            .flat_map(|(entity_id, collisions)| {
+                collisions
+                    .iter()
+                    .filter(move |Collision(other_id)| entity_id < other_id)
+                    .map(move |Collision(other_id)| (*entity_id, *other_id))
+            })
+            .collect();
+
+        let previous = std::mem::replace(&mut *self.current.write().unwrap(), current.clone());
+
+        // NOTE: both snapshots are sorted, since they are collected from ordered maps and sets
+        let started = current
+            .iter()
+            .filter(|pair| previous.binary_search(pair).is_err())
+            .map(|(left, right)| events::Event::CollisionStarted(BTreeSet::from([*left, *right])));
+
+        let finished = previous
+            .iter()
+            .filter(|pair| current.binary_search(pair).is_err())
+            .map(|(left, right)| events::Event::CollisionFinished(BTreeSet::from([*left, *right])));
+
+        for event in started.chain(finished) {
+            self.event_sender.send(event);
+        }
+    }
+
+    /// INTERNAL: stores collisions in [crate::game::entities::ColliderComponent], replacing collisions of previous step,
+    /// so component holds current contacts only; collisions of entities, which no longer collide, are cleared.
+    /// Collisions are deferred to ECS update, so physics does not contend with ECS for write lock
+    fn store_collisions(&self, mut collisions: BTreeMap<EntityId, BTreeSet<Collision>>) {
+        let mut colliding = self.colliding.lock().unwrap();
+        let current: BTreeSet<_> = collisions.keys().copied().collect();
+
+        for entity_id in colliding.difference(&current) {
+            collisions.insert(*entity_id, Default::default());
+        }
+
+        *colliding = current;
 
         for (entity_id, collisions) in collisions {
-            entities.modify(entity_id, |entity| {
-                entity
-                    .collider_mut()
-                    .map(|collider| collider.collisions.extend(collisions))
+            self.ecs.defer_modify(entity_id, move |entity| {
+                if let Some(collider) = entity.collider_mut() {
+                    collider.collisions = collisions;
+                }
             });
         }
     }
@@ -235,30 +656,130 @@ impl Physics {
 
 /// INTERNAL: Physics worker thread function
 fn worker_func(physics: &Physics) {
-    let collisions = physics.collect_collisions();
-
-    physics.store_collisions(collisions);
+    physics.step();
 }
 
 /// Spawns physics worker thread
-pub fn spawn_worker(workers: &workers::Workers, physics: Physics) -> handle::Handle {
+pub fn spawn_worker(
+    workers: &workers::Workers,
+    physics: Arc<Physics>,
+    clock: Arc<Clock>,
+    config: workers::WorkerConfig,
+) -> handle::Handle {
     workers.spawn("Physics", move |token| {
-        const UPDATE_RATE: f32 = 1.0 / 120.0;
+        let update_rate = config.update_rate();
 
         let mut last_update = Instant::now();
 
         while !token.is_cancelled() {
             let elapsed = Instant::now().duration_since(last_update).as_secs_f32();
 
-            worker_func(&physics);
+            if !clock.is_paused() {
+                worker_func(&physics);
+            }
+
+            token.tick();
 
             last_update = Instant::now();
 
-            if elapsed < UPDATE_RATE {
-                let duration = Duration::from_secs_f32(UPDATE_RATE - elapsed);
+            if elapsed < update_rate {
+                let duration = Duration::from_secs_f32(update_rate - elapsed);
 
                 thread::sleep(duration);
             }
         }
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::entities::{Asteroid, Spacecraft};
+
+    /// Creates [Physics] over empty [ECS]
+    fn create_physics() -> (Arc<ECS>, Arc<Physics>) {
+        let events: events::Events = Default::default();
+        let ecs = ECS::new(&events);
+        let physics = Physics::new(&events, ecs.clone());
+
+        (ecs, physics)
+    }
+
+    /// Returns cached bounds of entity colliders
+    fn cached_bounds(physics: &Physics, entity_id: EntityId) -> Vec<(Vec2, Vec2)> {
+        physics.cache.lock().unwrap()[&entity_id]
+            .colliders
+            .iter()
+            .map(|(bounds, _)| *bounds)
+            .collect()
+    }
+
+    #[test]
+    fn cache_is_invalidated_when_entity_id_is_reused() {
+        let (ecs, physics) = create_physics();
+
+        let asteroid_id = ecs.write().create(Asteroid {
+            transform: Default::default(),
+            ..Asteroid::with_size(2.0)
+        });
+
+        physics.step();
+
+        ecs.clear();
+        let spacecraft_id = ecs.write().create(Spacecraft::default());
+        assert_eq!(spacecraft_id, asteroid_id);
+
+        physics.step();
+
+        let (expected_ecs, expected_physics) = create_physics();
+        let expected_id = expected_ecs.write().create(Spacecraft::default());
+        expected_physics.step();
+
+        assert_eq!(
+            cached_bounds(&physics, spacecraft_id),
+            cached_bounds(&expected_physics, expected_id)
+        );
+    }
+
+    #[test]
+    fn cache_is_invalidated_when_scale_changes() {
+        let (ecs, physics) = create_physics();
+
+        let entity_id = ecs.write().create(Spacecraft::default());
+        physics.step();
+
+        let [(unscaled_min, unscaled_max)] = cached_bounds(&physics, entity_id)[..] else {
+            panic!("spacecraft has single collider");
+        };
+
+        ecs.write().modify(entity_id, |entity| {
+            entity.transform_mut().scale = Vec2::splat(2.0);
+        });
+        physics.step();
+
+        let [(min, max)] = cached_bounds(&physics, entity_id)[..] else {
+            panic!("spacecraft has single collider");
+        };
+
+        assert!(min.abs_diff_eq(2.0 * unscaled_min, 1e-5));
+        assert!(max.abs_diff_eq(2.0 * unscaled_max, 1e-5));
+    }
+
+    #[test]
+    fn current_collisions_list_collided_pairs_of_last_step() {
+        let (ecs, physics) = create_physics();
+
+        let asteroid_id = ecs.write().create(Asteroid::with_size(1.0));
+        let spacecraft_id = ecs.write().create(Spacecraft::default());
+
+        physics.step();
+        assert_eq!(physics.current_collisions(), [(asteroid_id, spacecraft_id)]);
+
+        ecs.write().modify(spacecraft_id, |entity| {
+            entity.transform_mut().position = Vec2::new(100.0, 0.0);
+        });
+
+        physics.step();
+        assert!(physics.current_collisions().is_empty());
+    }
+}
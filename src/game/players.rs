@@ -1,25 +1,252 @@
 use std::{
     collections::BTreeMap,
+    fmt, fs, io,
+    path::Path,
     ptr::NonNull,
+    str::FromStr,
     sync::{
         Arc, RwLock, RwLockReadGuard, RwLockWriteGuard,
         atomic::{AtomicUsize, Ordering},
     },
 };
 
-use crate::{events, game::entities::EntityId, handle};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    diagnostics, events,
+    game::entities::{EntityId, consts},
+    handle,
+};
+
+/// Statistics of player game session
+#[derive(Clone, Copy, Default)]
+pub struct SessionStats {
+    /// Count of asteroids destroyed by player
+    pub asteroids_destroyed: u32,
+
+    /// Count of bullets fired by player
+    pub shots_fired: u32,
+
+    /// Count of bullets of player which hit asteroid or spacecraft
+    pub hits: u32,
+
+    /// Time in seconds player stayed in game
+    pub time_survived: f32,
+}
+
+impl SessionStats {
+    /// Returns ratio of hits to fired shots, zero if there were no shots
+    pub fn accuracy(&self) -> f32 {
+        if self.shots_fired == 0 {
+            return 0.0;
+        }
+
+        (self.hits as f32 / self.shots_fired as f32).min(1.0)
+    }
+}
+
+/// Formats score with thousands separators, e.g. `1234567` as `1,234,567`
+pub fn format_score(score: u32) -> String {
+    const SEPARATOR: char = ',';
+
+    let digits = score.to_string();
+    let mut formatted = String::with_capacity(digits.len() + digits.len() / 3);
+
+    for (index, digit) in digits.chars().enumerate() {
+        if index > 0 && (digits.len() - index).is_multiple_of(3) {
+            formatted.push(SEPARATOR);
+        }
+
+        formatted.push(digit);
+    }
+
+    formatted
+}
+
+/// Single entry of [ScoringTable]
+#[derive(Clone, Copy, Serialize, Deserialize)]
+pub struct ScoringEntry {
+    /// Maximal size of asteroid covered by entry
+    pub max_size: f32,
+    /// Points awarded for destroyed asteroid
+    pub points: u32,
+}
+
+/// Table of points awarded for destroyed asteroids by their size, entries are sorted by [ScoringEntry::max_size]
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ScoringTable(Vec<ScoringEntry>);
+
+impl ScoringTable {
+    /// Loads table from JSON file, default table is used if file is missing or invalid
+    pub fn load<P>(path: P) -> ScoringTable
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref();
+
+        match fs::read_to_string(path) {
+            Ok(content) => content.parse().unwrap_or_else(|error| {
+                diagnostics::warning(format_args!(
+                    "{} in {}, default is used",
+                    error,
+                    path.display()
+                ));
+
+                Default::default()
+            }),
+
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Default::default(),
+
+            Err(error) => {
+                diagnostics::warning(format_args!("failed to read {}: {}", path.display(), error));
+
+                Default::default()
+            }
+        }
+    }
+
+    /// Returns points for asteroid of given size: entry with smallest sufficient max size is used,
+    /// asteroids larger than any entry use last entry
+    pub fn points(&self, size: f32) -> u32 {
+        self.0
+            .iter()
+            .find(|entry| size <= entry.max_size)
+            .or(self.0.last())
+            .map(|entry| entry.points)
+            .unwrap_or_default()
+    }
+}
+
+impl Default for ScoringTable {
+    fn default() -> Self {
+        ScoringTable(vec![
+            ScoringEntry {
+                max_size: 0.5,
+                points: 5,
+            },
+            ScoringEntry {
+                max_size: 1.0,
+                points: 10,
+            },
+            ScoringEntry {
+                max_size: 2.0,
+                points: 20,
+            },
+            ScoringEntry {
+                max_size: 3.0,
+                points: 30,
+            },
+            ScoringEntry {
+                max_size: 4.0,
+                points: 40,
+            },
+        ])
+    }
+}
+
+impl FromStr for ScoringTable {
+    type Err = ScoringTableParseError;
+
+    /// Parses table from JSON list of entries, entries are sorted by max size
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut entries =
+            serde_json::from_str::<Vec<ScoringEntry>>(s).map_err(ScoringTableParseError)?;
+
+        entries.sort_by(|l, r| l.max_size.total_cmp(&r.max_size));
+
+        Ok(ScoringTable(entries))
+    }
+}
+
+/// Error of [ScoringTable] parsing, i.e. malformed source
+#[derive(Debug)]
+pub struct ScoringTableParseError(serde_json::Error);
+
+impl fmt::Display for ScoringTableParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid scoring table: {}", self.0)
+    }
+}
+
+impl std::error::Error for ScoringTableParseError {}
 
 /// A player
-#[derive(Default)]
 pub struct Player {
     /// Identifier of spacecraft entity
     pub spacecraft_id: Option<EntityId>,
 
+    /// Spacecraft of player was destroyed and not yet accounted
+    pub spacecraft_destroyed: bool,
+
     /// Respawn timer
     pub respawn_timer: f32,
 
     /// Player score
     pub score: u32,
+
+    /// Remaining lives of player
+    pub lives: u32,
+
+    /// Score at which player gets next bonus life
+    pub next_bonus_life: u32,
+
+    /// Count of consecutive quick kills
+    pub combo: u32,
+
+    /// Remaining time in seconds to continue combo
+    pub combo_timer: f32,
+
+    /// Statistics of current game session
+    pub stats: SessionStats,
+}
+
+impl Player {
+    /// Resets player to initial state
+    pub fn reset(&mut self) {
+        *self = Player {
+            respawn_timer: consts::PLAYER_RESPAWN_DELAY,
+            ..Default::default()
+        };
+    }
+
+    /// Returns score multiplier of current combo
+    pub fn combo_multiplier(&self) -> f32 {
+        let step = self.combo.saturating_sub(1) as f32 * consts::COMBO_MULTIPLIER_STEP;
+
+        (1.0 + step).min(consts::COMBO_MAX_MULTIPLIER)
+    }
+
+    /// Decays combo, combo is reset when its timer runs out
+    pub fn decay_combo(&mut self, elapsed: f32) {
+        self.combo_timer -= elapsed;
+
+        if self.combo_timer <= 0.0 {
+            self.combo = 0;
+            self.combo_timer = 0.0;
+        }
+    }
+
+    /// Returns true if player is out of lives and has no spacecraft
+    pub fn is_out(&self) -> bool {
+        self.lives == 0 && self.spacecraft_id.is_none()
+    }
+}
+
+impl Default for Player {
+    fn default() -> Self {
+        Player {
+            spacecraft_id: None,
+            spacecraft_destroyed: false,
+            respawn_timer: 0.0,
+            score: 0,
+            lives: consts::PLAYER_INITIAL_LIVES,
+            next_bonus_life: consts::BONUS_LIFE_SCORE_INTERVAL,
+            combo: 0,
+            combo_timer: 0.0,
+            stats: Default::default(),
+        }
+    }
 }
 
 /// Type alias for player identifier
@@ -84,10 +311,11 @@ impl<'a> Iterator for PlayerIterMut<'a> {
 }
 
 /// INTERNAL: inner store of players container
-#[derive(Default)]
 struct Store {
     player_counter: AtomicUsize,
     players: RwLock<BTreeMap<PlayerId, Player>>,
+    scoring: ScoringTable,
+    event_sender: events::Sender,
 }
 
 impl Store {
@@ -105,9 +333,71 @@ impl Store {
         if let Some(player_id) = player_id {
             if let Some(player) = self.players.write().unwrap().get_mut(&player_id) {
                 player.spacecraft_id = None;
+                player.spacecraft_destroyed = true;
+
+                self.event_sender
+                    .send(events::Event::PlayerDied { player: player_id });
             }
         }
     }
+
+    /// INTERNAL: handles [crate::events::Event::AsteroidDestroyed]
+    fn handle_asteroid_destroy(&self, player_id: PlayerId, size: f32) {
+        let points = self.scoring.points(size) as f32;
+
+        if let Some(player) = self.players.write().unwrap().get_mut(&player_id) {
+            player.combo += 1;
+            player.combo_timer = consts::COMBO_WINDOW;
+
+            player.score += (points * player.combo_multiplier()).round() as u32;
+            player.stats.asteroids_destroyed += 1;
+
+            self.event_sender.send(events::Event::ScoreChanged {
+                player: player_id,
+                new_score: player.score,
+            });
+
+            while player.score >= player.next_bonus_life {
+                player.lives += 1;
+                player.next_bonus_life += consts::BONUS_LIFE_SCORE_INTERVAL;
+
+                self.event_sender.send(events::Event::BonusLife {
+                    player: player_id,
+                    lives: player.lives,
+                });
+            }
+        }
+    }
+
+    /// INTERNAL: handles [crate::events::Event::BulletHit]
+    fn handle_bullet_hit(&self, player_id: PlayerId) {
+        if let Some(player) = self.players.write().unwrap().get_mut(&player_id) {
+            player.stats.hits += 1;
+        }
+    }
+
+    /// INTERNAL: handles [crate::events::Event::GameOver]: reports session statistics of players
+    fn handle_game_over(&self) {
+        for (player_id, player) in self.players.read().unwrap().iter() {
+            diagnostics::info(format_args!(
+                "player {} scored {}, destroyed {} asteroids, accuracy {:.0}% ({}/{}), survived {:.0}s",
+                player_id,
+                format_score(player.score),
+                player.stats.asteroids_destroyed,
+                100.0 * player.stats.accuracy(),
+                player.stats.hits,
+                player.stats.shots_fired,
+                player.stats.time_survived,
+            ));
+        }
+    }
+
+    /// INTERNAL: handles [crate::events::Event::WeaponFired]
+    fn handle_weapon_fire(&self, player_id: PlayerId, count: u32) {
+        if let Some(player) = self.players.write().unwrap().get_mut(&player_id) {
+            player.stats.shots_fired += count;
+        }
+    }
 }
 
 /// Players container
@@ -117,9 +407,14 @@ pub struct Players {
 }
 
 impl Players {
-    /// Creates new instance of [Players]
-    pub fn new(events: &events::Events) -> Arc<Players> {
-        let store: Arc<Store> = Default::default();
+    /// Creates new instance of [Players], asteroids are scored with [ScoringTable]
+    pub fn new(events: &events::Events, scoring: ScoringTable) -> Arc<Players> {
+        let store = Arc::new(Store {
+            player_counter: Default::default(),
+            players: Default::default(),
+            scoring,
+            event_sender: events.get_sender(),
+        });
 
         let players = Players {
             store: store.clone(),
@@ -129,6 +424,22 @@ impl Players {
                         store.handle_entity_destroy(*entity_id);
                     }
 
+                    events::Event::AsteroidDestroyed { by, size } => {
+                        store.handle_asteroid_destroy(*by, *size);
+                    }
+
+                    events::Event::WeaponFired { by, count } => {
+                        store.handle_weapon_fire(*by, *count);
+                    }
+
+                    events::Event::BulletHit { by } => {
+                        store.handle_bullet_hit(*by);
+                    }
+
+                    events::Event::GameOver => {
+                        store.handle_game_over();
+                    }
+
                     _ => {}
                 };
             }),
@@ -185,6 +496,11 @@ impl Players {
         players.get(player_id).map(visitor)
     }
 
+    /// Returns statistics of current game session of player by its [PlayerId]
+    pub fn session_stats(&self, player_id: &PlayerId) -> Option<SessionStats> {
+        self.visit_player(player_id, |player| player.stats)
+    }
+
     /// Creates new player and returns its [PlayerId]
     pub fn new_player(&self) -> PlayerId {
         let mut players = self.store.players.write().unwrap();
@@ -195,6 +511,18 @@ impl Players {
         player_id
     }
 
+    /// Resets all players to initial state, see [Player::reset]
+    pub fn reset_players(&self) {
+        for (player_id, player) in self.iter_mut() {
+            player.reset();
+
+            self.store.event_sender.send(events::Event::ScoreChanged {
+                player: player_id,
+                new_score: player.score,
+            });
+        }
+    }
+
     /// Kicks player by its [PlayerId]
     pub fn kick_player(&self, player_id: PlayerId) {
         let mut players = self.store.players.write().unwrap();
@@ -202,3 +530,72 @@ impl Players {
         players.remove(&player_id);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn session_stats_are_accumulated_from_events() {
+        let events: Arc<events::Events> = Default::default();
+        let players = Players::new(&events, Default::default());
+        let sender = events.get_sender();
+
+        let player_id = players.new_player();
+
+        sender.send(events::Event::WeaponFired {
+            by: player_id,
+            count: 3,
+        });
+        sender.send(events::Event::WeaponFired {
+            by: player_id,
+            count: 1,
+        });
+        sender.send(events::Event::BulletHit { by: player_id });
+        sender.send(events::Event::AsteroidDestroyed {
+            by: player_id,
+            size: 1.0,
+        });
+        events.flush();
+
+        let stats = players.session_stats(&player_id).unwrap();
+
+        assert_eq!(stats.shots_fired, 4);
+        assert_eq!(stats.hits, 1);
+        assert_eq!(stats.asteroids_destroyed, 1);
+        assert_eq!(stats.accuracy(), 0.25);
+    }
+
+    #[test]
+    fn session_stats_of_unknown_player_are_none() {
+        let events: Arc<events::Events> = Default::default();
+        let players = Players::new(&events, Default::default());
+
+        assert!(players.session_stats(&42).is_none());
+    }
+
+    #[test]
+    fn invalid_scoring_table_is_reported_and_default_is_used() {
+        let path =
+            std::env::temp_dir().join(format!("asteroids_rs_scoring_{}.json", std::process::id()));
+        fs::write(&path, "{ not a table").unwrap();
+
+        let reported: Arc<AtomicUsize> = Default::default();
+        let _hook = diagnostics::add_hook({
+            let reported = reported.clone();
+            let path = path.display().to_string();
+
+            move |level, message| {
+                if level == diagnostics::Level::Warning && message.contains(&path) {
+                    reported.fetch_add(1, Ordering::Relaxed);
+                }
+            }
+        });
+
+        let table = ScoringTable::load(&path);
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(reported.load(Ordering::Relaxed), 1);
+        assert_eq!(table.points(1.0), ScoringTable::default().points(1.0));
+    }
+}
@@ -1,11 +1,20 @@
-use std::sync::Arc;
+use std::{f32::consts::PI, sync::Arc};
+
+use glam::Vec2;
 
 use crate::{
     consts::VEC2_RIGHT,
+    events,
     game::{
         ecs::SystemArgs,
         entities::{
-            Bullet, BulletComponent, CameraTarget, Entity, MovementComponent, TransformComponent,
+            Afterimage, Asteroid, AsteroidComponent, BlinkComponent, Bullet, BulletComponent,
+            CameraTarget, Entity, MovementComponent, Particle, PowerUpKind, RenderComponent,
+            TransformComponent, WorldBounds,
+            consts::{
+                AFTERIMAGE_ALPHA, AFTERIMAGE_LIFETIME, AFTERIMAGE_PERIOD, ASTEROID_SPLIT_MIN_SIZE,
+                CAMERA_PAN_SPEED, MAX_PARTICLES_COUNT, SHIELD_DURATION, THRUSTER_PARTICLE_PERIOD,
+            },
         },
         physics::Collision,
         players::Players,
@@ -27,12 +36,33 @@ impl CameraSyncSystemState {
 
 /// Synchronizes camera position with target position
 pub fn camera_sync_system(args: SystemArgs, state: &CameraSyncSystemState) {
+    if let Some(camera) = args
+        .entity
+        .camera()
+        .filter(|camera| matches!(camera.target, CameraTarget::Free))
+    {
+        let direction = camera.pan.normalize_or_zero();
+
+        if direction != Vec2::ZERO {
+            let position = args.entity.transform().position
+                + direction * CAMERA_PAN_SPEED * camera.distance * args.elapsed;
+
+            let position = camera
+                .bounds
+                .map_or(position, |bounds| bounds.clamp(position));
+
+            args.modify(move |entity| entity.transform_mut().position = position);
+        }
+
+        return;
+    }
+
     let position = args
         .entity
         .camera()
         .filter(|camera| camera.follow)
         .and_then(|camera| match camera.target {
-            CameraTarget::None => None,
+            CameraTarget::None | CameraTarget::Free => None,
 
             CameraTarget::Entity(entity_id) => args
                 .get_entity(entity_id)
@@ -46,6 +76,28 @@ pub fn camera_sync_system(args: SystemArgs, state: &CameraSyncSystemState) {
                     args.get_entity(entity_id)
                         .map(|entity| entity.transform().position)
                 }),
+        })
+        .map(|target| {
+            let camera = args.entity.camera().unwrap();
+            let current = args.entity.transform().position;
+
+            let snap = camera.follow_smoothing <= 0.0
+                || camera
+                    .follow_snap_distance
+                    .is_some_and(|distance| current.distance(target) > distance);
+
+            let position = if snap {
+                target
+            } else {
+                current.lerp(
+                    target,
+                    1.0 - (-camera.follow_smoothing * args.elapsed).exp(),
+                )
+            };
+
+            camera
+                .bounds
+                .map_or(position, |bounds| bounds.clamp(position))
         });
 
     if let Some(position) = position {
@@ -53,6 +105,36 @@ pub fn camera_sync_system(args: SystemArgs, state: &CameraSyncSystemState) {
     }
 }
 
+/// Applies decaying random offset to shaking cameras
+pub fn camera_shake_system(args: SystemArgs) {
+    const DECAY_RATE: f32 = 4.0;
+    const SHAKE_EPSILON: f32 = 0.01;
+    const MAX_OFFSET: f32 = 0.5;
+
+    let camera = match args
+        .entity
+        .camera()
+        .filter(|camera| camera.shake > 0.0 || camera.shake_offset != Vec2::ZERO)
+    {
+        Some(camera) => camera,
+        None => return,
+    };
+
+    let shake = match camera.shake * (-DECAY_RATE * args.elapsed).exp() {
+        shake if shake < SHAKE_EPSILON => 0.0,
+        shake => shake,
+    };
+
+    let shake_offset = MAX_OFFSET * shake * Vec2::from_angle(rand::random_range(0.0..2.0 * PI));
+
+    args.modify(move |entity| {
+        let camera = entity.camera_mut().unwrap();
+
+        camera.shake = shake;
+        camera.shake_offset = shake_offset;
+    });
+}
+
 /// Controls entities movement
 pub fn movement_system(args: SystemArgs) {
     const BREAKING_ACCELERATION_EPSILON: f32 = 0.01;
@@ -86,12 +168,23 @@ pub fn movement_system(args: SystemArgs) {
     }
 }
 
+/// State for [spacecraft_weapon_fire_system]
+pub struct SpacecraftWeaponFireSystemState {
+    event_sender: events::Sender,
+}
+
+impl SpacecraftWeaponFireSystemState {
+    /// Creates new instance of [SpacecraftWeaponFireSystemState]
+    pub fn new(event_sender: events::Sender) -> SpacecraftWeaponFireSystemState {
+        SpacecraftWeaponFireSystemState { event_sender }
+    }
+}
+
 /// Handles spacecraft weapon fire
-pub fn spacecraft_weapon_fire_system(args: SystemArgs) {
+pub fn spacecraft_weapon_fire_system(args: SystemArgs, state: &SpacecraftWeaponFireSystemState) {
     const BULLET_VELOCITY: f32 = 8.0;
-    const COOLDOWN: f32 = 0.2;
 
-    let bullet = args
+    let bullets = args
         .entity
         .spacecraft()
         .filter(|spacecraft| spacecraft.weapon_fire)
@@ -99,35 +192,131 @@ pub fn spacecraft_weapon_fire_system(args: SystemArgs) {
             if spacecraft.weapon_cooldown > 0.0 {
                 None
             } else {
-                let velocity = BULLET_VELOCITY
-                    * VEC2_RIGHT.rotate(args.entity.transform().rotation.sin_cos().into());
-
-                let bullet = Bullet {
-                    transform: TransformComponent {
-                        position: args.entity.transform().position,
-                        ..Default::default()
-                    },
-                    movement: MovementComponent {
-                        velocity,
-                        const_velocity: true,
-                        ..Default::default()
-                    },
-                    bullet: BulletComponent {
-                        owner: spacecraft.owner.clone(),
-                    },
-                    ..Default::default()
-                };
-
-                Some(bullet)
+                let heading = VEC2_RIGHT.rotate(args.entity.transform().rotation.sin_cos().into());
+
+                let bullets: Vec<_> = spacecraft
+                    .weapon
+                    .spread()
+                    .iter()
+                    .map(|angle| {
+                        let bullet = Bullet::with_scale(spacecraft.weapon.bullet_scale());
+
+                        Bullet {
+                            transform: TransformComponent {
+                                position: args.entity.transform().position,
+                                ..bullet.transform
+                            },
+                            movement: MovementComponent {
+                                velocity: BULLET_VELOCITY
+                                    * heading.rotate(Vec2::from_angle(*angle)),
+                                const_velocity: true,
+                                ..Default::default()
+                            },
+                            bullet: BulletComponent {
+                                owner: spacecraft.owner,
+                            },
+                            ..bullet
+                        }
+                    })
+                    .collect();
+
+                Some((bullets, spacecraft.weapon.cooldown(), spacecraft.owner))
             }
         });
 
-    if let Some(bullet) = bullet {
-        args.modify(|entity| entity.spacecraft_mut().unwrap().weapon_cooldown = COOLDOWN);
-        args.create(move || bullet.into());
+    if let Some((bullets, cooldown, owner)) = bullets {
+        args.modify(move |entity| entity.spacecraft_mut().unwrap().weapon_cooldown = cooldown);
+
+        if let Some(owner) = owner {
+            state.event_sender.send(events::Event::WeaponFired {
+                by: owner,
+                count: bullets.len() as u32,
+            });
+        }
+
+        for bullet in bullets {
+            args.create(move || bullet.into());
+        }
     }
 }
 
+/// State for [ai_system]
+pub struct AiSystemState {
+    players: Arc<Players>,
+}
+
+impl AiSystemState {
+    /// Creates new instance of [AiSystemState]
+    pub fn new(players: Arc<Players>) -> AiSystemState {
+        AiSystemState { players }
+    }
+}
+
+/// Steers AI spacecraft toward the nearest player spacecraft and fires when it is aligned with target
+pub fn ai_system(args: SystemArgs, state: &AiSystemState) {
+    const ROTATION_VELOCITY: f32 = PI;
+    const ACCELERATION: f32 = 1.0;
+    const ALIGN_EPSILON: f32 = 0.02;
+
+    let ai = match args
+        .entity
+        .spacecraft()
+        .and_then(|spacecraft| spacecraft.ai)
+    {
+        Some(ai) => ai,
+        None => return,
+    };
+
+    let position = args.entity.transform().position;
+    let heading = VEC2_RIGHT.rotate(args.entity.transform().rotation.sin_cos().into());
+
+    let target = state
+        .players
+        .iter()
+        .filter_map(|(_, player)| {
+            player.spacecraft_id.and_then(|spacecraft_id| {
+                args.get_entity(spacecraft_id)
+                    .map(|spacecraft| spacecraft.transform().position)
+            })
+        })
+        .min_by(|l, r| position.distance(*l).total_cmp(&position.distance(*r)));
+
+    let (rotation_velocity, acceleration, weapon_fire) = match target {
+        Some(target) => {
+            let distance = position.distance(target);
+            let angle = heading.angle_to(target - position);
+
+            let rotation_velocity = if angle.abs() > ALIGN_EPSILON {
+                ROTATION_VELOCITY * angle.signum()
+            } else {
+                0.0
+            };
+
+            let acceleration = if distance > ai.fire_distance / 2.0 {
+                ACCELERATION * heading
+            } else {
+                Vec2::ZERO
+            };
+
+            let weapon_fire = angle.abs() < ai.fire_angle && distance < ai.fire_distance;
+
+            (rotation_velocity, acceleration, weapon_fire)
+        }
+
+        None => (0.0, Vec2::ZERO, false),
+    };
+
+    args.modify(move |entity| {
+        if let Some(movement) = entity.movement_mut() {
+            movement.acceleration = acceleration;
+        }
+
+        let spacecraft = entity.spacecraft_mut().unwrap();
+        spacecraft.rotation_velocity = rotation_velocity;
+        spacecraft.weapon_fire = weapon_fire;
+    });
+}
+
 /// Updates spacecraft weapon cooldown
 pub fn spacecraft_weapon_cooldown_system(args: SystemArgs) {
     let cooldown = args
@@ -169,15 +358,253 @@ pub fn asteroid_rotation_system(args: SystemArgs) {
     }
 }
 
+/// Destroys entity when its lifetime expires
+pub fn lifetime_system(args: SystemArgs) {
+    let remaining = args
+        .entity
+        .lifetime()
+        .map(|lifetime| lifetime.remaining - args.elapsed);
+
+    match remaining {
+        Some(remaining) if remaining <= 0.0 => {
+            args.destroy();
+        }
+
+        Some(remaining) => {
+            args.modify(move |entity| entity.lifetime_mut().unwrap().remaining = remaining);
+        }
+
+        None => {}
+    }
+}
+
+/// Leaves afterimages behind spacecraft while it is boosting
+pub fn spacecraft_afterimage_system(args: SystemArgs) {
+    const BOOST_EPSILON: f32 = 0.01;
+
+    let (spacecraft, movement, render) = match (
+        args.entity.spacecraft(),
+        args.entity.movement(),
+        args.entity.render(),
+    ) {
+        (Some(spacecraft), Some(movement), Some(render)) => (spacecraft, movement, render),
+        _ => return,
+    };
+
+    let transform = *args.entity.transform();
+    let direction = VEC2_RIGHT.rotate(transform.rotation.sin_cos().into());
+    let boosting = movement.acceleration.dot(direction) > BOOST_EPSILON;
+
+    let cooldown = (spacecraft.afterimage_cooldown - args.elapsed).max(0.0);
+
+    if !boosting || cooldown > 0.0 {
+        if cooldown != spacecraft.afterimage_cooldown {
+            args.modify(move |entity| {
+                entity.spacecraft_mut().unwrap().afterimage_cooldown = cooldown;
+            });
+        }
+
+        return;
+    }
+
+    let afterimage = Afterimage {
+        transform,
+        render: RenderComponent {
+            mesh: render.mesh.clone(),
+            color: render.color,
+            ..Afterimage::default().render
+        },
+        ..Default::default()
+    };
+
+    args.modify(|entity| {
+        entity.spacecraft_mut().unwrap().afterimage_cooldown = AFTERIMAGE_PERIOD;
+    });
+    args.create(move || afterimage.into());
+}
+
+/// Emits short-lived exhaust particles behind accelerating spacecraft
+pub fn thruster_particle_system(args: SystemArgs) {
+    const ACCELERATION_EPSILON: f32 = 0.01;
+    const EXHAUST_VELOCITY: f32 = 3.0;
+    const EXHAUST_SPREAD: f32 = 0.3;
+    const EXHAUST_OFFSET: f32 = 0.4;
+
+    let (spacecraft, movement) = match (args.entity.spacecraft(), args.entity.movement()) {
+        (Some(spacecraft), Some(movement)) => (spacecraft, movement),
+        _ => return,
+    };
+
+    let accelerating = movement.acceleration.length() > ACCELERATION_EPSILON;
+    let cooldown = (spacecraft.thruster_cooldown - args.elapsed).max(0.0);
+
+    if !accelerating || cooldown > 0.0 {
+        if cooldown != spacecraft.thruster_cooldown {
+            args.modify(move |entity| {
+                entity.spacecraft_mut().unwrap().thruster_cooldown = cooldown;
+            });
+        }
+
+        return;
+    }
+
+    args.modify(|entity| {
+        entity.spacecraft_mut().unwrap().thruster_cooldown = THRUSTER_PARTICLE_PERIOD;
+    });
+
+    let particles_count =
+        args.count_entities_where(|_, entity| matches!(entity, Entity::Particle(_)));
+
+    if particles_count >= MAX_PARTICLES_COUNT {
+        return;
+    }
+
+    let direction = -movement.acceleration.normalize();
+    let spread = rand::random_range(-EXHAUST_SPREAD..=EXHAUST_SPREAD);
+
+    let particle = Particle::default();
+    let particle = Particle {
+        transform: TransformComponent {
+            position: args.entity.transform().position + EXHAUST_OFFSET * direction,
+            ..particle.transform
+        },
+        movement: MovementComponent {
+            velocity: movement.velocity
+                + EXHAUST_VELOCITY * direction.rotate(Vec2::from_angle(spread)),
+            ..particle.movement
+        },
+        ..particle
+    };
+
+    args.create(move || particle.into());
+}
+
+/// Applies power-ups, which spacecraft collided with
+pub fn power_up_pickup_system(args: SystemArgs) {
+    if args.entity.spacecraft().is_none() {
+        return;
+    }
+
+    let kinds: Vec<_> = args
+        .entity
+        .collider()
+        .iter()
+        .flat_map(|collider| collider.collisions.iter())
+        .filter_map(|Collision(entity_id)| match args.get_entity(*entity_id) {
+            Some(Entity::PowerUp(power_up)) => Some(power_up.power_up.kind),
+            _ => None,
+        })
+        .collect();
+
+    for kind in kinds {
+        match kind {
+            PowerUpKind::Shield => {
+                args.modify(|entity| entity.spacecraft_mut().unwrap().shield = SHIELD_DURATION);
+            }
+
+            PowerUpKind::Weapon(weapon) => {
+                args.modify(move |entity| entity.spacecraft_mut().unwrap().weapon = weapon);
+            }
+        }
+    }
+}
+
+/// Updates remaining time of spacecraft shield
+pub fn spacecraft_shield_system(args: SystemArgs) {
+    let shield = args
+        .entity
+        .spacecraft()
+        .filter(|spacecraft| spacecraft.is_shielded())
+        .map(|spacecraft| (spacecraft.shield - args.elapsed).max(0.0));
+
+    if let Some(shield) = shield {
+        args.modify(move |entity| entity.spacecraft_mut().unwrap().shield = shield);
+    }
+}
+
+/// Fades out afterimages by their remaining lifetime
+pub fn afterimage_fade_system(args: SystemArgs) {
+    let alpha = match args.entity {
+        Entity::Afterimage(afterimage) => {
+            AFTERIMAGE_ALPHA * afterimage.lifetime.remaining / AFTERIMAGE_LIFETIME
+        }
+        _ => return,
+    };
+
+    args.modify(move |entity| entity.render_mut().unwrap().alpha = alpha.max(0.0));
+}
+
+/// Toggles visibility of blinking entities
+pub fn blink_system(args: SystemArgs) {
+    let blink = match args.entity.blink().filter(|blink| blink.is_active()) {
+        Some(blink) => blink,
+        None => return,
+    };
+
+    let blink = BlinkComponent {
+        remaining: (blink.remaining - args.elapsed).max(0.0),
+        ..*blink
+    };
+
+    args.modify(move |entity| {
+        if let Some(render) = entity.render_mut() {
+            render.visible = blink.is_visible();
+        }
+
+        if let Some(entity_blink) = entity.blink_mut() {
+            *entity_blink = blink;
+        }
+    });
+}
+
+/// State for [wraparound_system]
+pub struct WraparoundSystemState {
+    world_bounds: Option<WorldBounds>,
+}
+
+impl WraparoundSystemState {
+    /// Creates new instance of [WraparoundSystemState]: without world bounds entities never wrap
+    pub fn new(world_bounds: Option<WorldBounds>) -> WraparoundSystemState {
+        WraparoundSystemState { world_bounds }
+    }
+}
+
+/// Wraps entity around play field edges when it leaves world bounds
+pub fn wraparound_system(args: SystemArgs, state: &WraparoundSystemState) {
+    let world_bounds = match state.world_bounds {
+        Some(world_bounds) => world_bounds,
+        None => return,
+    };
+
+    let should_wrap = matches!(
+        args.entity,
+        Entity::Asteroid(_) | Entity::Bullet(_) | Entity::Spacecraft(_)
+    );
+
+    if should_wrap && !world_bounds.contains(args.entity.transform().position) {
+        // NOTE: position is wrapped when modification is applied, so movement of current tick is preserved,
+        // as wraparound system has greater priority than movement system
+        args.modify(move |entity| {
+            let transform = entity.transform_mut();
+
+            transform.position = world_bounds.wrap(transform.position);
+        });
+    }
+}
+
 /// State for [entity_despawn_system]
 pub struct EntityDespawnSystemState {
     players: Arc<Players>,
+    wraparound: bool,
 }
 
 impl EntityDespawnSystemState {
-    /// Creates new instance of [EntityDespawnSystemState]
-    pub fn new(players: Arc<Players>) -> EntityDespawnSystemState {
-        EntityDespawnSystemState { players }
+    /// Creates new instance of [EntityDespawnSystemState]: with wraparound entities are never despawned
+    pub fn new(players: Arc<Players>, wraparound: bool) -> EntityDespawnSystemState {
+        EntityDespawnSystemState {
+            players,
+            wraparound,
+        }
     }
 }
 
@@ -185,6 +612,10 @@ impl EntityDespawnSystemState {
 pub fn entity_despawn_system(args: SystemArgs, state: &EntityDespawnSystemState) {
     const MAX_DISTANCE: f32 = 150.0;
 
+    if state.wraparound {
+        return;
+    }
+
     let should_despawn = match args.entity {
         Entity::Camera(_) | Entity::Spacecraft(_) => false,
         _ => true,
@@ -214,10 +645,63 @@ pub fn entity_despawn_system(args: SystemArgs, state: &EntityDespawnSystemState)
     }
 }
 
+/// State for [entity_collision_system]
+pub struct EntityCollisionSystemState {
+    event_sender: events::Sender,
+}
+
+impl EntityCollisionSystemState {
+    /// Creates new instance of [EntityCollisionSystemState]
+    pub fn new(event_sender: events::Sender) -> EntityCollisionSystemState {
+        EntityCollisionSystemState { event_sender }
+    }
+}
+
+/// INTERNAL: enqueues creation of two smaller asteroids in place of destroyed one
+fn split_asteroid(args: &SystemArgs, asteroid: &AsteroidComponent) {
+    const VELOCITY_SPREAD: f32 = PI / 6.0;
+    const VELOCITY_MULTIPLIER: f32 = 1.5;
+    const SEPARATION_MULTIPLIER: f32 = 1.1;
+
+    if asteroid.size <= ASTEROID_SPLIT_MIN_SIZE {
+        return;
+    }
+
+    let size = asteroid.size / 2.0;
+    let position = args.entity.transform().position;
+    let velocity = args
+        .entity
+        .movement()
+        .map(|movement| movement.velocity)
+        .unwrap_or_default();
+    let direction = velocity.try_normalize().unwrap_or(VEC2_RIGHT);
+
+    for sign in [1.0, -1.0] {
+        let velocity =
+            VELOCITY_MULTIPLIER * velocity.rotate(Vec2::from_angle(sign * VELOCITY_SPREAD));
+        let offset = sign * SEPARATION_MULTIPLIER * direction.perp();
+
+        args.create(move || {
+            let mut asteroid = Asteroid::with_size(size);
+            asteroid.transform.position = position + offset * asteroid.asteroid.radius();
+            asteroid.movement.velocity = velocity;
+
+            asteroid.into()
+        });
+    }
+}
+
 /// Handles collisions of entities
-pub fn entity_collision_system(args: SystemArgs) {
+pub fn entity_collision_system(args: SystemArgs, state: &EntityCollisionSystemState) {
+    const SPACECRAFT_IMPACT_STRENGTH: f32 = 1.0;
+    const ASTEROID_IMPACT_STRENGTH_PER_SIZE: f32 = 0.1;
+    const SPACECRAFT_EXPLOSION_SIZE: f32 = 0.5;
+
     let should_destroy = match args.entity {
-        Entity::Camera(_) | Entity::Spacecraft(_) => false,
+        Entity::Camera(_) => false,
+        Entity::Spacecraft(spacecraft) => {
+            !spacecraft.blink.is_active() && !spacecraft.spacecraft.is_shielded()
+        }
         _ => true,
     };
 
@@ -232,16 +716,97 @@ pub fn entity_collision_system(args: SystemArgs) {
         .flat_map(|collider| {
             collider.collisions.iter().map(|Collision(entity_id)| {
                 args.get_entity(*entity_id)
-                    .is_some_and(|entity| match entity {
-                        Entity::Camera(_) | Entity::Spacecraft(_) => false,
+                    .is_some_and(|entity| match (args.entity, entity) {
+                        (_, Entity::Camera(_)) => false,
+                        (Entity::PowerUp(_), Entity::Spacecraft(_)) => true,
+                        (Entity::PowerUp(_), _) | (_, Entity::PowerUp(_)) => false,
+                        (Entity::Spacecraft(_), Entity::Asteroid(_)) => true,
+                        (Entity::Spacecraft(spacecraft), Entity::Bullet(bullet))
+                        | (Entity::Bullet(bullet), Entity::Spacecraft(spacecraft)) => {
+                            bullet.bullet.is_hostile_to(&spacecraft.spacecraft)
+                        }
+                        (Entity::Spacecraft(_), _) | (_, Entity::Spacecraft(_)) => false,
                         _ => true,
                     })
             })
         })
         .any(|collided| collided);
 
-    if any_collided {
-        args.destroy();
+    if !any_collided {
+        return;
+    }
+
+    args.destroy();
+
+    let position = args.entity.transform().position;
+
+    match args.entity {
+        Entity::Spacecraft(spacecraft) => {
+            state.event_sender.send(events::Event::Impact {
+                position,
+                strength: SPACECRAFT_IMPACT_STRENGTH,
+            });
+
+            state.event_sender.send(events::Event::EntityExploded {
+                position,
+                size: SPACECRAFT_EXPLOSION_SIZE,
+                color: spacecraft.render.color,
+            });
+        }
+
+        Entity::Asteroid(asteroid) => {
+            state.event_sender.send(events::Event::Impact {
+                position,
+                strength: ASTEROID_IMPACT_STRENGTH_PER_SIZE * asteroid.asteroid.size,
+            });
+
+            state.event_sender.send(events::Event::EntityExploded {
+                position,
+                size: asteroid.asteroid.size,
+                color: asteroid.render.color,
+            });
+        }
+
+        Entity::Bullet(bullet) => {
+            let hit = args
+                .entity
+                .collider()
+                .iter()
+                .flat_map(|collider| collider.collisions.iter())
+                .filter_map(|Collision(entity_id)| args.get_entity(*entity_id))
+                .any(|entity| match entity {
+                    Entity::Asteroid(_) => true,
+                    Entity::Spacecraft(spacecraft) => {
+                        bullet.bullet.is_hostile_to(&spacecraft.spacecraft)
+                    }
+                    _ => false,
+                });
+
+            if let (true, Some(by)) = (hit, bullet.bullet.owner) {
+                state.event_sender.send(events::Event::BulletHit { by });
+            }
+        }
+
+        _ => {}
+    }
+
+    let bullet = args
+        .entity
+        .collider()
+        .iter()
+        .flat_map(|collider| collider.collisions.iter())
+        .filter_map(|Collision(entity_id)| args.get_entity(*entity_id))
+        .find_map(|entity| entity.bullet());
+
+    if let (Some(asteroid), Some(bullet)) = (args.entity.asteroid(), bullet) {
+        split_asteroid(&args, asteroid);
+
+        if let Some(by) = bullet.owner {
+            state.event_sender.send(events::Event::AsteroidDestroyed {
+                by,
+                size: asteroid.size,
+            });
+        }
     }
 }
 
@@ -259,6 +824,12 @@ impl SceneDispatchSystemState {
 
 /// Dispatches scene data from entities
 pub fn scene_dispatch_system(args: SystemArgs, state: &SceneDispatchSystemState) {
+    if args.entity.render().is_some_and(|render| !render.visible) {
+        state.scene.remove(args.entity_id);
+
+        return;
+    }
+
     match args.entity {
         Entity::Camera(camera) => {
             state
@@ -283,5 +854,214 @@ pub fn scene_dispatch_system(args: SystemArgs, state: &SceneDispatchSystemState)
                 .scene
                 .dispatch::<scene::ModelSceneEntity>(args.entity_id, bullet.into());
         }
+
+        Entity::Afterimage(afterimage) => {
+            state
+                .scene
+                .dispatch::<scene::ModelSceneEntity>(args.entity_id, afterimage.into());
+        }
+
+        Entity::Particle(particle) => {
+            state
+                .scene
+                .dispatch::<scene::ModelSceneEntity>(args.entity_id, particle.into());
+        }
+
+        Entity::PowerUp(power_up) => {
+            state
+                .scene
+                .dispatch::<scene::ModelSceneEntity>(args.entity_id, power_up.into());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use glam::Vec2;
+
+    use super::*;
+    use crate::{
+        game::{
+            ecs::{ECS, StatefulSystem, StatelessSystem},
+            entities::{Spacecraft, Weapon},
+            physics::Physics,
+        },
+        handle,
+    };
+
+    /// Creates [ECS] with its events infrastructure
+    fn ecs() -> (Arc<events::Events>, Arc<ECS>) {
+        let events: Arc<events::Events> = Default::default();
+        let ecs = ECS::new(&events);
+
+        (events, ecs)
+    }
+
+    /// Adds [entity_collision_system] into [ECS]
+    fn add_collision_system(events: &events::Events, ecs: &ECS) -> handle::Handle {
+        ecs.add_system(
+            "entity_collision_system",
+            StatefulSystem::new(
+                EntityCollisionSystemState::new(events.get_sender()),
+                entity_collision_system,
+            ),
+        )
+    }
+
+    #[test]
+    fn contact_during_blink_does_not_destroy_spacecraft_after_blink() {
+        let (events, ecs) = ecs();
+        let physics = Physics::new(&events, ecs.clone());
+
+        let _system = add_collision_system(&events, &ecs);
+
+        let spacecraft_id = ecs.write().create(Spacecraft {
+            blink: BlinkComponent::new(1.0),
+            ..Default::default()
+        });
+        let asteroid_id = ecs.write().create(Asteroid::with_size(1.0));
+
+        physics.step();
+        ecs.update(0.0);
+
+        let collided = ecs
+            .read()
+            .get(spacecraft_id)
+            .and_then(|entity| entity.collider())
+            .is_some_and(|collider| !collider.collisions.is_empty());
+
+        assert!(collided, "spacecraft should touch asteroid during blink");
+
+        ecs.write().modify(asteroid_id, |entity| {
+            entity.transform_mut().position = Vec2::new(100.0, 0.0);
+        });
+
+        physics.step();
+        ecs.update(0.0);
+
+        ecs.write().modify(spacecraft_id, |entity| {
+            entity.blink_mut().unwrap().remaining = 0.0;
+        });
+
+        ecs.update(0.0);
+
+        assert!(ecs.read().get(spacecraft_id).is_some());
+    }
+
+    #[test]
+    fn owned_bullet_destroying_asteroid_increases_score() {
+        let (events, ecs) = ecs();
+        let physics = Physics::new(&events, ecs.clone());
+        let players = Players::new(&events, Default::default());
+        let player_id = players.new_player();
+
+        let _system = add_collision_system(&events, &ecs);
+
+        ecs.write().create(Asteroid::with_size(1.0));
+        ecs.write().create(Bullet {
+            bullet: BulletComponent {
+                owner: Some(player_id),
+            },
+            ..Default::default()
+        });
+
+        physics.step();
+        ecs.update(0.0);
+        events.flush();
+
+        let score = players.visit_player(&player_id, |player| player.score);
+        assert!(score.is_some_and(|score| score > 0));
+    }
+
+    #[test]
+    fn entity_crossing_positive_x_bound_reappears_at_negative_x() {
+        let (_events, ecs) = ecs();
+        let world_bounds = WorldBounds {
+            half_extent: Vec2::new(10.0, 10.0),
+        };
+
+        let _systems = [
+            ecs.add_system_ordered(0, "movement_system", StatelessSystem::from(movement_system)),
+            ecs.add_system_ordered(
+                1,
+                "wraparound_system",
+                StatefulSystem::new(
+                    WraparoundSystemState::new(Some(world_bounds)),
+                    wraparound_system,
+                ),
+            ),
+        ];
+
+        let mut asteroid = Asteroid::default();
+        asteroid.transform.position = Vec2::new(9.5, 2.0);
+        asteroid.movement.velocity = Vec2::new(1.0, 0.0);
+        asteroid.movement.const_velocity = true;
+
+        let asteroid_id = ecs.write().create(asteroid);
+
+        // NOTE: entity leaves bounds in first update, it is wrapped in next one
+        ecs.update(1.0);
+        ecs.update(0.0);
+
+        let position = ecs.read().get(asteroid_id).unwrap().transform().position;
+        assert!(position.x < 0.0, "{}", position);
+        assert!((position.x + 9.5).abs() < 1e-4, "{}", position);
+        assert_eq!(position.y, 2.0);
+    }
+
+    /// Fires weapon of single spacecraft, returns angles of spawned bullets relative to spacecraft heading
+    fn fire_weapon(weapon: Weapon) -> Vec<f32> {
+        const ROTATION: f32 = 1.0;
+
+        let (events, ecs) = ecs();
+
+        let _system = ecs.add_system(
+            "spacecraft_weapon_fire_system",
+            StatefulSystem::new(
+                SpacecraftWeaponFireSystemState::new(events.get_sender()),
+                spacecraft_weapon_fire_system,
+            ),
+        );
+
+        let mut spacecraft = Spacecraft::default();
+        spacecraft.transform.rotation = ROTATION;
+        spacecraft.spacecraft.weapon = weapon;
+        spacecraft.spacecraft.weapon_fire = true;
+
+        ecs.write().create(spacecraft);
+        ecs.update(0.0);
+
+        let heading = VEC2_RIGHT.rotate(ROTATION.sin_cos().into());
+
+        ecs.read()
+            .iter()
+            .filter_map(|(_, entity)| entity.bullet().and(entity.movement()))
+            .map(|movement| heading.angle_to(movement.velocity))
+            .collect()
+    }
+
+    #[test]
+    fn weapons_fire_bullets_at_their_spread_angles() {
+        for weapon in [
+            Weapon::Regular,
+            Weapon::Heavy,
+            Weapon::Triple,
+            Weapon::Rapid,
+        ] {
+            let angles = fire_weapon(weapon);
+
+            assert_eq!(angles.len(), weapon.spread().len(), "{:?}", weapon);
+
+            for (angle, expected) in angles.iter().zip(weapon.spread()) {
+                assert!(
+                    (angle - expected).abs() < 1e-4,
+                    "{:?}: {:?}",
+                    weapon,
+                    angles
+                );
+            }
+        }
     }
 }
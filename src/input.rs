@@ -1,20 +1,26 @@
 use std::{
     collections::{BTreeMap, BTreeSet},
+    fmt, fs, io,
+    path::Path,
+    str::FromStr,
     sync::{
         Arc, Mutex, RwLock,
         atomic::{AtomicUsize, Ordering},
     },
+    thread,
+    time::{Duration, Instant},
 };
 
+use serde::{Deserialize, Serialize, de};
 use winit::{
-    event::{ElementState, KeyEvent},
+    event::{ElementState, KeyEvent, MouseScrollDelta},
     keyboard::{KeyCode, PhysicalKey},
 };
 
-use crate::{commands, handle};
+use crate::{commands, diagnostics, handle, workers};
 
 /// Enumeration of input keys: contains keyboard (`Kbd...`), mouse (`Mouse...`), gamepad (`G`) keys
-#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum Key {
     KbdSpace,
     KbdEscape,
@@ -65,6 +71,13 @@ pub enum Key {
     // TODO: add more keys
 }
 
+/// Enumeration of input axes: unlike [Key], axis carries a value
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum Axis {
+    /// Vertical mouse wheel: positive value when wheel is scrolled up, measured in lines
+    MouseWheel,
+}
+
 impl TryFrom<KeyCode> for Key {
     type Error = ();
 
@@ -123,7 +136,7 @@ impl TryFrom<KeyCode> for Key {
 }
 
 /// Enumeration of possible input [Key] state
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, PartialEq, Eq)]
 pub enum State {
     Pressed,
     Released,
@@ -138,43 +151,309 @@ impl From<ElementState> for State {
     }
 }
 
+/// Enumeration of modes, which determine when mapped command is triggered
+#[derive(Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum TriggerMode {
+    /// Command is triggered on both key press and key release
+    #[default]
+    Edge,
+    /// Command is triggered on key press only
+    OnPress,
+    /// Command is triggered on key release only
+    OnRelease,
+    /// Command is triggered on key press, repeatedly while key is held and on key release
+    WhileHeld,
+}
+
+impl TriggerMode {
+    /// INTERNAL: checks if mode triggers command on key state change
+    fn triggers_on(&self, state: State) -> bool {
+        matches!(
+            (self, state),
+            (TriggerMode::Edge, _)
+                | (TriggerMode::WhileHeld, _)
+                | (TriggerMode::OnPress, State::Pressed)
+                | (TriggerMode::OnRelease, State::Released)
+        )
+    }
+}
+
+/// Mapping of keys and axes to single command in [Scheme]
+#[derive(Clone, Default)]
+struct Mapping {
+    mode: TriggerMode,
+    keys: BTreeSet<Key>,
+    axes: BTreeSet<Axis>,
+}
+
+/// Type alias for [Scheme] priority: when several schemes bind the same key, only schemes with the highest priority
+/// receive it
+pub type SchemePriority = i32;
+
 /// Input scheme
 pub struct Scheme {
-    mapping: BTreeMap<String, BTreeSet<Key>>,
+    mapping: BTreeMap<String, Mapping>,
+    priority: SchemePriority,
 }
 
 impl Scheme {
+    /// Loads scheme from JSON file: empty scheme is used if file is missing or malformed
+    pub fn load<P>(path: P) -> Scheme
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref();
+
+        match fs::read_to_string(path) {
+            Ok(content) => content.parse().unwrap_or_else(|error| {
+                diagnostics::warning(format_args!(
+                    "{} in {}, scheme is ignored",
+                    error,
+                    path.display()
+                ));
+
+                Default::default()
+            }),
+
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Default::default(),
+
+            Err(error) => {
+                diagnostics::warning(format_args!("failed to read {}: {}", path.display(), error));
+
+                Default::default()
+            }
+        }
+    }
+
+    /// Sets priority of scheme
+    pub fn with_priority(mut self, priority: SchemePriority) -> Scheme {
+        self.priority = priority;
+
+        self
+    }
+
     /// Adds mapping of keys to command
-    pub fn add<S, I>(mut self, command: S, inputs: I) -> Scheme
+    pub fn add<S, I>(self, command: S, inputs: I) -> Scheme
+    where
+        S: Into<String>,
+        I: IntoIterator<Item = Key>,
+    {
+        self.add_with_mode(command, TriggerMode::default(), inputs)
+    }
+
+    /// Adds mapping of keys to command with specified [TriggerMode]
+    pub fn add_with_mode<S, I>(mut self, command: S, mode: TriggerMode, inputs: I) -> Scheme
     where
         S: Into<String>,
         I: IntoIterator<Item = Key>,
     {
         let command = command.into();
 
-        self.mapping
-            .entry(command)
-            .or_default()
-            .extend(inputs.into_iter());
+        let mapping = self.mapping.entry(command).or_default();
+        mapping.mode = mode;
+        mapping.keys.extend(inputs);
 
         self
     }
+
+    /// Adds mapping of axes to command: command is invoked with [commands::Arg::Axis] on each axis change
+    pub fn add_axes<S, I>(mut self, command: S, axes: I) -> Scheme
+    where
+        S: Into<String>,
+        I: IntoIterator<Item = Axis>,
+    {
+        let command = command.into();
+
+        let mapping = self.mapping.entry(command).or_default();
+        mapping.axes.extend(axes);
+
+        self
+    }
+
+    /// Converts scheme into its [SerializableScheme] representation
+    pub fn to_serializable(&self) -> SerializableScheme {
+        let mapping = self
+            .mapping
+            .iter()
+            .map(|(command, mapping)| {
+                let mapping = match (mapping.mode, mapping.keys.is_empty()) {
+                    (TriggerMode::Edge, true) if !mapping.axes.is_empty() => {
+                        SerializableMapping::Axes {
+                            axes: mapping.axes.clone(),
+                        }
+                    }
+                    (TriggerMode::Edge, _) if mapping.axes.is_empty() => {
+                        SerializableMapping::Keys(mapping.keys.clone())
+                    }
+                    (mode, _) => SerializableMapping::WithMode {
+                        mode,
+                        keys: mapping.keys.clone(),
+                        axes: mapping.axes.clone(),
+                    },
+                };
+
+                (command.clone(), mapping)
+            })
+            .collect();
+
+        SerializableScheme(mapping)
+    }
 }
 
 impl Default for Scheme {
     fn default() -> Self {
         Self {
             mapping: Default::default(),
+            priority: Default::default(),
         }
     }
 }
 
+impl From<SerializableScheme> for Scheme {
+    fn from(value: SerializableScheme) -> Self {
+        value.0.into_iter().fold(
+            Scheme::default(),
+            |scheme, (command, mapping)| match mapping {
+                SerializableMapping::Keys(keys) => scheme.add(command, keys),
+                SerializableMapping::WithMode { mode, keys, axes } => scheme
+                    .add_with_mode(command.clone(), mode, keys)
+                    .add_axes(command, axes),
+                SerializableMapping::Axes { axes } => scheme.add_axes(command, axes),
+            },
+        )
+    }
+}
+
+impl FromStr for Scheme {
+    type Err = SchemeParseError;
+
+    /// Parses scheme from JSON map of command name to list of key names
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        serde_json::from_str::<SerializableScheme>(s)
+            .map(Into::into)
+            .map_err(SchemeParseError)
+    }
+}
+
+/// Serializable representation of single command mapping in [SerializableScheme]
+#[derive(Serialize)]
+#[serde(untagged)]
+pub enum SerializableMapping {
+    /// List of key names, triggered with [TriggerMode::Edge]
+    Keys(BTreeSet<Key>),
+    /// List of key names with explicit [TriggerMode] and optional list of axis names
+    WithMode {
+        mode: TriggerMode,
+        keys: BTreeSet<Key>,
+        #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+        axes: BTreeSet<Axis>,
+    },
+    /// List of axis names
+    Axes { axes: BTreeSet<Axis> },
+}
+
+/// INTERNAL: fields of [SerializableMapping] in map form
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SerializableMappingFields {
+    mode: Option<TriggerMode>,
+    #[serde(default)]
+    keys: BTreeSet<Key>,
+    #[serde(default)]
+    axes: BTreeSet<Axis>,
+}
+
+/// INTERNAL: visitor of [SerializableMapping]: unlike untagged deserialization, it reports errors of nested values,
+/// e.g. unknown key name
+struct SerializableMappingVisitor;
+
+impl<'de> de::Visitor<'de> for SerializableMappingVisitor {
+    type Value = SerializableMapping;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "list of key names or map with `mode`, `keys` and `axes`")
+    }
+
+    fn visit_seq<A>(self, seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: de::SeqAccess<'de>,
+    {
+        BTreeSet::deserialize(de::value::SeqAccessDeserializer::new(seq))
+            .map(SerializableMapping::Keys)
+    }
+
+    fn visit_map<A>(self, map: A) -> Result<Self::Value, A::Error>
+    where
+        A: de::MapAccess<'de>,
+    {
+        let SerializableMappingFields { mode, keys, axes } =
+            SerializableMappingFields::deserialize(de::value::MapAccessDeserializer::new(map))?;
+
+        let mapping = match mode {
+            Some(mode) => SerializableMapping::WithMode { mode, keys, axes },
+            None if keys.is_empty() => SerializableMapping::Axes { axes },
+            None => SerializableMapping::WithMode {
+                mode: TriggerMode::Edge,
+                keys,
+                axes,
+            },
+        };
+
+        Ok(mapping)
+    }
+}
+
+impl<'de> Deserialize<'de> for SerializableMapping {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        deserializer.deserialize_any(SerializableMappingVisitor)
+    }
+}
+
+/// Serializable representation of [Scheme]: map of command name to list of key names
+#[derive(Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct SerializableScheme(BTreeMap<String, SerializableMapping>);
+
+/// Error of [Scheme] parsing, i.e. malformed source or unknown key name
+#[derive(Debug)]
+pub struct SchemeParseError(serde_json::Error);
+
+impl fmt::Display for SchemeParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid input scheme: {}", self.0)
+    }
+}
+
+impl std::error::Error for SchemeParseError {}
+
+/// INTERNAL: command with its [TriggerMode], bound to key
+type Binding = (String, TriggerMode);
+
+/// INTERNAL: reference counted bindings of single key, grouped by [SchemePriority]
+type KeyBindings = BTreeMap<SchemePriority, BTreeMap<Binding, usize>>;
+
+/// INTERNAL: base priority of schemes pushed as input contexts
+const CONTEXT_BASE_PRIORITY: SchemePriority = 1 << 16;
+
+/// INTERNAL: gets bindings with the highest priority
+fn active_bindings(bindings: &KeyBindings) -> impl Iterator<Item = &Binding> {
+    bindings
+        .last_key_value()
+        .into_iter()
+        .flat_map(|(_, bindings)| bindings.keys())
+}
+
 /// Input manager
 pub struct Input {
     commands: Arc<commands::Commands>,
     scheme_counter: AtomicUsize,
     schemes: Arc<Mutex<BTreeMap<usize, Scheme>>>,
-    mapping: Arc<RwLock<BTreeMap<Key, BTreeMap<String, usize>>>>,
+    mapping: Arc<RwLock<BTreeMap<Key, KeyBindings>>>,
+    pressed: Mutex<BTreeSet<Key>>,
+    contexts: Mutex<Vec<handle::Handle>>,
 }
 
 impl Input {
@@ -185,6 +464,8 @@ impl Input {
             scheme_counter: Default::default(),
             schemes: Default::default(),
             mapping: Default::default(),
+            pressed: Default::default(),
+            contexts: Default::default(),
         };
 
         Arc::new(input)
@@ -197,16 +478,23 @@ impl Input {
         let mut mapping = self.mapping.write().unwrap();
 
         let scheme_id = self.scheme_counter.fetch_add(1, Ordering::Relaxed);
+        let priority = scheme.priority;
 
         scheme
             .mapping
             .iter()
-            .flat_map(|(command, keys)| keys.iter().copied().map(move |key| (key, command.clone())))
-            .for_each(|(key, command)| {
+            .flat_map(|(command, Mapping { mode, keys, .. })| {
+                keys.iter()
+                    .copied()
+                    .map(move |key| (key, (command.clone(), *mode)))
+            })
+            .for_each(|(key, binding)| {
                 mapping
                     .entry(key)
                     .or_default()
-                    .entry(command)
+                    .entry(priority)
+                    .or_default()
+                    .entry(binding)
                     .and_modify(|ref_count| *ref_count += 1)
                     .or_insert(1);
             });
@@ -222,16 +510,25 @@ impl Input {
 
                 scheme
                     .mapping
-                    .iter()
-                    .flat_map(|(command, keys)| keys.iter().map(move |key| (key, command)))
-                    .for_each(|(key, command)| {
-                        let mapping = mapping.entry(*key).or_default();
-
-                        if let Some(ref_count) = mapping.get_mut(command) {
-                            *ref_count -= 1;
+                    .into_iter()
+                    .flat_map(|(command, Mapping { mode, keys, .. })| {
+                        keys.into_iter()
+                            .map(move |key| (key, (command.clone(), mode)))
+                    })
+                    .for_each(|(key, binding)| {
+                        let mapping = mapping.entry(key).or_default();
+
+                        if let Some(bindings) = mapping.get_mut(&priority) {
+                            if let Some(ref_count) = bindings.get_mut(&binding) {
+                                *ref_count -= 1;
+
+                                if *ref_count == 0 {
+                                    bindings.remove(&binding);
+                                }
+                            }
 
-                            if *ref_count == 0 {
-                                mapping.remove(command);
+                            if bindings.is_empty() {
+                                mapping.remove(&priority);
                             }
                         }
                     });
@@ -241,6 +538,24 @@ impl Input {
         drop.into()
     }
 
+    /// Pushes input context: scheme, which overrides bindings of all previously added schemes and contexts until
+    /// it is popped
+    pub fn push_context(&self, scheme: Scheme) {
+        let mut contexts = self.contexts.lock().unwrap();
+
+        let priority = CONTEXT_BASE_PRIORITY + contexts.len() as SchemePriority;
+        let handle = self.add_scheme(scheme.with_priority(priority));
+
+        contexts.push(handle);
+    }
+
+    /// Pops last pushed input context and restores previous bindings
+    pub fn pop_context(&self) {
+        let handle = self.contexts.lock().unwrap().pop();
+
+        drop(handle);
+    }
+
     /// Dispatches [winit::event::KeyEvent] by our key mapping
     pub fn dispatch_key_event(&self, event: KeyEvent) {
         if event.repeat {
@@ -265,15 +580,265 @@ impl Input {
         }
     }
 
+    /// Dispatches [winit::event::WindowEvent::MouseWheel] delta by our axis mapping
+    pub fn dispatch_mouse_wheel(&self, delta: MouseScrollDelta) {
+        const PIXELS_PER_LINE: f32 = 40.0;
+
+        let value = match delta {
+            MouseScrollDelta::LineDelta(_, y) => y,
+            MouseScrollDelta::PixelDelta(position) => position.y as f32 / PIXELS_PER_LINE,
+        };
+
+        self.dispatch_axis(Axis::MouseWheel, value);
+    }
+
+    /// Dispatches [State::Released] for all held keys, e.g. when window loses focus
+    pub fn release_all(&self) {
+        let pressed = self.pressed.lock().unwrap().clone();
+
+        for key in pressed {
+            self.dispatch(key, State::Released);
+        }
+    }
+
     /// INTERNAL: dispatches key state
     fn dispatch(&self, key: Key, state: State) {
+        {
+            let mut pressed = self.pressed.lock().unwrap();
+
+            match state {
+                State::Pressed => pressed.insert(key),
+                State::Released => pressed.remove(&key),
+            };
+        }
+
         let arg = (key, state).into();
-        let mapping = self.mapping.read().unwrap();
+        let commands: Vec<_> = self
+            .mapping
+            .read()
+            .unwrap()
+            .get(&key)
+            .into_iter()
+            .flat_map(active_bindings)
+            .filter(|(_, mode)| mode.triggers_on(state))
+            .map(|(command, _)| command.clone())
+            .collect();
+
+        for command in commands {
+            self.commands.invoke(command, &[arg]);
+        }
+    }
+
+    /// INTERNAL: dispatches axis value to commands of schemes with the highest priority, which bind the axis
+    fn dispatch_axis(&self, axis: Axis, value: f32) {
+        let commands: Vec<_> = {
+            let schemes = self.schemes.lock().unwrap();
+
+            let bound = |scheme: &&Scheme| {
+                scheme
+                    .mapping
+                    .values()
+                    .any(|mapping| mapping.axes.contains(&axis))
+            };
+
+            let priority = schemes
+                .values()
+                .filter(bound)
+                .map(|scheme| scheme.priority)
+                .max();
+
+            schemes
+                .values()
+                .filter(bound)
+                .filter(|scheme| Some(scheme.priority) == priority)
+                .flat_map(|scheme| scheme.mapping.iter())
+                .filter(|(_, mapping)| mapping.axes.contains(&axis))
+                .map(|(command, _)| command.clone())
+                .collect()
+        };
+
+        let arg = (axis, value).into();
+
+        for command in commands {
+            self.commands.invoke(command, &[arg]);
+        }
+    }
+
+    /// INTERNAL: repeats [TriggerMode::WhileHeld] commands of held keys
+    fn repeat_held(&self) {
+        let pressed = self.pressed.lock().unwrap().clone();
+
+        let commands: Vec<_> = {
+            let mapping = self.mapping.read().unwrap();
+
+            pressed
+                .into_iter()
+                .flat_map(|key| {
+                    mapping
+                        .get(&key)
+                        .into_iter()
+                        .flat_map(active_bindings)
+                        .filter(|(_, mode)| *mode == TriggerMode::WhileHeld)
+                        .map(move |(command, _)| (key, command.clone()))
+                })
+                .collect()
+        };
+
+        for (key, command) in commands {
+            self.commands
+                .invoke(command, &[(key, State::Pressed).into()]);
+        }
+    }
+}
+
+/// Spawns input worker thread, which repeats commands of held keys
+pub fn spawn_worker(workers: &workers::Workers, input: Arc<Input>) -> handle::Handle {
+    workers.spawn("Input", move |token| {
+        const UPDATE_RATE: f32 = 1.0 / 120.0;
 
-        mapping.get(&key).map(|commands| {
-            for command in commands.keys() {
-                self.commands.invoke(command, &[arg]);
+        let mut last_update = Instant::now();
+
+        while !token.is_cancelled() {
+            let elapsed = Instant::now().duration_since(last_update).as_secs_f32();
+
+            input.repeat_held();
+
+            last_update = Instant::now();
+
+            if elapsed < UPDATE_RATE {
+                let duration = Duration::from_secs_f32(UPDATE_RATE - elapsed);
+
+                thread::sleep(duration);
             }
-        });
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scheme_parse_reports_unknown_key_name() {
+        let error = Scheme::from_str(r#"{ "fire": ["KbdSpace", "KbdNope"] }"#)
+            .err()
+            .expect("unknown key should be rejected");
+
+        assert!(error.to_string().contains("KbdNope"), "{}", error);
+    }
+
+    #[test]
+    fn scheme_parse_reports_unknown_key_name_in_map_form() {
+        let error = Scheme::from_str(r#"{ "fire": { "mode": "WhileHeld", "keys": ["KbdNope"] } }"#)
+            .err()
+            .expect("unknown key should be rejected");
+
+        assert!(error.to_string().contains("KbdNope"), "{}", error);
+    }
+
+    #[test]
+    fn scheme_parses_all_mapping_forms() {
+        let scheme = Scheme::from_str(
+            r#"{
+                "exit": ["KbdEscape"],
+                "fire": { "mode": "WhileHeld", "keys": ["KbdSpace"] }
+            }"#,
+        );
+
+        assert!(scheme.is_ok());
+    }
+
+    /// Adds command, which counts its invocations
+    fn add_counter(
+        commands: &commands::Commands,
+        name: &str,
+    ) -> (handle::Handle, Arc<AtomicUsize>) {
+        let counter: Arc<AtomicUsize> = Default::default();
+
+        let handle = commands.add(
+            name,
+            commands::StatefulCommand::new(counter.clone(), |_, counter| {
+                counter.fetch_add(1, Ordering::Relaxed);
+
+                true
+            }),
+        );
+
+        (handle, counter)
+    }
+
+    #[test]
+    fn context_overrides_bindings_until_popped() {
+        let commands: Arc<commands::Commands> = Default::default();
+        let input = Input::new(commands.clone());
+
+        let (_fire, fire) = add_counter(&commands, "fire");
+        let (_select, select) = add_counter(&commands, "menu_select");
+
+        let _scheme = input.add_scheme(Scheme::default().add("fire", [Key::KbdSpace]));
+
+        input.push_context(Scheme::default().add("menu_select", [Key::KbdSpace]));
+        input.dispatch(Key::KbdSpace, State::Pressed);
+
+        assert_eq!(fire.load(Ordering::Relaxed), 0);
+        assert_eq!(select.load(Ordering::Relaxed), 1);
+
+        input.pop_context();
+        input.dispatch(Key::KbdSpace, State::Pressed);
+
+        assert_eq!(fire.load(Ordering::Relaxed), 1);
+        assert_eq!(select.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn scheme_with_higher_priority_overrides_bindings() {
+        let commands: Arc<commands::Commands> = Default::default();
+        let input = Input::new(commands.clone());
+
+        let (_fire, fire) = add_counter(&commands, "fire");
+        let (_boost, boost) = add_counter(&commands, "boost");
+
+        let _default = input.add_scheme(Scheme::default().add("fire", [Key::KbdSpace]));
+        let user = input.add_scheme(
+            Scheme::from_str(r#"{ "boost": ["KbdSpace"] }"#)
+                .unwrap()
+                .with_priority(1),
+        );
+
+        input.dispatch(Key::KbdSpace, State::Pressed);
+
+        assert_eq!(fire.load(Ordering::Relaxed), 0);
+        assert_eq!(boost.load(Ordering::Relaxed), 1);
+
+        drop(user);
+        input.dispatch(Key::KbdSpace, State::Pressed);
+
+        assert_eq!(fire.load(Ordering::Relaxed), 1);
+        assert_eq!(boost.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn missing_scheme_file_is_loaded_as_empty_scheme() {
+        let scheme = Scheme::load("missing_input_scheme.json");
+
+        assert!(scheme.mapping.is_empty());
+    }
+
+    #[test]
+    fn scheme_round_trips_through_json() {
+        let scheme = Scheme::default()
+            .add("exit", [Key::KbdEscape])
+            .add_with_mode("fire", TriggerMode::WhileHeld, [Key::KbdSpace])
+            .add_axes("zoom", [Axis::MouseWheel])
+            .add_with_mode("pan", TriggerMode::OnPress, [Key::KbdArrowUp])
+            .add_axes("pan", [Axis::MouseWheel]);
+
+        let json = serde_json::to_string(&scheme.to_serializable()).unwrap();
+        let parsed = Scheme::from_str(&json).unwrap();
+
+        assert_eq!(
+            serde_json::to_string(&parsed.to_serializable()).unwrap(),
+            json
+        );
     }
 }
@@ -2,6 +2,7 @@ mod app;
 mod assets;
 mod commands;
 mod consts;
+mod diagnostics;
 mod events;
 mod game;
 mod handle;
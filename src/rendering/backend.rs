@@ -7,19 +7,34 @@ use smallvec::{SmallVec, smallvec};
 use vulkano::sync::GpuFuture;
 use winit::{event_loop::ActiveEventLoop, window::Window};
 
-use crate::rendering::{
-    buffer, commands, descriptors, frame, image, logical_device::LogicalDevice,
-    physical_device::PhysicalDevice, pipeline, swapchain::Swapchain,
+#[cfg(test)]
+use crate::rendering::offscreen::Offscreen;
+
+use crate::{
+    diagnostics,
+    rendering::{
+        buffer, commands, descriptors, frame, image,
+        logical_device::LogicalDevice,
+        physical_device::{self, PhysicalDevice},
+        pipeline, sampler,
+        swapchain::Swapchain,
+    },
 };
 
+pub use crate::rendering::physical_device::PresentMode;
+
 mod vk {
     pub use vulkano::{
         Validated, VulkanError, VulkanLibrary,
         buffer::{Buffer, BufferContents, BufferCreateInfo},
         command_buffer::allocator::StandardCommandBufferAllocator,
         descriptor_set::allocator::StandardDescriptorSetAllocator,
-        image::{Image, ImageCreateInfo, ImageType},
-        instance::{Instance, InstanceCreateInfo},
+        format::Format,
+        image::{
+            Image, ImageCreateInfo, ImageType,
+            sampler::{Sampler, SamplerCreateInfo},
+        },
+        instance::{Instance, InstanceCreateInfo, InstanceExtensions},
         memory::allocator::{
             AllocationCreateInfo, MemoryAllocator, MemoryTypeFilter, StandardMemoryAllocator,
         },
@@ -27,8 +42,10 @@ mod vk {
             DynamicState, GraphicsPipeline, PipelineLayout, PipelineShaderStageCreateInfo,
             graphics::{
                 GraphicsPipelineCreateInfo,
-                color_blend::ColorBlendState,
-                input_assembly::{InputAssemblyState, PrimitiveTopology},
+                color_blend::{AttachmentBlend, ColorBlendAttachmentState, ColorBlendState},
+                depth_stencil::{CompareOp, DepthState, DepthStencilState},
+                input_assembly::InputAssemblyState,
+                multisample::MultisampleState,
                 subpass::{PipelineRenderingCreateInfo, PipelineSubpassType},
                 vertex_input::{
                     VertexInputAttributeDescription, VertexInputBindingDescription,
@@ -43,35 +60,40 @@ mod vk {
     };
 }
 
+/// INTERNAL: enumeration of images, which frames are rendered into
+enum RenderTarget {
+    /// Frames are rendered into swapchain of window
+    Swapchain(Mutex<Swapchain>),
+    /// Frames are rendered into offscreen image, see [Backend::new_headless]
+    #[cfg(test)]
+    Offscreen(Mutex<Offscreen>),
+}
+
 /// Rendering backend
 pub struct Backend {
     physical_device: PhysicalDevice,
     logical_device: LogicalDevice,
-    swapchain: Mutex<Swapchain>,
+    render_target: RenderTarget,
 
     memory_allocator: Arc<dyn vk::MemoryAllocator>,
 }
 
 impl Backend {
-    /// Creates new instance of [Backend]
-    pub fn new(event_loop: &ActiveEventLoop, window: Arc<Window>) -> Arc<Backend> {
+    /// Creates new instance of [Backend], swapchain uses `present_mode` if it is supported
+    pub fn new(
+        event_loop: &ActiveEventLoop,
+        window: Arc<Window>,
+        present_mode: PresentMode,
+    ) -> Arc<Backend> {
         let required_extensions = vk::Surface::required_extensions(event_loop)
             .expect("failed to retrieve required extensions");
 
-        let library = vk::VulkanLibrary::new().expect("Vulkan library is not available");
-
-        let create_info = vk::InstanceCreateInfo {
-            application_name: Some("asteroids-rs".into()),
-            enabled_extensions: required_extensions,
-
-            ..Default::default()
-        };
-
-        let instance = vk::Instance::new(library, create_info).expect("failed to create instance");
+        let instance = Self::create_instance(required_extensions);
         let surface = vk::Surface::from_window(instance.clone(), window.clone())
             .expect("failed to create surface");
 
-        let physical_device = PhysicalDevice::autoselect(instance, surface.clone());
+        let physical_device =
+            PhysicalDevice::autoselect(instance, Some(surface.clone()), present_mode);
         let logical_device = LogicalDevice::new(&physical_device);
         let swapchain = Swapchain::new(&physical_device, &logical_device, window, surface);
 
@@ -81,13 +103,104 @@ impl Backend {
         let backend = Backend {
             physical_device,
             logical_device,
-            swapchain: Mutex::new(swapchain),
+            render_target: RenderTarget::Swapchain(Mutex::new(swapchain)),
 
             memory_allocator: Arc::new(memory_allocator),
         };
 
         Arc::new(backend)
     }
+
+    /// INTERNAL: creates VK instance with extensions
+    fn create_instance(enabled_extensions: vk::InstanceExtensions) -> Arc<vk::Instance> {
+        let library = vk::VulkanLibrary::new().expect("Vulkan library is not available");
+
+        let create_info = vk::InstanceCreateInfo {
+            application_name: Some("asteroids-rs".into()),
+            enabled_extensions,
+
+            ..Default::default()
+        };
+
+        vk::Instance::new(library, create_info).expect("failed to create instance")
+    }
+
+    /// Checks if frame images can be read back, e.g. to take screenshots
+    pub fn supports_readback(&self) -> bool {
+        self.physical_device.surface_readback
+    }
+
+    /// Converts pixels read back from frame image into RGBA with 8 bits per channel in place, returns `false` if format
+    /// of frame image is not supported. Colors are not converted between sRGB and linear: pixels of sRGB image are
+    /// already encoded, and pixels of UNORM image are displayed as is, so saved colors match the screen
+    pub fn convert_to_rgba(&self, pixels: &mut [u8]) -> bool {
+        match self.physical_device.surface_format {
+            vk::Format::R8G8B8A8_SRGB | vk::Format::R8G8B8A8_UNORM => true,
+
+            vk::Format::B8G8R8A8_SRGB | vk::Format::B8G8R8A8_UNORM => {
+                pixels
+                    .chunks_exact_mut(4)
+                    .for_each(|pixel| pixel.swap(0, 2));
+
+                true
+            }
+
+            format => {
+                diagnostics::warning(format_args!(
+                    "frame image format {:?} can not be read back",
+                    format
+                ));
+
+                false
+            }
+        }
+    }
+
+    /// Waits until GPU finishes all submitted work, e.g. before resources are destroyed on shutdown
+    pub fn wait_idle(&self) {
+        // SAFETY: no queue is accessed by other threads, renderer and assets workers are expected to be stopped
+        let result = unsafe { self.logical_device.handle.wait_idle() };
+
+        if let Err(error) = result {
+            diagnostics::warning(format_args!("failed to wait for GPU: {}", error));
+        }
+    }
+
+    /// Recreates swapchain if its extent differs from new window extent, zero extent (e.g. minimized window) is ignored;
+    /// offscreen image of headless backend is never resized
+    pub fn resize_swapchain(&self, extent: [u32; 2]) {
+        if extent.contains(&0) {
+            return;
+        }
+
+        let swapchain = match &self.render_target {
+            RenderTarget::Swapchain(swapchain) => swapchain,
+
+            #[cfg(test)]
+            RenderTarget::Offscreen(_) => return,
+        };
+
+        let mut swapchain = swapchain.lock().unwrap();
+
+        if swapchain.extent == extent.map(|value| value as f32) {
+            return;
+        }
+
+        *swapchain = swapchain.clone().recreate();
+    }
+
+    /// INTERNAL: converts [image::ImageFormat] into VK format
+    fn image_format(&self, format: image::ImageFormat) -> vk::Format {
+        match format {
+            // NOTE: color images hold unsigned 8-bit sRGB data (e.g. decoded PNG), sampling converts it to linear
+            image::ImageFormat::Y => vk::Format::R8_SRGB,
+            image::ImageFormat::YA => vk::Format::R8G8_SRGB,
+            image::ImageFormat::RGB => vk::Format::R8G8B8_SRGB,
+            image::ImageFormat::RGBA => vk::Format::R8G8B8A8_SRGB,
+            image::ImageFormat::Depth => vk::Format::D32_SFLOAT,
+            image::ImageFormat::Swapchain => self.physical_device.surface_format,
+        }
+    }
 }
 
 impl buffer::BufferFactory for Backend {
@@ -145,7 +258,8 @@ impl image::ImageFactory for Backend {
 
             usage: definition.usage.into(),
             extent: [w as u32, h as u32, 1],
-            format: definition.format.into(),
+            format: self.image_format(definition.format),
+            samples: definition.samples.into(),
 
             ..Default::default()
         };
@@ -165,6 +279,52 @@ impl image::ImageFactory for Backend {
 
         image
     }
+
+    fn create_with_data<T>(&self, definition: image::ImageDef, data: &[T]) -> image::Image
+    where
+        T: vk::BufferContents + Sized + Clone,
+    {
+        let staging = buffer::BufferFactory::create(
+            self,
+            buffer::BufferDef {
+                usage: buffer::BufferUsage::Transfer,
+                data: buffer::BufferData::Slice(data),
+            },
+        );
+
+        let image = image::ImageFactory::create(self, definition);
+
+        let command_list_allocator = commands::CommandListAllocatorFactory::create(self);
+
+        let mut command_list = command_list_allocator.new_list(
+            physical_device::QueueFamilyType::Graphics,
+            commands::CommandListUsage::Once,
+        );
+
+        command_list.copy_buffer_to_image(&staging, &image);
+
+        commands::CommandListSubmit::submit(self, command_list);
+
+        image
+    }
+}
+
+impl sampler::SamplerFactory for Backend {
+    fn create(&self, definition: sampler::SamplerDef) -> sampler::Sampler {
+        let create_info = vk::SamplerCreateInfo {
+            mag_filter: definition.filter.into(),
+            min_filter: definition.filter.into(),
+
+            ..Default::default()
+        };
+
+        let handle = vk::Sampler::new(self.logical_device.handle.clone(), create_info)
+            .expect("failed to create sampler");
+
+        let sampler = sampler::Sampler { handle };
+
+        sampler
+    }
 }
 
 impl pipeline::PipelineFactory for Backend {
@@ -237,7 +397,7 @@ impl pipeline::PipelineFactory for Backend {
             vertex_input_state: Some(vertex_input_state),
 
             input_assembly_state: Some(vk::InputAssemblyState {
-                topology: vk::PrimitiveTopology::TriangleList,
+                topology: definition.topology.into(),
                 ..Default::default()
             }),
 
@@ -247,18 +407,37 @@ impl pipeline::PipelineFactory for Backend {
             }),
 
             rasterization_state: Some(Default::default()),
-            multisample_state: Some(Default::default()),
+            multisample_state: Some(vk::MultisampleState {
+                rasterization_samples: definition.samples.into(),
+                ..Default::default()
+            }),
+
+            depth_stencil_state: definition.depth_test.then(|| vk::DepthStencilState {
+                depth: Some(vk::DepthState {
+                    write_enable: true,
+                    compare_op: vk::CompareOp::LessOrEqual,
+                }),
+                ..Default::default()
+            }),
 
             color_blend_state: Some(vk::ColorBlendState::with_attachment_states(
                 1,
-                Default::default(),
+                vk::ColorBlendAttachmentState {
+                    blend: Some(vk::AttachmentBlend::alpha()),
+                    ..Default::default()
+                },
             )),
 
             dynamic_state: [vk::DynamicState::Viewport].into_iter().collect(),
 
             subpass: Some(vk::PipelineSubpassType::BeginRendering(
                 vk::PipelineRenderingCreateInfo {
-                    color_attachment_formats: vec![Some(self.physical_device.surface_format)],
+                    color_attachment_formats: vec![Some(
+                        self.image_format(image::ImageFormat::Swapchain),
+                    )],
+                    depth_attachment_format: definition
+                        .depth_test
+                        .then(|| self.image_format(image::ImageFormat::Depth)),
                     ..Default::default()
                 },
             )),
@@ -292,7 +471,7 @@ impl commands::CommandListAllocatorFactory for Backend {
     }
 }
 
-impl commands::CommandListSubmit for Backend {
+impl commands::CommandListSubmit for &Backend {
     fn submit(self, command_list: commands::CommandList) {
         let command_buffer = command_list
             .builder
@@ -321,12 +500,39 @@ impl descriptors::DescriptorAllocatorFactory for Backend {
 
 impl frame::FrameFactory for Backend {
     fn try_acquire(&self) -> Option<frame::Frame> {
-        let swapchain = self.swapchain.lock().unwrap();
+        let swapchain = match &self.render_target {
+            RenderTarget::Swapchain(swapchain) => swapchain,
+
+            #[cfg(test)]
+            RenderTarget::Offscreen(offscreen) => {
+                let offscreen = offscreen.lock().unwrap();
+
+                let frame = frame::Frame {
+                    logical_device: self.logical_device.clone(),
+                    extent: offscreen.image.extent,
+                    image_view: offscreen.image_view.clone(),
+                    wait_on_submit: true,
+                    presentation: frame::FramePresentation::Offscreen(offscreen),
+                };
+
+                return Some(frame);
+            }
+        };
+
+        let mut swapchain = swapchain.lock().unwrap();
+
+        // NOTE: minimized window has zero extent, frame is not acquired until window is restored
+        if swapchain.is_minimized() {
+            return None;
+        }
 
         let result = vk::acquire_next_image(swapchain.handle.clone(), None);
         let (image_index, suboptimal, acquire_future) = match result.map_err(vk::Validated::unwrap)
         {
             Err(vk::VulkanError::OutOfDate) => {
+                // NOTE: restored window may keep its extent, so swapchain is recreated here instead of on resize
+                *swapchain = swapchain.clone().recreate();
+
                 return None;
             }
 
@@ -335,12 +541,107 @@ impl frame::FrameFactory for Backend {
 
         let frame = frame::Frame {
             logical_device: self.logical_device.clone(),
-            swapchain,
-            swapchain_suboptimal: suboptimal,
-            image_index,
-            acquire_future,
+            extent: swapchain.extent,
+            image_view: swapchain
+                .image_views
+                .get(image_index as usize)
+                .cloned()
+                .expect("invalid image index"),
+            wait_on_submit: false,
+            presentation: frame::FramePresentation::Swapchain {
+                swapchain,
+                suboptimal,
+                image_index,
+                acquire_future,
+            },
         };
 
         Some(frame)
     }
 }
+
+#[cfg(test)]
+impl Backend {
+    /// Creates new instance of headless [Backend]: it does not require window and surface, frames are rendered into
+    /// offscreen image of `extent` instead of swapchain, see [Backend::read_pixels]
+    pub fn new_headless(extent: [u32; 2]) -> Arc<Backend> {
+        let instance = Self::create_instance(Default::default());
+
+        let physical_device = PhysicalDevice::autoselect(instance, None, PresentMode::Fifo);
+        let logical_device = LogicalDevice::new(&physical_device);
+
+        let memory_allocator: Arc<dyn vk::MemoryAllocator> = Arc::new(
+            vk::StandardMemoryAllocator::new_default(logical_device.handle.clone()),
+        );
+
+        let offscreen = Offscreen::new(
+            memory_allocator.clone(),
+            physical_device.surface_format,
+            extent,
+        );
+
+        let backend = Backend {
+            physical_device,
+            logical_device,
+            render_target: RenderTarget::Offscreen(Mutex::new(offscreen)),
+
+            memory_allocator,
+        };
+
+        Arc::new(backend)
+    }
+
+    /// Reads pixels of last frame rendered by headless backend, returns [None] if backend renders into swapchain or
+    /// format of offscreen image is not supported; pixels are RGBA with 8 bits per channel, row by row from top-left
+    /// corner
+    pub fn read_pixels(&self) -> Option<Vec<u8>> {
+        let RenderTarget::Offscreen(offscreen) = &self.render_target else {
+            return None;
+        };
+
+        let offscreen = offscreen.lock().unwrap();
+        let [w, h] = offscreen.image.extent;
+
+        let readback = buffer::BufferFactory::create::<u8>(
+            self,
+            buffer::BufferDef {
+                usage: buffer::BufferUsage::Readback,
+                data: buffer::BufferData::EmptySlice(4 * w as usize * h as usize),
+            },
+        );
+
+        let command_list_allocator = commands::CommandListAllocatorFactory::create(self);
+
+        let mut command_list = command_list_allocator.new_list(
+            physical_device::QueueFamilyType::Graphics,
+            commands::CommandListUsage::Once,
+        );
+
+        command_list.copy_image_to_buffer(&offscreen.image, &readback);
+
+        let command_buffer = command_list
+            .builder
+            .build()
+            .expect("failed to build command buffer");
+
+        vk::sync::now(self.logical_device.handle.clone())
+            .then_execute(command_list.queue, command_buffer)
+            .expect("failed to execute command buffer")
+            .then_signal_fence_and_flush()
+            .expect("failed to flush readback")
+            .wait(None)
+            .expect("failed to wait for readback");
+
+        let mut pixels = readback.read().to_vec();
+
+        self.convert_to_rgba(&mut pixels).then_some(pixels)
+    }
+
+    /// Creates headless [Backend] for tests, returns [None] if there is no Vulkan implementation on the machine,
+    /// so tests which require GPU are skipped there
+    pub fn try_new_headless(extent: [u32; 2]) -> Option<Arc<Backend>> {
+        vk::VulkanLibrary::new().ok()?;
+
+        std::panic::catch_unwind(|| Backend::new_headless(extent)).ok()
+    }
+}
@@ -1,3 +1,5 @@
+use std::ops::Range;
+
 mod vk {
     pub use vulkano::buffer::{
         BufferContents, BufferReadGuard, BufferUsage, BufferWriteGuard, Subbuffer,
@@ -12,6 +14,10 @@ pub enum BufferUsage {
     Index,
     /// Buffer is used for shader data
     Uniform,
+    /// Buffer is used as source of transfer operations
+    Transfer,
+    /// Buffer is used as destination of transfer operations, its data is read back by CPU
+    Readback,
 }
 
 impl From<BufferUsage> for vk::BufferUsage {
@@ -20,6 +26,8 @@ impl From<BufferUsage> for vk::BufferUsage {
             BufferUsage::Vertex => vk::BufferUsage::VERTEX_BUFFER,
             BufferUsage::Index => vk::BufferUsage::INDEX_BUFFER,
             BufferUsage::Uniform => vk::BufferUsage::UNIFORM_BUFFER,
+            BufferUsage::Transfer => vk::BufferUsage::TRANSFER_SRC,
+            BufferUsage::Readback => vk::BufferUsage::TRANSFER_DST,
         }
     }
 }
@@ -76,6 +84,16 @@ where
     pub fn len(&self) -> usize {
         self.handle.len() as usize
     }
+
+    /// Returns part of the buffer in range of elements
+    pub fn slice(&self, range: Range<usize>) -> Buffer<T> {
+        Buffer {
+            handle: self
+                .handle
+                .clone()
+                .slice(range.start as u64..range.end as u64),
+        }
+    }
 }
 
 /// Trait of a [Buffer] factory
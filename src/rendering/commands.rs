@@ -9,7 +9,8 @@ mod vk {
         buffer::BufferContents,
         command_buffer::{
             AutoCommandBufferBuilder, CommandBufferUsage, CopyBufferToImageInfo,
-            PrimaryAutoCommandBuffer, RenderingInfo, allocator::CommandBufferAllocator,
+            CopyImageToBufferInfo, PrimaryAutoCommandBuffer, RenderingInfo,
+            allocator::CommandBufferAllocator,
         },
         descriptor_set::DescriptorSetWithOffsets,
         device::Queue,
@@ -107,6 +108,19 @@ impl CommandList {
             .expect("failed to add copy buffer to image command");
     }
 
+    /// Adds command to copy image to buffer
+    pub fn copy_image_to_buffer<T>(&mut self, image: &image::Image, buffer: &buffer::Buffer<T>)
+    where
+        T: vk::BufferContents + Sized,
+    {
+        let copy_image_to_buffer_info =
+            vk::CopyImageToBufferInfo::image_buffer(image.handle.clone(), buffer.handle.clone());
+
+        self.builder
+            .copy_image_to_buffer(copy_image_to_buffer_info)
+            .expect("failed to add copy image to buffer command");
+    }
+
     /// Adds command to set viewports
     pub fn set_viewports<I>(&mut self, viewports: I)
     where
@@ -136,6 +150,20 @@ impl CommandList {
             .expect("failed to add bind vertex buffer command");
     }
 
+    /// Adds command to bind vertex buffer and per-instance buffer
+    pub fn bind_instanced_vertex_buffers<T, I>(
+        &mut self,
+        vertex: &buffer::Buffer<T>,
+        instance: &buffer::Buffer<I>,
+    ) where
+        T: vk::BufferContents + Sized,
+        I: vk::BufferContents + Sized,
+    {
+        self.builder
+            .bind_vertex_buffers(0, (vertex.handle.clone(), instance.handle.clone()))
+            .expect("failed to add bind vertex buffers command");
+    }
+
     /// Adds command to bind index buffer
     pub fn bind_index_buffer(&mut self, buffer: &buffer::Buffer<u32>) {
         self.builder
@@ -2,7 +2,10 @@ use std::sync::Arc;
 
 use vulkano::pipeline::Pipeline;
 
-use crate::rendering::pipeline;
+use crate::{
+    diagnostics,
+    rendering::{image, pipeline, sampler},
+};
 
 mod vk {
     pub use vulkano::descriptor_set::{
@@ -10,6 +13,19 @@ mod vk {
     };
 }
 
+/// Constructs descriptor write of image with sampler
+pub fn image_sampler_write(
+    binding: u32,
+    image_view: &image::ImageView,
+    sampler: &sampler::Sampler,
+) -> vk::WriteDescriptorSet {
+    vk::WriteDescriptorSet::image_view_sampler(
+        binding,
+        image_view.handle.clone(),
+        sampler.handle.clone(),
+    )
+}
+
 /// [vk::DescriptorSet] allocator
 pub struct DescriptorAllocator {
     /// VK allocator handle
@@ -17,14 +33,14 @@ pub struct DescriptorAllocator {
 }
 
 impl DescriptorAllocator {
-    /// Allocates [vk::DescriptorSet]
+    /// Allocates [vk::DescriptorSet], returns [None] if allocation failed (e.g. out of memory)
     pub fn allocate<W, C>(
         &self,
         pipeline: &pipeline::Pipeline,
         layout_index: usize,
         writes: W,
         copies: C,
-    ) -> Arc<vk::DescriptorSet>
+    ) -> Option<Arc<vk::DescriptorSet>>
     where
         W: IntoIterator<Item = vk::WriteDescriptorSet>,
         C: IntoIterator<Item = vk::CopyDescriptorSet>,
@@ -38,7 +54,10 @@ impl DescriptorAllocator {
             .expect("invalid layout index");
 
         let handle = vk::DescriptorSet::new(self.allocator.clone(), layout, writes, copies)
-            .expect("failed to create descriptor");
+            .inspect_err(|error| {
+                diagnostics::warning(format_args!("failed to create descriptor: {}", error))
+            })
+            .ok();
 
         handle
     }
@@ -1,28 +1,89 @@
-use std::sync::MutexGuard;
+use std::sync::{Arc, MutexGuard};
 
 use vulkano::sync::GpuFuture;
 
-use crate::rendering::{commands, logical_device, physical_device, swapchain};
+use crate::rendering::{commands, image, logical_device, physical_device, swapchain};
+
+#[cfg(test)]
+use crate::rendering::offscreen;
 
 mod vk {
     pub use vulkano::{
         Validated, VulkanError,
+        image::view::ImageView,
         swapchain::{SwapchainAcquireFuture, SwapchainPresentInfo},
         sync,
     };
 }
 
+/// Enumeration of ways to present [Frame]
+pub enum FramePresentation<'a> {
+    /// Frame is rendered into swapchain image and presented into window
+    Swapchain {
+        swapchain: MutexGuard<'a, swapchain::Swapchain>,
+        suboptimal: bool,
+        image_index: u32,
+        acquire_future: vk::SwapchainAcquireFuture,
+    },
+    /// Frame is rendered into offscreen image, rendering is awaited on submit
+    #[cfg(test)]
+    Offscreen(MutexGuard<'a, offscreen::Offscreen>),
+}
+
 /// A frame - temporary acquisition of backend resources to render single frame
 pub struct Frame<'a> {
     pub logical_device: logical_device::LogicalDevice,
-    pub swapchain: MutexGuard<'a, swapchain::Swapchain>,
-    pub swapchain_suboptimal: bool,
-    pub image_index: u32,
-    pub acquire_future: vk::SwapchainAcquireFuture,
+    /// Extent of frame image
+    pub extent: [f32; 2],
+    /// View of image to render frame into
+    pub image_view: Arc<vk::ImageView>,
+    /// Determines whether submit blocks until frame is rendered, e.g. to read frame image back
+    pub wait_on_submit: bool,
+    pub presentation: FramePresentation<'a>,
+}
+
+impl Frame<'_> {
+    /// Returns image to render frame into
+    pub fn image(&self) -> image::Image {
+        image::Image {
+            handle: self.image_view.image().clone(),
+            extent: self.extent,
+        }
+    }
 }
 
 impl commands::CommandListSubmit for Frame<'_> {
-    fn submit(mut self, command_list: commands::CommandList) {
+    fn submit(self, command_list: commands::CommandList) {
+        let command_buffer = command_list
+            .builder
+            .build()
+            .expect("failed to build command buffer");
+
+        let execute_future = vk::sync::now(self.logical_device.handle.clone());
+
+        let (mut swapchain, suboptimal, image_index, acquire_future) = match self.presentation {
+            FramePresentation::Swapchain {
+                swapchain,
+                suboptimal,
+                image_index,
+                acquire_future,
+            } => (swapchain, suboptimal, image_index, acquire_future),
+
+            #[cfg(test)]
+            FramePresentation::Offscreen(_offscreen) => {
+                // NOTE: offscreen image stays locked until rendering is finished, so it can be read back right away
+                execute_future
+                    .then_execute(command_list.queue, command_buffer)
+                    .expect("failed to execute command buffer")
+                    .then_signal_fence_and_flush()
+                    .expect("failed to flush frame")
+                    .wait(None)
+                    .expect("failed to wait for frame");
+
+                return;
+            }
+        };
+
         let present_queue = self
             .logical_device
             .queues
@@ -30,20 +91,15 @@ impl commands::CommandListSubmit for Frame<'_> {
             .cloned()
             .expect("queue family is not available");
 
-        let command_buffer = command_list
-            .builder
-            .build()
-            .expect("failed to build command buffer");
-
-        let result = vk::sync::now(self.logical_device.handle.clone())
-            .join(self.acquire_future)
+        let result = execute_future
+            .join(acquire_future)
             .then_execute(command_list.queue, command_buffer)
             .expect("failed to execute command buffer")
             .then_swapchain_present(
                 present_queue,
                 vk::SwapchainPresentInfo::swapchain_image_index(
-                    self.swapchain.handle.clone(),
-                    self.image_index,
+                    swapchain.handle.clone(),
+                    image_index,
                 ),
             )
             .then_signal_fence_and_flush()
@@ -52,19 +108,26 @@ impl commands::CommandListSubmit for Frame<'_> {
         let outdated = match result {
             Err(vk::VulkanError::OutOfDate) => true,
             result => {
-                result.expect("failed to present frame").cleanup_finished();
-                self.swapchain_suboptimal
+                let mut future = result.expect("failed to present frame");
+
+                if self.wait_on_submit {
+                    future.wait(None).expect("failed to wait for frame");
+                }
+
+                future.cleanup_finished();
+                suboptimal
             }
         };
 
-        if outdated {
-            *self.swapchain = self.swapchain.clone().recreate();
+        // NOTE: swapchain is recreated after window is restored, see [FrameFactory::try_acquire]
+        if outdated && !swapchain.is_minimized() {
+            *swapchain = swapchain.clone().recreate();
         }
     }
 }
 
 /// Trait of [Frame] factory
 pub trait FrameFactory {
-    /// Tries to acquire [Frame]
+    /// Tries to acquire [Frame], returns [None] if window is minimized or swapchain is out of date
     fn try_acquire(&self) -> Option<Frame>;
 }
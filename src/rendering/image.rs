@@ -4,9 +4,9 @@ use bitflags::bitflags;
 
 mod vk {
     pub use vulkano::{
-        format::Format,
+        buffer::BufferContents,
         image::{
-            Image, ImageUsage,
+            Image, ImageUsage, SampleCount,
             view::{ImageView, ImageViewCreateInfo},
         },
     };
@@ -14,11 +14,14 @@ mod vk {
 
 bitflags! {
     /// Type alias of [Image] usage flags
+    #[derive(Clone, Copy, Debug)]
     pub struct ImageUsage : u8 {
         /// Image can be used as color attachment in [super::render_graph::Pass]
         const COLOR_ATTACHMENT = 1 << 0;
         /// Image can be used as depth attachment in [super::render_graph::Pass]
         const DEPTH_ATTACHMENT = 1 << 1;
+        /// Image can be sampled in shaders
+        const SAMPLED = 1 << 2;
     }
 }
 
@@ -36,12 +39,16 @@ impl From<ImageUsage> for vk::ImageUsage {
             result |= vk::ImageUsage::DEPTH_STENCIL_ATTACHMENT;
         }
 
+        if value.contains(ImageUsage::SAMPLED) {
+            result |= vk::ImageUsage::SAMPLED;
+        }
+
         result
     }
 }
 
 /// Enumeration of possible [Image] formats
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum ImageFormat {
     /// Grayscale
     Y,
@@ -51,15 +58,33 @@ pub enum ImageFormat {
     RGB,
     /// RGB with alpha channel
     RGBA,
+    /// Depth
+    Depth,
+    /// Same format as images of [crate::rendering::swapchain::Swapchain]
+    Swapchain,
 }
 
-impl From<ImageFormat> for vk::Format {
-    fn from(value: ImageFormat) -> Self {
+/// Enumeration of samples count per pixel of [Image]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum SampleCount {
+    /// Single sample, no multisampling
+    #[default]
+    Sample1,
+    /// 2 samples
+    Sample2,
+    /// 4 samples
+    Sample4,
+    /// 8 samples
+    Sample8,
+}
+
+impl From<SampleCount> for vk::SampleCount {
+    fn from(value: SampleCount) -> Self {
         match value {
-            ImageFormat::Y => Self::R8_SNORM,
-            ImageFormat::YA => Self::R8G8_SNORM,
-            ImageFormat::RGB => Self::R8G8B8_SNORM,
-            ImageFormat::RGBA => Self::R8G8B8A8_SNORM,
+            SampleCount::Sample1 => Self::Sample1,
+            SampleCount::Sample2 => Self::Sample2,
+            SampleCount::Sample4 => Self::Sample4,
+            SampleCount::Sample8 => Self::Sample8,
         }
     }
 }
@@ -72,6 +97,8 @@ pub struct ImageDef {
     pub extent: [f32; 2],
     /// Color format of image
     pub format: ImageFormat,
+    /// Samples count per pixel of image
+    pub samples: SampleCount,
 }
 
 /// Image
@@ -111,4 +138,9 @@ pub struct ImageView {
 pub trait ImageFactory {
     /// Creates instance of [Image] from [ImageDef]
     fn create(&self, definition: ImageDef) -> Image;
+
+    /// Creates instance of [Image] from [ImageDef] and uploads pixel data into it
+    fn create_with_data<T>(&self, definition: ImageDef, data: &[T]) -> Image
+    where
+        T: vk::BufferContents + Sized + Clone;
 }
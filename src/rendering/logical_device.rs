@@ -13,12 +13,18 @@ mod vk {
 
 /// INTERNAL: list of required device extensions
 const REQUIRED_EXTENSIONS: vk::DeviceExtensions = vk::DeviceExtensions {
-    khr_swapchain: true,
     khr_dynamic_rendering: true,
 
     ..vk::DeviceExtensions::empty()
 };
 
+/// INTERNAL: list of device extensions, required to present frames
+const PRESENT_EXTENSIONS: vk::DeviceExtensions = vk::DeviceExtensions {
+    khr_swapchain: true,
+
+    ..vk::DeviceExtensions::empty()
+};
+
 /// INTERNAL: list of required device features
 const REQUIRED_FEATURES: vk::DeviceFeatures = vk::DeviceFeatures {
     dynamic_rendering: true,
@@ -34,7 +40,7 @@ pub struct LogicalDevice {
 }
 
 impl LogicalDevice {
-    /// Creates new instance of [LogicalDevice]
+    /// Creates new instance of [LogicalDevice], swapchain extension is enabled only if device has present queue
     pub fn new(physical_device: &PhysicalDevice) -> LogicalDevice {
         let queue_families = physical_device
             .queue_families
@@ -42,8 +48,17 @@ impl LogicalDevice {
             .copied()
             .collect::<BTreeSet<_>>();
 
+        let enabled_extensions = if physical_device
+            .queue_families
+            .contains_key(&QueueFamilyType::Present)
+        {
+            REQUIRED_EXTENSIONS.union(&PRESENT_EXTENSIONS)
+        } else {
+            REQUIRED_EXTENSIONS
+        };
+
         let create_info = vk::DeviceCreateInfo {
-            enabled_extensions: REQUIRED_EXTENSIONS,
+            enabled_extensions,
             enabled_features: REQUIRED_FEATURES,
 
             queue_create_infos: queue_families
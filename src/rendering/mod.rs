@@ -5,10 +5,13 @@ pub mod pipeline;
 pub mod render_graph;
 pub mod render_graph_operation;
 pub mod renderer;
+pub mod sampler;
 
 mod commands;
 mod descriptors;
 mod frame;
 mod logical_device;
+#[cfg(test)]
+mod offscreen;
 mod physical_device;
 mod swapchain;
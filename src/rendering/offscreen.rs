@@ -0,0 +1,59 @@
+use std::sync::Arc;
+
+use crate::rendering::image;
+
+mod vk {
+    pub use vulkano::{
+        format::Format,
+        image::{Image, ImageCreateInfo, ImageType, ImageUsage, view::ImageView},
+        memory::allocator::{AllocationCreateInfo, MemoryAllocator, MemoryTypeFilter},
+    };
+}
+
+/// Offscreen image, which frames of headless backend are rendered into instead of swapchain
+pub struct Offscreen {
+    pub image: image::Image,
+    pub image_view: Arc<vk::ImageView>,
+}
+
+impl Offscreen {
+    /// Creates new instance of [Offscreen]
+    pub fn new(
+        memory_allocator: Arc<dyn vk::MemoryAllocator>,
+        format: vk::Format,
+        extent: [u32; 2],
+    ) -> Offscreen {
+        let [w, h] = extent;
+
+        let create_info = vk::ImageCreateInfo {
+            image_type: vk::ImageType::Dim2d,
+
+            usage: vk::ImageUsage::COLOR_ATTACHMENT | vk::ImageUsage::TRANSFER_SRC,
+            extent: [w, h, 1],
+            format,
+
+            ..Default::default()
+        };
+
+        let allocation_info = vk::AllocationCreateInfo {
+            memory_type_filter: vk::MemoryTypeFilter::PREFER_DEVICE,
+            ..Default::default()
+        };
+
+        let handle = vk::Image::new(memory_allocator, create_info, allocation_info)
+            .expect("failed to create offscreen image");
+
+        let image_view = vk::ImageView::new_default(handle.clone())
+            .expect("failed to create offscreen image view");
+
+        let offscreen = Offscreen {
+            image: image::Image {
+                handle,
+                extent: extent.map(|value| value as f32),
+            },
+            image_view,
+        };
+
+        offscreen
+    }
+}
@@ -1,4 +1,6 @@
-use std::{collections::BTreeMap, ops::Add, sync::Arc};
+use std::{collections::BTreeMap, fmt, ops::Add, str::FromStr, sync::Arc};
+
+use crate::diagnostics;
 
 mod vk {
     pub use vulkano::{
@@ -7,8 +9,9 @@ mod vk {
             physical::{PhysicalDevice, PhysicalDeviceType},
         },
         format::Format,
+        image::ImageUsage,
         instance::Instance,
-        swapchain::{ColorSpace, Surface},
+        swapchain::{ColorSpace, PresentMode, Surface},
     };
 }
 
@@ -21,6 +24,57 @@ pub enum QueueFamilyType {
     Present,
 }
 
+/// Enumeration of swapchain present modes
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PresentMode {
+    /// Frames are presented on vertical blank, rendering is capped by display refresh rate (VSync)
+    Fifo,
+    /// Frames are presented on vertical blank, newer frame replaces queued one, rendering is not capped
+    Mailbox,
+    /// Frames are presented immediately, tearing is possible, rendering is not capped
+    Immediate,
+}
+
+impl From<PresentMode> for vk::PresentMode {
+    fn from(value: PresentMode) -> Self {
+        match value {
+            PresentMode::Fifo => Self::Fifo,
+            PresentMode::Mailbox => Self::Mailbox,
+            PresentMode::Immediate => Self::Immediate,
+        }
+    }
+}
+
+impl FromStr for PresentMode {
+    type Err = PresentModeParseError;
+
+    /// Parses present mode from its name: `fifo`, `mailbox` or `immediate`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "fifo" => Ok(PresentMode::Fifo),
+            "mailbox" => Ok(PresentMode::Mailbox),
+            "immediate" => Ok(PresentMode::Immediate),
+            _ => Err(PresentModeParseError(s.to_string())),
+        }
+    }
+}
+
+/// Error of [PresentMode] parsing, contains unknown name
+#[derive(Debug)]
+pub struct PresentModeParseError(String);
+
+impl fmt::Display for PresentModeParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "unknown present mode {}, expected fifo, mailbox or immediate",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for PresentModeParseError {}
+
 /// INTERNAL: list of all required queue family types
 const REQUIRED_QUEUE_FAMILY_TYPES: [QueueFamilyType; 2] = [
     QueueFamilyType::Graphics, //
@@ -41,15 +95,23 @@ pub struct PhysicalDevice {
     pub surface_format: vk::Format,
     pub surface_color_space: vk::ColorSpace,
     pub surface_image_count: u32,
+    pub surface_present_mode: PresentMode,
+    /// Frame images can be copied into buffer, e.g. to take screenshots
+    pub surface_readback: bool,
 }
 
 impl PhysicalDevice {
-    /// Autoselects [PhysicalDevice]
-    pub fn autoselect(instance: Arc<vk::Instance>, surface: Arc<vk::Surface>) -> PhysicalDevice {
+    /// Autoselects [PhysicalDevice], `present_mode` is used if it is supported by surface, otherwise
+    /// [PresentMode::Fifo] is used; without surface (i.e. headless) present queue is not required
+    pub fn autoselect(
+        instance: Arc<vk::Instance>,
+        surface: Option<Arc<vk::Surface>>,
+        present_mode: PresentMode,
+    ) -> PhysicalDevice {
         let physical_device = instance
             .enumerate_physical_devices()
             .expect("failed to enumerate physical devices")
-            .filter_map(|handle| Self::try_from(handle, &surface))
+            .filter_map(|handle| Self::try_from(handle, surface.as_deref(), present_mode))
             .min_by_key(|physical_device| match physical_device.device_type {
                 vk::PhysicalDeviceType::DiscreteGpu => 0,
                 vk::PhysicalDeviceType::VirtualGpu => 1,
@@ -59,11 +121,37 @@ impl PhysicalDevice {
             })
             .expect("no suitable physical devices available");
 
+        if surface.is_none() {
+            return physical_device;
+        }
+
+        if physical_device.surface_present_mode != present_mode {
+            diagnostics::warning(format_args!(
+                "present mode {:?} is not supported by surface",
+                present_mode
+            ));
+        }
+
+        if !physical_device.surface_readback {
+            diagnostics::warning(
+                "surface does not support reading frames back, screenshots are disabled",
+            );
+        }
+
+        diagnostics::info(format_args!(
+            "selected present mode {:?}",
+            physical_device.surface_present_mode
+        ));
+
         physical_device
     }
 
     /// INTERNAL: tries to construct physical device if it supports everything we need
-    fn try_from(handle: Arc<vk::PhysicalDevice>, surface: &vk::Surface) -> Option<PhysicalDevice> {
+    fn try_from(
+        handle: Arc<vk::PhysicalDevice>,
+        surface: Option<&vk::Surface>,
+        present_mode: PresentMode,
+    ) -> Option<PhysicalDevice> {
         let queue_families = handle
             .queue_family_properties()
             .iter()
@@ -72,9 +160,11 @@ impl PhysicalDevice {
                 let index = index as u32;
                 let mut pairs = Vec::new();
 
-                let surface_support = handle
-                    .surface_support(index, surface)
-                    .expect("failed to query physical device for surface support");
+                let surface_support = surface.is_some_and(|surface| {
+                    handle
+                        .surface_support(index, surface)
+                        .expect("failed to query physical device for surface support")
+                });
 
                 if properties.queue_flags.contains(vk::QueueFlags::GRAPHICS) {
                     pairs.push((QueueFamilyType::Graphics, index));
@@ -97,18 +187,62 @@ impl PhysicalDevice {
 
         let has_missing_queue_family = REQUIRED_QUEUE_FAMILY_TYPES
             .iter()
+            .filter(|queue_family_type| {
+                surface.is_some() || **queue_family_type != QueueFamilyType::Present
+            })
             .any(|queue_family_type| !queue_families.contains_key(queue_family_type));
 
         if has_missing_queue_family {
             return None;
         }
 
+        let (
+            surface_format,
+            surface_color_space,
+            surface_image_count,
+            surface_present_mode,
+            surface_readback,
+        ) = match surface {
+            Some(surface) => Self::surface_properties(&handle, surface, present_mode),
+
+            // NOTE: headless device renders into offscreen image with default format, which is always read back
+            None => (
+                DEFAULT_FORMAT,
+                DEFAULT_COLOR_SPACE,
+                1,
+                PresentMode::Fifo,
+                true,
+            ),
+        };
+
+        let physical_device = PhysicalDevice {
+            device_type: handle.properties().device_type,
+
+            handle,
+            queue_families,
+            surface_format,
+            surface_color_space,
+            surface_image_count,
+            surface_present_mode,
+            surface_readback,
+        };
+
+        Some(physical_device)
+    }
+
+    /// INTERNAL: selects format, color space, image count and present mode of surface, checks if its images can be
+    /// read back
+    fn surface_properties(
+        handle: &vk::PhysicalDevice,
+        surface: &vk::Surface,
+        present_mode: PresentMode,
+    ) -> (vk::Format, vk::ColorSpace, u32, PresentMode, bool) {
         let surface_capabilities = handle
-            .surface_capabilities(&surface, Default::default())
+            .surface_capabilities(surface, Default::default())
             .expect("failed to retrieve surface capabilites");
 
         let surface_formats = handle
-            .surface_formats(&surface, Default::default())
+            .surface_formats(surface, Default::default())
             .expect("failed to retrieve surface formats");
 
         let (surface_format, surface_color_space) = surface_formats
@@ -122,24 +256,38 @@ impl PhysicalDevice {
                     .expect("no formats are supported by surface")
             });
 
-        let physical_device = PhysicalDevice {
-            device_type: handle.properties().device_type,
+        let surface_image_count = surface_capabilities
+            .max_image_count
+            .map(|max_image_count| {
+                surface_capabilities
+                    .min_image_count
+                    .add(1)
+                    .clamp(surface_capabilities.min_image_count, max_image_count)
+            })
+            .unwrap_or_else(|| surface_capabilities.min_image_count.add(1));
 
-            handle,
-            queue_families,
-            surface_format,
-            surface_color_space,
-            surface_image_count: surface_capabilities
-                .max_image_count
-                .map(|max_image_count| {
-                    surface_capabilities
-                        .min_image_count
-                        .add(1)
-                        .clamp(surface_capabilities.min_image_count, max_image_count)
-                })
-                .unwrap_or_else(|| surface_capabilities.min_image_count.add(1)),
+        // NOTE: FIFO is always supported
+        let present_mode_supported = handle
+            .surface_present_modes(surface, Default::default())
+            .expect("failed to retrieve surface present modes")
+            .contains(&present_mode.into());
+
+        let surface_present_mode = if present_mode_supported {
+            present_mode
+        } else {
+            PresentMode::Fifo
         };
 
-        Some(physical_device)
+        let surface_readback = surface_capabilities
+            .supported_usage_flags
+            .contains(vk::ImageUsage::TRANSFER_SRC);
+
+        (
+            surface_format,
+            surface_color_space,
+            surface_image_count,
+            surface_present_mode,
+            surface_readback,
+        )
     }
 }
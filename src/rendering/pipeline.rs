@@ -1,18 +1,40 @@
 use std::sync::Arc;
 
+use crate::rendering::image;
+
 mod vk {
     pub use vulkano::{
         Validated, VulkanError,
         device::Device,
         format::Format,
-        pipeline::{GraphicsPipeline, graphics::vertex_input::VertexInputRate},
-        shader::ShaderModule,
+        pipeline::{
+            GraphicsPipeline,
+            graphics::{input_assembly::PrimitiveTopology, vertex_input::VertexInputRate},
+        },
+        shader::{ShaderModule, ShaderModuleCreateInfo, spirv},
     };
 }
 
 /// Type alias of shader factory
-pub type ShaderFactory =
-    Box<dyn Fn(Arc<vk::Device>) -> Result<Arc<vk::ShaderModule>, vk::Validated<vk::VulkanError>>>;
+pub type ShaderFactory = Arc<
+    dyn Fn(Arc<vk::Device>) -> Result<Arc<vk::ShaderModule>, vk::Validated<vk::VulkanError>>
+        + Send
+        + Sync,
+>;
+
+/// Creates [ShaderFactory] from content of SPIR-V file, returns [None] if content is not valid SPIR-V module
+pub fn spirv_shader_factory(bytes: &[u8]) -> Option<ShaderFactory> {
+    let code = vk::spirv::bytes_to_words(bytes).ok()?.into_owned();
+
+    vk::spirv::Spirv::new(&code).ok()?;
+
+    let factory = move |device| {
+        // SAFETY: code is parsed as SPIR-V module above, same as code embedded by vulkano_shaders
+        unsafe { vk::ShaderModule::new(device, vk::ShaderModuleCreateInfo::new(&code)) }
+    };
+
+    Some(Arc::new(factory))
+}
 
 /// Enumeration of data rates of [InputDataBinding]
 #[derive(Clone, Copy)]
@@ -32,21 +54,46 @@ impl From<InputDataRate> for vk::VertexInputRate {
     }
 }
 
+/// Enumeration of primitive topologies of [Pipeline]
+#[derive(Clone, Copy)]
+pub enum PipelineTopology {
+    /// Each three vertices form separate triangle
+    TriangleList,
+    /// Each vertex forms line with previous vertex
+    LineStrip,
+}
+
+impl From<PipelineTopology> for vk::PrimitiveTopology {
+    fn from(value: PipelineTopology) -> Self {
+        match value {
+            PipelineTopology::TriangleList => Self::TriangleList,
+            PipelineTopology::LineStrip => Self::LineStrip,
+        }
+    }
+}
+
 /// Enumeration of data formats for [InputDataAttribute]
 #[derive(Clone, Copy)]
 pub enum InputDataFormat {
+    Float,
     Vec2,
+    Vec3,
+    Vec4,
 }
 
 impl From<InputDataFormat> for vk::Format {
     fn from(value: InputDataFormat) -> Self {
         match value {
+            InputDataFormat::Float => Self::R32_SFLOAT,
             InputDataFormat::Vec2 => Self::R32G32_SFLOAT,
+            InputDataFormat::Vec3 => Self::R32G32B32_SFLOAT,
+            InputDataFormat::Vec4 => Self::R32G32B32A32_SFLOAT,
         }
     }
 }
 
 /// Input attribute definition in the [InputDataBinding]
+#[derive(Clone)]
 pub struct InputDataAttribute {
     /// Attribute offset
     pub offset: usize,
@@ -55,6 +102,7 @@ pub struct InputDataAttribute {
 }
 
 /// Input definition of [PipelineStage::Vertex] stage
+#[derive(Clone)]
 pub struct InputDataBinding {
     /// Stride of input
     pub stride: usize,
@@ -70,6 +118,13 @@ pub struct PipelineDef {
     pub shaders: Vec<ShaderFactory>,
     /// List of input data bindings
     pub bindings: Vec<InputDataBinding>,
+    /// Samples count per pixel, should be same as in [crate::rendering::render_graph::Pass]
+    pub samples: image::SampleCount,
+    /// Primitive topology
+    pub topology: PipelineTopology,
+    /// Fragments are tested against and written to depth attachment, disabled for overlays and passes without
+    /// depth attachment
+    pub depth_test: bool,
 }
 
 /// Graphics pipeline
@@ -1,4 +1,4 @@
-use std::collections::BTreeMap;
+use std::{collections::BTreeMap, fmt};
 
 use crate::{
     game::entities::EntityId,
@@ -21,6 +21,8 @@ pub struct Attachments {
     pub color: Vec<image::ImageView>,
     /// Depth attachment
     pub depth: Option<image::ImageView>,
+    /// Images of input targets, which are sampled by pass, see [PassBuilder::add_input]
+    pub inputs: Vec<image::ImageView>,
 }
 
 /// Pass operation context
@@ -33,11 +35,61 @@ pub struct OperationContext<'a> {
     pub attachments: Attachments,
 }
 
+impl OperationContext<'_> {
+    /// Returns [EntityId] argument by its name, if argument exists and is [Arg::EntityId]
+    pub fn entity_id_arg(&self, name: &str) -> Option<EntityId> {
+        self.args.get(name).and_then(Arg::as_entity_id)
+    }
+
+    /// Returns scalar argument by its name, if argument exists and is [Arg::Float]
+    pub fn float_arg(&self, name: &str) -> Option<f32> {
+        self.args.get(name).and_then(Arg::as_float)
+    }
+
+    /// Returns 2-component vector argument by its name, if argument exists and is [Arg::Vec2]
+    pub fn vec2_arg(&self, name: &str) -> Option<[f32; 2]> {
+        self.args.get(name).and_then(Arg::as_vec2)
+    }
+}
+
 /// Enumeration of [RenderGraph] execution arguments
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub enum Arg {
     /// Argument is an [EntityId]
     EntityId(EntityId),
+    /// Argument is a scalar (e.g. time or intensity)
+    Float(f32),
+    /// Argument is a 2-component vector (e.g. position)
+    Vec2([f32; 2]),
+}
+
+impl Arg {
+    /// Returns [EntityId] if [Arg] is a [Arg::EntityId] variant
+    pub fn as_entity_id(&self) -> Option<EntityId> {
+        if let Arg::EntityId(entity_id) = self {
+            Some(*entity_id)
+        } else {
+            None
+        }
+    }
+
+    /// Returns scalar if [Arg] is a [Arg::Float] variant
+    pub fn as_float(&self) -> Option<f32> {
+        if let Arg::Float(value) = self {
+            Some(*value)
+        } else {
+            None
+        }
+    }
+
+    /// Returns vector if [Arg] is a [Arg::Vec2] variant
+    pub fn as_vec2(&self) -> Option<[f32; 2]> {
+        if let Arg::Vec2(value) = self {
+            Some(*value)
+        } else {
+            None
+        }
+    }
 }
 
 /// Trait of render graph pass operation
@@ -79,20 +131,32 @@ unsafe impl<S> Send for StatefulOperation<S> where S: Send + Sync {}
 unsafe impl<S> Sync for StatefulOperation<S> where S: Send + Sync {}
 
 /// Enumeration of [RenderGraph] target image
+#[derive(Debug)]
 pub enum Target {
-    /// Target image is an image from [crate::rendering::swapchain::Swapchain]
+    /// Target image is an image from [crate::rendering::swapchain::Swapchain], or offscreen image of headless
+    /// [crate::rendering::backend::Backend]
     Swapchain,
+    /// Target image is an image allocated by renderer, it has same extent as swapchain; multisampled color attachment
+    /// is rendered into separate image and resolved into target image
+    Image {
+        /// Usage flags of image
+        usage: image::ImageUsage,
+        /// Format of image
+        format: image::ImageFormat,
+    },
 }
 
 /// Enumeration of attachment clear value
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug)]
 pub enum ClearValue {
     /// Clear value is a RGBA color
     Float([f32; 4]),
+    /// Clear value is a depth
+    Depth(f32),
 }
 
 /// Enumeration of attachment loading operations
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug)]
 pub enum AttachmentLoadOp {
     /// Do nothing with attachment
     Ignore,
@@ -117,6 +181,7 @@ impl Into<Option<vk::ClearValue>> for AttachmentLoadOp {
         match self {
             AttachmentLoadOp::Clear(clear_value) => match clear_value {
                 ClearValue::Float(value) => Some(vk::ClearValue::Float(value)),
+                ClearValue::Depth(value) => Some(vk::ClearValue::Depth(value)),
             },
             _ => None,
         }
@@ -124,7 +189,7 @@ impl Into<Option<vk::ClearValue>> for AttachmentLoadOp {
 }
 
 /// Enumeration of attachment store operations
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug)]
 pub enum AttachmentStoreOp {
     /// Do nothing with attachment
     Ignore,
@@ -142,6 +207,7 @@ impl Into<vk::AttachmentStoreOp> for AttachmentStoreOp {
 }
 
 /// Attachment of target image
+#[derive(Debug)]
 pub struct Attachment {
     /// Name of target
     pub target: String,
@@ -157,6 +223,10 @@ pub struct Pass {
     pub color: Vec<Attachment>,
     /// Optional depth attachment
     pub depth: Option<Attachment>,
+    /// Samples count per pixel
+    pub samples: image::SampleCount,
+    /// List of names of input targets
+    pub inputs: Vec<String>,
     /// An [Operation] to execute
     pub operation: Box<dyn Operation>,
 }
@@ -165,6 +235,8 @@ pub struct Pass {
 pub struct PassBuilder {
     color: Vec<Attachment>,
     depth: Option<Attachment>,
+    samples: image::SampleCount,
+    inputs: Vec<String>,
     operation: Option<Box<dyn Operation>>,
 }
 
@@ -183,6 +255,25 @@ impl PassBuilder {
         self
     }
 
+    /// Sets samples count per pixel of [Pass]: with multisampling, color attachments are rendered
+    /// into multisampled images and resolved into targets, image targets are allocated multisampled
+    pub fn set_samples(mut self, samples: image::SampleCount) -> PassBuilder {
+        self.samples = samples;
+
+        self
+    }
+
+    /// Adds input target to [Pass]: image target, which was rendered by previous pass and is sampled by this pass;
+    /// target should have [image::ImageUsage::SAMPLED] usage
+    pub fn add_input<N>(mut self, target: N) -> PassBuilder
+    where
+        N: Into<String>,
+    {
+        self.inputs.push(target.into());
+
+        self
+    }
+
     /// Sets [Operation] to [Pass]
     pub fn set_operation<O>(mut self, operation: O) -> PassBuilder
     where
@@ -198,6 +289,8 @@ impl PassBuilder {
         Pass {
             color: self.color,
             depth: self.depth,
+            samples: self.samples,
+            inputs: self.inputs,
             operation: self.operation.expect("pass should have operation"),
         }
     }
@@ -208,6 +301,8 @@ impl Default for PassBuilder {
         Self {
             color: Default::default(),
             depth: Default::default(),
+            samples: Default::default(),
+            inputs: Default::default(),
             operation: Default::default(),
         }
     }
@@ -221,6 +316,36 @@ pub struct RenderGraph {
     pub passes: Vec<Pass>,
 }
 
+impl fmt::Display for RenderGraph {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "targets:")?;
+
+        for (name, target) in self.targets.iter() {
+            writeln!(f, "  {}: {:?}", name, target)?;
+        }
+
+        writeln!(f, "passes:")?;
+
+        for (index, pass) in self.passes.iter().enumerate() {
+            writeln!(f, "  #{} (samples: {:?})", index, pass.samples)?;
+
+            for attachment in pass.color.iter() {
+                writeln!(f, "    color: {:?}", attachment)?;
+            }
+
+            if let Some(attachment) = &pass.depth {
+                writeln!(f, "    depth: {:?}", attachment)?;
+            }
+
+            if !pass.inputs.is_empty() {
+                writeln!(f, "    inputs: {}", pass.inputs.join(", "))?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
 /// [RenderGraph] builder
 pub struct RenderGraphBuilder {
     targets: BTreeMap<String, Target>,
@@ -279,3 +404,32 @@ pub trait RenderGraphExecutor {
         args: &BTreeMap<String, Arg>,
     );
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn args_round_trip_through_map() {
+        let args: BTreeMap<String, Arg> = [
+            ("entity_id", Arg::EntityId(7)),
+            ("float", Arg::Float(0.5)),
+            ("vec2", Arg::Vec2([1.0, 2.0])),
+        ]
+        .into_iter()
+        .map(|(name, arg)| (name.to_string(), arg))
+        .collect();
+
+        assert_eq!(args["entity_id"].as_entity_id(), Some(7));
+        assert_eq!(args["float"].as_float(), Some(0.5));
+        assert_eq!(args["vec2"].as_vec2(), Some([1.0, 2.0]));
+    }
+
+    #[test]
+    fn arg_accessor_of_other_variant_returns_none() {
+        let arg = Arg::Vec2([1.0, 2.0]);
+
+        assert_eq!(arg.as_entity_id(), None);
+        assert_eq!(arg.as_float(), None);
+    }
+}
@@ -1,19 +1,23 @@
 use std::{
-    collections::BTreeMap,
+    collections::{BTreeMap, VecDeque, btree_map::Entry},
     f32::consts::PI,
-    sync::{Arc, Mutex},
+    iter::once,
+    mem,
+    ops::Range,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::Instant,
 };
 
-use glam::Mat4;
+use glam::{Mat4, Vec2, Vec3};
 
 use crate::{
     assets, events,
     game::entities::EntityId,
     handle,
-    rendering::{
-        backend, buffer, descriptors,
-        render_graph::{self, Arg},
-    },
+    rendering::{backend, buffer, commands, descriptors, image, pipeline, render_graph, sampler},
     scene,
 };
 
@@ -26,15 +30,91 @@ mod vk {
 
 /// INTERNAL: cached model data
 struct CachedModel {
+    // NOTE: pipeline is kept alive, so its address is not reused while model is pooled
+    pipeline: pipeline::Pipeline,
     buffer: buffer::Buffer<assets::types::Model>,
     descriptor: Arc<vk::DescriptorSet>,
+    last_used: u64,
+}
+
+/// INTERNAL: model, which can be pooled in [ModelCache]
+trait PooledModel {
+    /// INTERNAL: returns key of pool, model is handed out only for key it was pooled with
+    fn pool_key(&self) -> usize;
+}
+
+impl PooledModel for CachedModel {
+    fn pool_key(&self) -> usize {
+        Arc::as_ptr(&self.pipeline.handle) as usize
+    }
+}
+
+/// INTERNAL: cache of model data: models of rendered entities and pool of released models,
+/// released models are handed out to new entities with same pipeline instead of allocating new buffer and descriptor
+struct ModelCache<M = CachedModel> {
+    models: BTreeMap<EntityId, M>,
+    pool: BTreeMap<usize, Vec<M>>,
+    pooled: usize,
+    max_pooled: usize,
+}
+
+impl<M> ModelCache<M>
+where
+    M: PooledModel,
+{
+    /// INTERNAL: creates new instance of [ModelCache], pool is limited by `max_pooled` models
+    fn new(max_pooled: usize) -> ModelCache<M> {
+        ModelCache {
+            models: Default::default(),
+            pool: Default::default(),
+            pooled: 0,
+            max_pooled,
+        }
+    }
+
+    /// INTERNAL: releases model of entity into pool, model is dropped if pool is full
+    fn release(&mut self, entity_id: &EntityId) {
+        let Some(model) = self.models.remove(entity_id) else {
+            return;
+        };
+
+        if self.pooled >= self.max_pooled {
+            return;
+        }
+
+        self.pool.entry(model.pool_key()).or_default().push(model);
+        self.pooled += 1;
+    }
+
+    /// INTERNAL: takes pooled model with same pool key, e.g. address of pipeline handle
+    fn acquire(&mut self, key: usize) -> Option<M> {
+        let model = self.pool.get_mut(&key)?.pop()?;
+
+        self.pooled -= 1;
+
+        Some(model)
+    }
+}
+
+/// INTERNAL: key of shared descriptor: addresses of pipeline and image view handles
+type SharedDescriptorKey = (usize, usize);
+
+/// INTERNAL: cached descriptor, which is shared by all models with same bindings
+struct CachedSharedDescriptor {
+    // NOTE: pipeline is kept alive, so its address is not reused while descriptor is cached
+    _pipeline: pipeline::Pipeline,
+    descriptor: Arc<vk::DescriptorSet>,
 }
 
 /// State for [scene_rendering_operation]
 pub struct SceneRenderingOperationState {
     descriptor_allocator: descriptors::DescriptorAllocator,
 
-    cached_models: Arc<Mutex<BTreeMap<EntityId, CachedModel>>>,
+    cached_models: Arc<Mutex<ModelCache>>,
+    cached_textures: Mutex<BTreeMap<SharedDescriptorKey, CachedSharedDescriptor>>,
+    max_cached_models: usize,
+    instanced_pipelines: BTreeMap<assets::AssetRef, assets::AssetRef>,
+    frame: AtomicU64,
 
     assets: Arc<assets::Assets>,
     scene: Arc<scene::Scene>,
@@ -44,36 +124,57 @@ pub struct SceneRenderingOperationState {
 }
 
 impl SceneRenderingOperationState {
-    /// Creates new instance of [SceneRenderingOperationState]
+    /// Creates new instance of [SceneRenderingOperationState], cache of model data is limited by `max_cached_models`:
+    /// least recently rendered models are evicted; models of destroyed and evicted entities are pooled for reuse
     pub fn new(
         events: &events::Events,
         backend: Arc<backend::Backend>,
         assets: Arc<assets::Assets>,
         scene: Arc<scene::Scene>,
+        max_cached_models: usize,
     ) -> SceneRenderingOperationState {
-        let cached_models: Arc<Mutex<BTreeMap<EntityId, CachedModel>>> = Default::default();
+        let cached_models = Arc::new(Mutex::new(ModelCache::new(max_cached_models)));
 
         SceneRenderingOperationState {
             descriptor_allocator: descriptors::DescriptorAllocatorFactory::create(backend.as_ref()),
 
             cached_models: cached_models.clone(),
+            cached_textures: Default::default(),
+            max_cached_models,
+            instanced_pipelines: Default::default(),
+            frame: Default::default(),
 
             assets,
             scene,
             backend,
 
-            _handler: events.add_handler(move |event| match event {
-                events::Event::EntityDestroyed(entity_id) => {
-                    cached_models.lock().unwrap().remove(entity_id);
-                }
-
-                _ => {}
-            }),
+            _handler: events.add_handler_for(
+                mem::discriminant(&events::Event::EntityDestroyed(Default::default())),
+                move |event| {
+                    if let events::Event::EntityDestroyed(entity_id) = event {
+                        cached_models.lock().unwrap().release(entity_id);
+                    }
+                },
+            ),
         }
     }
+
+    /// Enables instanced rendering of untextured models with `pipeline`: models sharing mesh are drawn with single
+    /// draw call of `instanced_pipeline`, which takes [assets::types::Model] as per-instance input
+    pub fn with_instancing(
+        mut self,
+        pipeline: assets::AssetRef,
+        instanced_pipeline: assets::AssetRef,
+    ) -> SceneRenderingOperationState {
+        self.instanced_pipelines
+            .insert(pipeline, instanced_pipeline);
+
+        self
+    }
 }
 
-/// Scene rendering operation: renders entire scene
+/// Scene rendering operation: renders entire scene, models sharing mesh and instanced pipeline are batched
+/// into single instanced draw, remaining models are drawn one by one
 pub fn scene_rendering_operation(
     state: &SceneRenderingOperationState,
     context: render_graph::OperationContext,
@@ -86,11 +187,7 @@ pub fn scene_rendering_operation(
         .extent;
 
     let view_entity_id = context
-        .args
-        .get("view_entity_id")
-        .and_then(|arg| match arg {
-            Arg::EntityId(entity_id) => Some(*entity_id),
-        })
+        .entity_id_arg("view_entity_id")
         .expect("there is no view entity ID provided");
 
     context.command_list.set_viewports([vk::Viewport {
@@ -103,13 +200,7 @@ pub fn scene_rendering_operation(
         .scene
         .get::<scene::ViewSceneEntity>(view_entity_id)
         .get()
-        .map(|view| {
-            let aspect = w / h;
-            let mut projection = Mat4::perspective_infinite_lh(PI / 2.0, aspect, 0.001);
-            projection.col_mut(1)[1] *= -1.0;
-
-            projection * view.matrix
-        });
+        .map(|view| projection_view_matrix(view, w / h));
 
     if let None = projection_view_matrix {
         return;
@@ -117,78 +208,1066 @@ pub fn scene_rendering_operation(
 
     let projection_view_matrix = projection_view_matrix.unwrap();
     let mut cached_models = state.cached_models.lock().unwrap();
+    let mut cached_textures = state.cached_textures.lock().unwrap();
+    let frame = state.frame.fetch_add(1, Ordering::Relaxed);
 
-    let items = state
-        .scene
-        .iter()
+    // NOTE: scene iterator holds scene lock, it is kept alive while collected models are used
+    let mut scene_iter = state.scene.iter();
+    let models = visible_models(scene_iter.by_ref(), projection_view_matrix, |mesh| {
+        state
+            .assets
+            .get(mesh)
+            .and_then(|asset| asset.as_mesh().map(|mesh| mesh.radius))
+    });
+
+    let (batches, models) =
+        batch_instanced_models(&state.instanced_pipelines, models, projection_view_matrix);
+
+    draw_instanced_batches(state, context.command_list, batches);
+
+    let items = models.into_iter().filter_map(|(entity_id, model)| {
+        let pipeline = match state
+            .assets
+            .get(&model.pipeline)
+            .and_then(|asset| asset.as_pipeline().map(|asset| asset.pipeline.clone()))
+        {
+            Some(pipeline) => pipeline,
+            None => return None,
+        };
+
+        // NOTE: textured model is not rendered until its texture is loaded
+        let texture = match &model.texture {
+            Some(texture_ref) => {
+                let asset = state.assets.get(texture_ref)?;
+                let texture = asset.as_texture()?;
+
+                let key = (
+                    Arc::as_ptr(&pipeline.handle) as usize,
+                    Arc::as_ptr(&texture.image_view.handle) as usize,
+                );
+
+                let cached_texture = match cached_textures.entry(key) {
+                    Entry::Occupied(entry) => entry.into_mut(),
+
+                    Entry::Vacant(entry) => {
+                        let descriptor = state.descriptor_allocator.allocate(
+                            &pipeline,
+                            1,
+                            [descriptors::image_sampler_write(
+                                0,
+                                &texture.image_view,
+                                &texture.sampler,
+                            )],
+                            [],
+                        )?;
+
+                        entry.insert(CachedSharedDescriptor {
+                            _pipeline: pipeline.clone(),
+                            descriptor,
+                        })
+                    }
+                };
+
+                Some(cached_texture.descriptor.clone())
+            }
+
+            None => None,
+        };
+
+        let (vertex, index) = match state.assets.get(&model.mesh).and_then(|asset| {
+            asset
+                .as_mesh()
+                .map(|asset| (asset.vertex.clone(), asset.index.clone()))
+        }) {
+            Some(mesh) => mesh,
+            None => return None,
+        };
+
+        let model_data = assets::types::Model {
+            color: model.color,
+            alpha: model.alpha,
+            matrix: projection_view_matrix * model.matrix,
+        };
+
+        if !cached_models.models.contains_key(&entity_id)
+            && let Some(model_cache) = cached_models.acquire(Arc::as_ptr(&pipeline.handle) as usize)
+        {
+            cached_models.models.insert(entity_id, model_cache);
+        }
+
+        let model_cache = match cached_models.models.entry(entity_id) {
+            Entry::Occupied(entry) => {
+                let model_cache = entry.into_mut();
+
+                *model_cache.buffer.write().get_mut(0).unwrap() = model_data;
+                model_cache.last_used = frame;
+
+                model_cache
+            }
+
+            Entry::Vacant(entry) => {
+                let buffer = buffer::BufferFactory::create(
+                    state.backend.as_ref(),
+                    buffer::BufferDef {
+                        usage: buffer::BufferUsage::Uniform,
+                        data: buffer::BufferData::Value(model_data),
+                    },
+                );
+
+                // NOTE: model is skipped if descriptor allocation failed, allocation is retried next frame
+                let descriptor = state.descriptor_allocator.allocate(
+                    &pipeline,
+                    0,
+                    [vk::WriteDescriptorSet::buffer(0, buffer.handle.clone())],
+                    [],
+                )?;
+
+                entry.insert(CachedModel {
+                    pipeline: pipeline.clone(),
+                    buffer,
+                    descriptor,
+                    last_used: frame,
+                })
+            }
+        };
+
+        let descriptors = once(model_cache.descriptor.clone()).chain(texture);
+        let item = (pipeline, descriptors, vertex, index);
+
+        Some(item)
+    });
+
+    for (pipeline, descriptors, vertex, index) in items {
+        context.command_list.bind_pipeline(&pipeline);
+        context.command_list.bind_vertex_buffer(&vertex);
+        context.command_list.bind_index_buffer(&index);
+        context
+            .command_list
+            .bind_descriptors(&pipeline, descriptors);
+        context.command_list.draw(index.len(), 1);
+    }
+
+    evict_cached_models(&mut cached_models, state.max_cached_models);
+}
+
+/// INTERNAL: combines perspective projection of viewport with `aspect` ratio and view matrix
+fn projection_view_matrix(view: &scene::ViewSceneEntity, aspect: f32) -> Mat4 {
+    let mut projection = Mat4::perspective_infinite_lh(PI / 2.0, aspect, 0.001);
+    projection.col_mut(1)[1] *= -1.0;
+
+    projection * view.matrix
+}
+
+/// INTERNAL: collects models of scene entities, which are visible in view frustum; `mesh_radius` returns radius of
+/// bounding circle of mesh, if mesh is loaded
+fn visible_models<'a, I, F>(
+    entities: I,
+    projection_view_matrix: Mat4,
+    mesh_radius: F,
+) -> Vec<(EntityId, &'a scene::ModelSceneEntity)>
+where
+    I: Iterator<Item = (EntityId, &'a scene::SceneEntity)>,
+    F: Fn(&assets::AssetRef) -> Option<f32>,
+{
+    entities
         .filter_map(|(entity_id, entity)| match entity {
             scene::SceneEntity::Model(model) => Some((entity_id, model)),
             _ => None,
         })
-        .filter_map(|(entity_id, model)| {
-            let pipeline = match state
-                .assets
-                .get(&model.pipeline)
-                .and_then(|asset| asset.as_pipeline().map(|asset| asset.pipeline.clone()))
-            {
-                Some(pipeline) => pipeline,
-                None => return None,
-            };
+        .filter(|(_, model)| {
+            is_model_visible(model, mesh_radius(&model.mesh), projection_view_matrix)
+        })
+        .collect()
+}
 
-            let (vertex, index) = match state.assets.get(&model.mesh).and_then(|asset| {
-                asset
-                    .as_mesh()
-                    .map(|asset| (asset.vertex.clone(), asset.index.clone()))
-            }) {
-                Some(mesh) => mesh,
-                None => return None,
-            };
+/// INTERNAL: checks that bounding sphere of model intersects view frustum, far plane is not tested since projection
+/// is infinite; model is considered visible until its mesh is loaded
+fn is_model_visible(
+    model: &scene::ModelSceneEntity,
+    mesh_radius: Option<f32>,
+    projection_view_matrix: Mat4,
+) -> bool {
+    let Some(radius) = mesh_radius else {
+        return true;
+    };
 
-            let model_cache = cached_models
-                .entry(entity_id)
-                .and_modify(|model_cache| {
-                    let mut buffer = model_cache.buffer.write();
-                    let buffer_model = buffer.get_mut(0).unwrap();
+    let scale = [
+        model.matrix.x_axis,
+        model.matrix.y_axis,
+        model.matrix.z_axis,
+    ]
+    .iter()
+    .map(|axis| axis.truncate().length())
+    .fold(0.0, f32::max);
 
-                    *buffer_model = assets::types::Model {
-                        color: model.color,
-                        matrix: projection_view_matrix * model.matrix,
-                    };
-                })
-                .or_insert_with(|| {
-                    let buffer = buffer::BufferFactory::create(
-                        state.backend.as_ref(),
-                        buffer::BufferDef {
-                            usage: buffer::BufferUsage::Uniform,
-                            data: buffer::BufferData::Value(assets::types::Model {
-                                color: model.color,
-                                matrix: projection_view_matrix * model.matrix,
-                            }),
-                        },
-                    );
-
-                    let descriptor = state.descriptor_allocator.allocate(
-                        &pipeline,
-                        0,
-                        [vk::WriteDescriptorSet::buffer(0, buffer.handle.clone())],
-                        [],
-                    );
-
-                    CachedModel { buffer, descriptor }
-                });
-
-            let item = (pipeline, model_cache.descriptor.clone(), vertex, index);
-
-            Some(item)
+    is_sphere_in_frustum(
+        projection_view_matrix,
+        model.matrix.w_axis.truncate(),
+        radius * scale,
+    )
+}
+
+/// INTERNAL: tests sphere against left, right, bottom, top and near planes of frustum, planes are extracted from
+/// rows of projection-view matrix
+fn is_sphere_in_frustum(projection_view_matrix: Mat4, center: Vec3, radius: f32) -> bool {
+    let [x, y, z, w] = [0, 1, 2, 3].map(|index| projection_view_matrix.row(index));
+
+    [w + x, w - x, w + y, w - y, z].iter().all(|plane| {
+        let length = plane.truncate().length();
+
+        if length == 0.0 {
+            return true;
+        }
+
+        (plane.truncate().dot(center) + plane.w) / length >= -radius
+    })
+}
+
+/// INTERNAL: per-instance model data of batches and list of batches: instanced pipeline, mesh and range of instances
+struct InstancedBatches<'a> {
+    instances: Vec<assets::types::Model>,
+    batches: Vec<(&'a assets::AssetRef, &'a assets::AssetRef, Range<usize>)>,
+}
+
+/// INTERNAL: groups untextured models with instanced pipeline by mesh, returns batches and models left for
+/// non-instanced rendering: models with unique mesh are not batched
+fn batch_instanced_models<'a>(
+    instanced_pipelines: &'a BTreeMap<assets::AssetRef, assets::AssetRef>,
+    models: Vec<(EntityId, &'a scene::ModelSceneEntity)>,
+    projection_view_matrix: Mat4,
+) -> (
+    InstancedBatches<'a>,
+    Vec<(EntityId, &'a scene::ModelSceneEntity)>,
+) {
+    const MIN_BATCH_SIZE: usize = 2;
+
+    let mut groups: BTreeMap<_, Vec<_>> = BTreeMap::new();
+    let mut remaining = Vec::new();
+
+    for (entity_id, model) in models {
+        let instanced_pipeline = instanced_pipelines
+            .get(&model.pipeline)
+            .filter(|_| model.texture.is_none());
+
+        match instanced_pipeline {
+            Some(pipeline) => groups
+                .entry((pipeline, &model.mesh))
+                .or_default()
+                .push((entity_id, model)),
+
+            None => remaining.push((entity_id, model)),
+        }
+    }
+
+    let mut batches = InstancedBatches {
+        instances: Vec::new(),
+        batches: Vec::new(),
+    };
+
+    for ((pipeline, mesh), group) in groups {
+        if group.len() < MIN_BATCH_SIZE {
+            remaining.extend(group);
+            continue;
+        }
+
+        let start = batches.instances.len();
+
+        batches
+            .instances
+            .extend(group.iter().map(|(_, model)| assets::types::Model {
+                color: model.color,
+                alpha: model.alpha,
+                matrix: projection_view_matrix * model.matrix,
+            }));
+
+        batches
+            .batches
+            .push((pipeline, mesh, start..batches.instances.len()));
+    }
+
+    (batches, remaining)
+}
+
+/// INTERNAL: uploads per-instance model data into single buffer and issues instanced draw per batch,
+/// batch is skipped until its pipeline and mesh are loaded
+fn draw_instanced_batches(
+    state: &SceneRenderingOperationState,
+    command_list: &mut commands::CommandList,
+    batches: InstancedBatches,
+) {
+    if batches.batches.is_empty() {
+        return;
+    }
+
+    let instances = buffer::BufferFactory::create(
+        state.backend.as_ref(),
+        buffer::BufferDef {
+            usage: buffer::BufferUsage::Vertex,
+            data: buffer::BufferData::Slice(&batches.instances),
+        },
+    );
+
+    for (pipeline, mesh, range) in batches.batches {
+        let pipeline = state
+            .assets
+            .get(pipeline)
+            .and_then(|asset| asset.as_pipeline().map(|asset| asset.pipeline.clone()));
+
+        let mesh = state.assets.get(mesh).and_then(|asset| {
+            asset
+                .as_mesh()
+                .map(|asset| (asset.vertex.clone(), asset.index.clone()))
         });
 
-    for (pipeline, descriptor, vertex, index) in items {
-        context.command_list.bind_pipeline(&pipeline);
+        let (Some(pipeline), Some((vertex, index))) = (pipeline, mesh) else {
+            continue;
+        };
+
+        let count = range.len();
+
+        command_list.bind_pipeline(&pipeline);
+        command_list.bind_instanced_vertex_buffers(&vertex, &instances.slice(range));
+        command_list.bind_index_buffer(&index);
+        command_list.draw(index.len(), count);
+    }
+}
+
+/// INTERNAL: evicts least recently used models from cache until cache fits `max_cached_models`
+fn evict_cached_models(cached_models: &mut ModelCache, max_cached_models: usize) {
+    if cached_models.models.len() <= max_cached_models {
+        return;
+    }
+
+    let mut entries: Vec<_> = cached_models
+        .models
+        .iter()
+        .map(|(entity_id, model_cache)| (model_cache.last_used, *entity_id))
+        .collect();
+
+    entries.sort_unstable();
+
+    let count = cached_models.models.len() - max_cached_models;
+
+    for (_, entity_id) in entries.into_iter().take(count) {
+        cached_models.release(&entity_id);
+    }
+}
+
+/// State for [post_process_operation]
+pub struct PostProcessOperationState {
+    descriptor_allocator: descriptors::DescriptorAllocator,
+    sampler: sampler::Sampler,
+    buffer: buffer::Buffer<assets::types::PostProcess>,
+    // NOTE: input image is reallocated when swapchain is resized, descriptor is recreated for new image
+    cached_descriptor: Mutex<Option<(image::ImageView, Arc<vk::DescriptorSet>)>>,
+
+    pipeline: assets::AssetRef,
+    mesh: assets::AssetRef,
+
+    assets: Arc<assets::Assets>,
+}
+
+impl PostProcessOperationState {
+    /// Creates new instance of [PostProcessOperationState], `mesh` should cover entire screen in clip space
+    pub fn new(
+        backend: Arc<backend::Backend>,
+        assets: Arc<assets::Assets>,
+        pipeline: assets::AssetRef,
+        mesh: assets::AssetRef,
+    ) -> PostProcessOperationState {
+        PostProcessOperationState {
+            descriptor_allocator: descriptors::DescriptorAllocatorFactory::create(backend.as_ref()),
+            sampler: sampler::SamplerFactory::create(
+                backend.as_ref(),
+                sampler::SamplerDef {
+                    filter: sampler::SamplerFilter::Linear,
+                },
+            ),
+            buffer: buffer::BufferFactory::create(
+                backend.as_ref(),
+                buffer::BufferDef {
+                    usage: buffer::BufferUsage::Uniform,
+                    data: buffer::BufferData::Value(assets::types::PostProcess { intensity: 0.0 }),
+                },
+            ),
+            cached_descriptor: Default::default(),
+
+            pipeline,
+            mesh,
+
+            assets,
+        }
+    }
+}
+
+/// Post-processing operation: samples first input of pass and applies vignette to it,
+/// strength of vignette is taken from `post_process_intensity` argument
+pub fn post_process_operation(
+    state: &PostProcessOperationState,
+    context: render_graph::OperationContext,
+) {
+    let [w, h] = context
+        .attachments
+        .color
+        .first()
+        .expect("there is no color target")
+        .extent;
+
+    let input = context
+        .attachments
+        .inputs
+        .first()
+        .expect("there is no input target");
+
+    let intensity = context.float_arg("post_process_intensity").unwrap_or(0.0);
+
+    let pipeline = state
+        .assets
+        .get(&state.pipeline)
+        .and_then(|asset| asset.as_pipeline().map(|asset| asset.pipeline.clone()));
+
+    let mesh = state.assets.get(&state.mesh).and_then(|asset| {
+        asset
+            .as_mesh()
+            .map(|asset| (asset.vertex.clone(), asset.index.clone()))
+    });
+
+    let (Some(pipeline), Some((vertex, index))) = (pipeline, mesh) else {
+        return;
+    };
+
+    let mut cached_descriptor = state.cached_descriptor.lock().unwrap();
+
+    let outdated = cached_descriptor
+        .as_ref()
+        .is_none_or(|(image_view, _)| !Arc::ptr_eq(&image_view.handle, &input.handle));
+
+    if outdated {
+        // NOTE: pass is skipped if descriptor allocation failed, allocation is retried next frame
+        let Some(descriptor) = state.descriptor_allocator.allocate(
+            &pipeline,
+            0,
+            [
+                descriptors::image_sampler_write(0, input, &state.sampler),
+                vk::WriteDescriptorSet::buffer(1, state.buffer.handle.clone()),
+            ],
+            [],
+        ) else {
+            return;
+        };
+
+        *cached_descriptor = Some((input.clone(), descriptor));
+    }
+
+    let (_, descriptor) = cached_descriptor.as_ref().unwrap();
+
+    *state.buffer.write().get_mut(0).unwrap() = assets::types::PostProcess { intensity };
+
+    context.command_list.set_viewports([vk::Viewport {
+        offset: [0.0, 0.0],
+        extent: [w, h],
+        ..Default::default()
+    }]);
+
+    context.command_list.bind_pipeline(&pipeline);
+    context.command_list.bind_vertex_buffer(&vertex);
+    context.command_list.bind_index_buffer(&index);
+    context
+        .command_list
+        .bind_descriptors(&pipeline, [descriptor.clone()]);
+    context.command_list.draw(index.len(), 1);
+}
+
+/// INTERNAL: rolling line graph of last values, drawn in clip space
+struct LineGraph {
+    values: VecDeque<f32>,
+    max_values: usize,
+}
+
+impl LineGraph {
+    /// INTERNAL: size of graph in clip space
+    const SIZE: Vec2 = Vec2::new(0.6, 0.3);
+
+    /// INTERNAL: creates new instance of [LineGraph], graph shows last `max_values` values
+    fn new(max_values: usize) -> LineGraph {
+        LineGraph {
+            values: VecDeque::with_capacity(max_values),
+            max_values,
+        }
+    }
+
+    /// INTERNAL: adds value to graph, oldest value is dropped if graph is full
+    fn push(&mut self, value: f32) {
+        if self.values.len() == self.max_values {
+            self.values.pop_front();
+        }
+
+        self.values.push_back(value);
+    }
+
+    /// INTERNAL: draws graph as line strip with bottom-left corner at `origin` in clip space, values are scaled to
+    /// graph height by `scale` and clamped
+    fn draw(
+        &self,
+        backend: &backend::Backend,
+        pipeline: &pipeline::Pipeline,
+        context: render_graph::OperationContext,
+        origin: Vec2,
+        scale: f32,
+    ) {
+        if self.values.len() < 2 {
+            return;
+        }
+
+        let [w, h] = context
+            .attachments
+            .color
+            .first()
+            .expect("there is no color target")
+            .extent;
+
+        let step = Self::SIZE.x / (self.max_values - 1).max(1) as f32;
+
+        // NOTE: Y axis of clip space points down, so greater values go up from origin
+        let vertices: Vec<_> = self
+            .values
+            .iter()
+            .enumerate()
+            .map(|(index, value)| assets::types::Vertex {
+                position: origin
+                    + Vec2::new(
+                        index as f32 * step,
+                        -Self::SIZE.y * (value / scale).clamp(0.0, 1.0),
+                    ),
+                ..Default::default()
+            })
+            .collect();
+
+        let indices: Vec<_> = (0..vertices.len() as u32).collect();
+
+        let vertex = buffer::BufferFactory::create(
+            backend,
+            buffer::BufferDef {
+                usage: buffer::BufferUsage::Vertex,
+                data: buffer::BufferData::Slice(&vertices),
+            },
+        );
+
+        let index = buffer::BufferFactory::create(
+            backend,
+            buffer::BufferDef {
+                usage: buffer::BufferUsage::Index,
+                data: buffer::BufferData::Slice(&indices),
+            },
+        );
+
+        context.command_list.set_viewports([vk::Viewport {
+            offset: [0.0, 0.0],
+            extent: [w, h],
+            ..Default::default()
+        }]);
+
+        context.command_list.bind_pipeline(pipeline);
         context.command_list.bind_vertex_buffer(&vertex);
         context.command_list.bind_index_buffer(&index);
-        context
-            .command_list
-            .bind_descriptors(&pipeline, [descriptor]);
         context.command_list.draw(index.len(), 1);
     }
 }
+
+/// INTERNAL: rolling history of frame times
+struct FrameTimeHistory {
+    last_frame: Option<Instant>,
+    graph: LineGraph,
+}
+
+/// State for [frame_time_graph_operation]
+pub struct FrameTimeGraphOperationState {
+    history: Mutex<FrameTimeHistory>,
+
+    pipeline: assets::AssetRef,
+
+    assets: Arc<assets::Assets>,
+    backend: Arc<backend::Backend>,
+}
+
+impl FrameTimeGraphOperationState {
+    /// Creates new instance of [FrameTimeGraphOperationState], graph shows last `max_frames` frames,
+    /// `pipeline` should draw line strips
+    pub fn new(
+        backend: Arc<backend::Backend>,
+        assets: Arc<assets::Assets>,
+        pipeline: assets::AssetRef,
+        max_frames: usize,
+    ) -> FrameTimeGraphOperationState {
+        FrameTimeGraphOperationState {
+            history: Mutex::new(FrameTimeHistory {
+                last_frame: None,
+                graph: LineGraph::new(max_frames),
+            }),
+
+            pipeline,
+
+            assets,
+            backend,
+        }
+    }
+}
+
+/// Frame time graph operation: measures time between executions and draws rolling graph of frame times
+/// at `frame_time_graph_origin` argument in clip space (top-left corner of the screen by default), graph is scaled to
+/// `frame_time_graph_scale` argument in seconds
+pub fn frame_time_graph_operation(
+    state: &FrameTimeGraphOperationState,
+    context: render_graph::OperationContext,
+) {
+    const DEFAULT_ORIGIN: Vec2 = Vec2::new(-0.95, -0.6);
+    const DEFAULT_SCALE: f32 = 1.0 / 30.0;
+
+    let origin = context
+        .vec2_arg("frame_time_graph_origin")
+        .map_or(DEFAULT_ORIGIN, Vec2::from);
+    let scale = context
+        .float_arg("frame_time_graph_scale")
+        .unwrap_or(DEFAULT_SCALE);
+
+    let mut history = state.history.lock().unwrap();
+    let now = Instant::now();
+
+    if let Some(last_frame) = history.last_frame.replace(now) {
+        history
+            .graph
+            .push(now.duration_since(last_frame).as_secs_f32());
+    }
+
+    let Some(pipeline) = state
+        .assets
+        .get(&state.pipeline)
+        .and_then(|asset| asset.as_pipeline().map(|asset| asset.pipeline.clone()))
+    else {
+        return;
+    };
+
+    history
+        .graph
+        .draw(state.backend.as_ref(), &pipeline, context, origin, scale);
+}
+
+/// State for [entity_count_graph_operation]
+pub struct EntityCountGraphOperationState {
+    graph: Mutex<LineGraph>,
+    counter: Box<dyn Fn() -> usize + Send + Sync>,
+
+    pipeline: assets::AssetRef,
+
+    assets: Arc<assets::Assets>,
+    backend: Arc<backend::Backend>,
+}
+
+impl EntityCountGraphOperationState {
+    /// Creates new instance of [EntityCountGraphOperationState], graph shows entity count, returned by `counter`,
+    /// for last `max_frames` frames, `pipeline` should draw line strips
+    pub fn new<F>(
+        backend: Arc<backend::Backend>,
+        assets: Arc<assets::Assets>,
+        pipeline: assets::AssetRef,
+        max_frames: usize,
+        counter: F,
+    ) -> EntityCountGraphOperationState
+    where
+        F: Fn() -> usize + Send + Sync + 'static,
+    {
+        EntityCountGraphOperationState {
+            graph: Mutex::new(LineGraph::new(max_frames)),
+            counter: Box::new(counter),
+
+            pipeline,
+
+            assets,
+            backend,
+        }
+    }
+}
+
+/// Entity count graph operation: samples entity count each frame and draws rolling graph of it at
+/// `entity_count_graph_origin` argument in clip space (below frame time graph by default), graph is scaled to
+/// `entity_count_graph_scale` argument
+pub fn entity_count_graph_operation(
+    state: &EntityCountGraphOperationState,
+    context: render_graph::OperationContext,
+) {
+    const DEFAULT_ORIGIN: Vec2 = Vec2::new(-0.95, -0.2);
+    const DEFAULT_SCALE: f32 = 256.0;
+
+    let origin = context
+        .vec2_arg("entity_count_graph_origin")
+        .map_or(DEFAULT_ORIGIN, Vec2::from);
+    let scale = context
+        .float_arg("entity_count_graph_scale")
+        .unwrap_or(DEFAULT_SCALE);
+
+    let mut graph = state.graph.lock().unwrap();
+
+    graph.push((state.counter)() as f32);
+
+    let Some(pipeline) = state
+        .assets
+        .get(&state.pipeline)
+        .and_then(|asset| asset.as_pipeline().map(|asset| asset.pipeline.clone()))
+    else {
+        return;
+    };
+
+    graph.draw(state.backend.as_ref(), &pipeline, context, origin, scale);
+}
+
+/// State for [text_rendering_operation]
+pub struct TextRenderingOperationState {
+    descriptor_allocator: descriptors::DescriptorAllocator,
+    cached_descriptor: Mutex<Option<(SharedDescriptorKey, CachedSharedDescriptor)>>,
+
+    pipeline: assets::AssetRef,
+    font: assets::AssetRef,
+
+    assets: Arc<assets::Assets>,
+    scene: Arc<scene::Scene>,
+    backend: Arc<backend::Backend>,
+}
+
+impl TextRenderingOperationState {
+    /// Creates new instance of [TextRenderingOperationState], `font` should be atlas texture built by
+    /// [assets::font::atlas], `pipeline` should take [assets::types::TextVertex] as input
+    pub fn new(
+        backend: Arc<backend::Backend>,
+        assets: Arc<assets::Assets>,
+        scene: Arc<scene::Scene>,
+        pipeline: assets::AssetRef,
+        font: assets::AssetRef,
+    ) -> TextRenderingOperationState {
+        TextRenderingOperationState {
+            descriptor_allocator: descriptors::DescriptorAllocatorFactory::create(backend.as_ref()),
+            cached_descriptor: Default::default(),
+
+            pipeline,
+            font,
+
+            assets,
+            scene,
+            backend,
+        }
+    }
+}
+
+/// Text rendering operation: draws all text scene entities with quad per glyph, glyphs of all texts are built
+/// into single vertex buffer each frame and drawn with single draw call
+pub fn text_rendering_operation(
+    state: &TextRenderingOperationState,
+    context: render_graph::OperationContext,
+) {
+    let [w, h] = context
+        .attachments
+        .color
+        .first()
+        .expect("there is no color target")
+        .extent;
+
+    let pipeline = state
+        .assets
+        .get(&state.pipeline)
+        .and_then(|asset| asset.as_pipeline().map(|asset| asset.pipeline.clone()));
+
+    let font = state.assets.get(&state.font).and_then(|asset| {
+        asset
+            .as_texture()
+            .map(|asset| (asset.image_view.clone(), asset.sampler.clone()))
+    });
+
+    let (Some(pipeline), Some((image_view, sampler))) = (pipeline, font) else {
+        return;
+    };
+
+    let (vertices, indices) = build_text_vertices(state.scene.texts().values(), Vec2::new(w, h));
+
+    if indices.is_empty() {
+        return;
+    }
+
+    let mut cached_descriptor = state.cached_descriptor.lock().unwrap();
+
+    let key = (
+        Arc::as_ptr(&pipeline.handle) as usize,
+        Arc::as_ptr(&image_view.handle) as usize,
+    );
+
+    let outdated = cached_descriptor
+        .as_ref()
+        .is_none_or(|(cached_key, _)| *cached_key != key);
+
+    if outdated {
+        // NOTE: texts are skipped if descriptor allocation failed, allocation is retried next frame
+        let Some(descriptor) = state.descriptor_allocator.allocate(
+            &pipeline,
+            0,
+            [descriptors::image_sampler_write(0, &image_view, &sampler)],
+            [],
+        ) else {
+            return;
+        };
+
+        let cached = CachedSharedDescriptor {
+            _pipeline: pipeline.clone(),
+            descriptor,
+        };
+
+        *cached_descriptor = Some((key, cached));
+    }
+
+    let (_, cached) = cached_descriptor.as_ref().unwrap();
+
+    let vertex = buffer::BufferFactory::create(
+        state.backend.as_ref(),
+        buffer::BufferDef {
+            usage: buffer::BufferUsage::Vertex,
+            data: buffer::BufferData::Slice(&vertices),
+        },
+    );
+
+    let index = buffer::BufferFactory::create(
+        state.backend.as_ref(),
+        buffer::BufferDef {
+            usage: buffer::BufferUsage::Index,
+            data: buffer::BufferData::Slice(&indices),
+        },
+    );
+
+    context.command_list.set_viewports([vk::Viewport {
+        offset: [0.0, 0.0],
+        extent: [w, h],
+        ..Default::default()
+    }]);
+
+    context.command_list.bind_pipeline(&pipeline);
+    context.command_list.bind_vertex_buffer(&vertex);
+    context.command_list.bind_index_buffer(&index);
+    context
+        .command_list
+        .bind_descriptors(&pipeline, [cached.descriptor.clone()]);
+    context.command_list.draw(index.len(), 1);
+}
+
+/// INTERNAL: builds vertices and indices of glyph quads of texts in clip space, `extent` is extent of target
+/// in pixels; line feed moves next glyphs to new line, characters without glyph are skipped as spaces
+fn build_text_vertices<'a, I>(texts: I, extent: Vec2) -> (Vec<assets::types::TextVertex>, Vec<u32>)
+where
+    I: IntoIterator<Item = &'a scene::TextSceneEntity>,
+{
+    let glyph_size = Vec2::new(
+        assets::font::GLYPH_WIDTH as f32,
+        assets::font::GLYPH_HEIGHT as f32,
+    );
+
+    // NOTE: glyphs are separated by single font pixel
+    let advance = glyph_size + Vec2::ONE;
+
+    let to_clip_space = |position: Vec2| position / extent * 2.0 - Vec2::ONE;
+
+    let mut vertices = Vec::new();
+    let mut indices = Vec::new();
+
+    for text in texts {
+        let color = text.color.extend(text.alpha);
+
+        // NOTE: separator after last glyph of the longest line is not a part of text width
+        let columns = text.text.lines().map(|line| line.chars().count()).max();
+        let width = (columns.unwrap_or_default() as f32 * advance.x - 1.0).max(0.0) * text.scale;
+
+        let origin = match text.anchor {
+            scene::TextAnchor::TopLeft => text.position,
+            scene::TextAnchor::TopRight => {
+                Vec2::new(extent.x - text.position.x - width, text.position.y)
+            }
+        };
+
+        let mut cursor = origin;
+
+        for character in text.text.chars() {
+            if character == '\n' {
+                cursor = Vec2::new(origin.x, cursor.y + advance.y * text.scale);
+
+                continue;
+            }
+
+            if let Some((uv_min, uv_max)) = assets::font::glyph_uv(character) {
+                let min = to_clip_space(cursor);
+                let max = to_clip_space(cursor + glyph_size * text.scale);
+
+                let corners = [
+                    (min, uv_min),
+                    (Vec2::new(max.x, min.y), Vec2::new(uv_max.x, uv_min.y)),
+                    (max, uv_max),
+                    (Vec2::new(min.x, max.y), Vec2::new(uv_min.x, uv_max.y)),
+                ];
+
+                let base = vertices.len() as u32;
+
+                vertices.extend(corners.map(|(position, uv)| assets::types::TextVertex {
+                    position,
+                    uv,
+                    color,
+                }));
+
+                indices.extend([0, 1, 2, 0, 2, 3].map(|offset| base + offset));
+            }
+
+            cursor.x += advance.x * text.scale;
+        }
+    }
+
+    (vertices, indices)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::entities::{Camera, Spacecraft, TransformComponent};
+
+    #[test]
+    fn far_model_is_culled() {
+        const RADIUS: f32 = 1.0;
+
+        let view: scene::ViewSceneEntity = (&Camera::default()).into();
+        let projection_view_matrix = projection_view_matrix(&view, 16.0 / 9.0);
+
+        assert!(is_sphere_in_frustum(
+            projection_view_matrix,
+            Vec3::ZERO,
+            RADIUS
+        ));
+
+        assert!(!is_sphere_in_frustum(
+            projection_view_matrix,
+            Vec3::new(1000.0, 0.0, 0.0),
+            RADIUS
+        ));
+    }
+
+    #[test]
+    fn draw_list_contains_only_models_in_view() {
+        let events: Arc<events::Events> = Default::default();
+        let scene = scene::Scene::new(&events);
+
+        let model = |position: Vec2| {
+            let spacecraft = Spacecraft {
+                transform: TransformComponent {
+                    position,
+                    ..Default::default()
+                },
+                ..Default::default()
+            };
+
+            scene::ModelSceneEntity::from(&spacecraft)
+        };
+
+        scene.dispatch(0, model(Vec2::ZERO));
+        scene.dispatch(1, model(Vec2::new(1000.0, 0.0)));
+
+        let view: scene::ViewSceneEntity = (&Camera::default()).into();
+        let projection_view_matrix = projection_view_matrix(&view, 16.0 / 9.0);
+
+        let instanced_pipelines = Default::default();
+
+        let models = visible_models(scene.iter(), projection_view_matrix, |_| Some(1.0));
+        let (batches, models) =
+            batch_instanced_models(&instanced_pipelines, models, projection_view_matrix);
+
+        assert_eq!(batches.batches.len() + models.len(), 1);
+        assert_eq!(models.first().map(|(entity_id, _)| *entity_id), Some(0));
+    }
+
+    /// Model, which is pooled by its key only
+    struct TestModel(usize);
+
+    impl PooledModel for TestModel {
+        fn pool_key(&self) -> usize {
+            self.0
+        }
+    }
+
+    #[test]
+    fn released_model_is_reused_for_same_pool_key() {
+        let mut cache: ModelCache<TestModel> = ModelCache::new(8);
+
+        cache.models.insert(0, TestModel(1));
+        cache.release(&0);
+
+        assert!(cache.models.is_empty());
+        assert!(cache.acquire(2).is_none());
+        assert!(cache.acquire(1).is_some());
+        assert!(cache.acquire(1).is_none());
+    }
+
+    #[test]
+    fn released_model_is_dropped_when_pool_is_full() {
+        let mut cache: ModelCache<TestModel> = ModelCache::new(1);
+
+        cache.models.insert(0, TestModel(1));
+        cache.models.insert(1, TestModel(1));
+        cache.release(&0);
+        cache.release(&1);
+
+        assert!(cache.acquire(1).is_some());
+        assert!(cache.acquire(1).is_none());
+    }
+
+    /// Returns minimal and maximal positions of text vertices in pixels
+    fn text_bounds(text: scene::TextSceneEntity, extent: Vec2) -> (Vec2, Vec2) {
+        let (vertices, _) = build_text_vertices([&text], extent);
+        let to_pixels = |position: Vec2| (position + Vec2::ONE) / 2.0 * extent;
+
+        let min = vertices
+            .iter()
+            .fold(Vec2::MAX, |min, vertex| min.min(to_pixels(vertex.position)));
+        let max = vertices
+            .iter()
+            .fold(Vec2::MIN, |max, vertex| max.max(to_pixels(vertex.position)));
+
+        (min, max)
+    }
+
+    #[test]
+    fn text_anchored_to_top_left_starts_at_offset() {
+        let extent = Vec2::new(800.0, 600.0);
+
+        let (min, _) = text_bounds(
+            scene::TextSceneEntity {
+                text: "SCORE 10".into(),
+                anchor: scene::TextAnchor::TopLeft,
+                position: Vec2::new(16.0, 16.0),
+                scale: 2.0,
+                color: Vec3::ONE,
+                alpha: 1.0,
+            },
+            extent,
+        );
+
+        assert!(min.abs_diff_eq(Vec2::new(16.0, 16.0), 1e-3));
+    }
+
+    #[test]
+    fn text_anchored_to_top_right_ends_at_offset_from_right_edge() {
+        let extent = Vec2::new(800.0, 600.0);
+
+        let (_, max) = text_bounds(
+            scene::TextSceneEntity {
+                text: "LIVES 3\nX".into(),
+                anchor: scene::TextAnchor::TopRight,
+                position: Vec2::new(16.0, 16.0),
+                scale: 2.0,
+                color: Vec3::ONE,
+                alpha: 1.0,
+            },
+            extent,
+        );
+
+        assert!((max.x - (extent.x - 16.0)).abs() < 1e-3);
+    }
+}
@@ -1,13 +1,19 @@
 use std::{
     collections::BTreeMap,
-    sync::{Arc, Mutex},
+    path::PathBuf,
+    sync::{
+        Arc, Condvar, Mutex,
+        atomic::{AtomicU64, Ordering},
+        mpsc,
+    },
     thread,
+    time::Duration,
 };
 
 use crate::{
-    handle,
+    diagnostics, handle,
     rendering::{
-        backend,
+        backend, buffer,
         commands::{self, CommandListSubmit},
         frame, image, physical_device,
         render_graph::{self, RenderGraph, RenderGraphExecutor},
@@ -16,7 +22,9 @@ use crate::{
 };
 
 mod vk {
-    pub use vulkano::command_buffer::{RenderingAttachmentInfo, RenderingInfo};
+    pub use vulkano::command_buffer::{
+        RenderingAttachmentInfo, RenderingAttachmentResolveInfo, RenderingInfo,
+    };
 }
 
 /// INTERNAL: entry with instance of [render_graph::RenderGraph] and its arguments
@@ -25,11 +33,33 @@ struct RenderGraphEntry {
     args: BTreeMap<String, render_graph::Arg>,
 }
 
+/// INTERNAL: pixels of frame read back for screenshot, which are pending to be saved
+struct Screenshot {
+    path: PathBuf,
+    extent: [f32; 2],
+    pixels: Vec<u8>,
+}
+
+/// Type alias for order of [render_graph::RenderGraph]: graphs with lower order are executed first
+pub type RenderGraphOrder = i32;
+
+/// Order of graphs added with [Renderer::add_graph]
+pub const DEFAULT_RENDER_GRAPH_ORDER: RenderGraphOrder = 0;
+
+/// INTERNAL: key of render graph in renderer, defines execution order: by order, then by name
+type RenderGraphKey = (RenderGraphOrder, String);
+
 /// Renderer
 pub struct Renderer {
     command_list_allocator: commands::CommandListAllocator,
 
-    entries: Mutex<BTreeMap<String, RenderGraphEntry>>,
+    entries: Mutex<BTreeMap<RenderGraphKey, RenderGraphEntry>>,
+    entries_added: Condvar,
+    images: Mutex<BTreeMap<(String, image::SampleCount), image::ImageView>>,
+    frame: AtomicU64,
+    screenshot: Mutex<Option<PathBuf>>,
+    screenshot_sender: Mutex<mpsc::Sender<Screenshot>>,
+    screenshot_receiver: Mutex<mpsc::Receiver<Screenshot>>,
 
     backend: Arc<backend::Backend>,
 }
@@ -37,23 +67,55 @@ pub struct Renderer {
 impl Renderer {
     /// Creates new instance of [Renderer]
     pub fn new(backend: Arc<backend::Backend>) -> Arc<Renderer> {
+        let (screenshot_sender, screenshot_receiver) = mpsc::channel();
+
         let renderer = Renderer {
             command_list_allocator: commands::CommandListAllocatorFactory::create(backend.as_ref()),
             entries: Default::default(),
+            entries_added: Default::default(),
+            images: Default::default(),
+            frame: Default::default(),
+            screenshot: Default::default(),
+            screenshot_sender: Mutex::new(screenshot_sender),
+            screenshot_receiver: Mutex::new(screenshot_receiver),
             backend,
         };
 
         Arc::new(renderer)
     }
 
-    /// Adds [render_graph::RenderGraph]
+    /// Returns count of frames submitted since start
+    pub fn frame(&self) -> u64 {
+        self.frame.load(Ordering::Relaxed)
+    }
+
+    /// Adds [render_graph::RenderGraph] with [DEFAULT_RENDER_GRAPH_ORDER]
     pub fn add_graph<S, I>(&self, name: S, graph: render_graph::RenderGraph, args: I)
     where
         S: Into<String>,
         I: IntoIterator<Item = (&'static str, render_graph::Arg)>,
     {
+        self.add_graph_ordered(DEFAULT_RENDER_GRAPH_ORDER, name, graph, args);
+    }
+
+    /// Adds [render_graph::RenderGraph] with explicit order: all graphs are recorded into single command list per frame
+    /// in ascending order, graphs with same order are executed in alphabetical order of their names (e.g. scene graph
+    /// with lower order is rendered before overlay graph); graph with same name is replaced
+    pub fn add_graph_ordered<S, I>(
+        &self,
+        order: RenderGraphOrder,
+        name: S,
+        graph: render_graph::RenderGraph,
+        args: I,
+    ) where
+        S: Into<String>,
+        I: IntoIterator<Item = (&'static str, render_graph::Arg)>,
+    {
+        let name = name.into();
         let mut render_graphs = self.entries.lock().unwrap();
 
+        render_graphs.retain(|(_, graph_name), _| *graph_name != name);
+
         let entry = RenderGraphEntry {
             graph,
             args: args
@@ -62,7 +124,197 @@ impl Renderer {
                 .collect(),
         };
 
-        render_graphs.insert(name.into(), entry);
+        render_graphs.insert((order, name), entry);
+
+        self.entries_added.notify_all();
+    }
+
+    /// Requests screenshot: next frame is read back after it is rendered and saved into PNG file at `path`;
+    /// request is ignored if frames can not be read back
+    pub fn request_screenshot<P>(&self, path: P)
+    where
+        P: Into<PathBuf>,
+    {
+        if !self.backend.supports_readback() {
+            diagnostics::warning(
+                "screenshot is not taken: surface does not support reading frames back",
+            );
+
+            return;
+        }
+
+        *self.screenshot.lock().unwrap() = Some(path.into());
+    }
+
+    /// Describes structure of all registered [render_graph::RenderGraph] in execution order: their targets, passes
+    /// and arguments
+    pub fn describe_graphs(&self) -> String {
+        let render_graphs = self.entries.lock().unwrap();
+
+        render_graphs
+            .iter()
+            .map(|((order, name), entry)| {
+                format!(
+                    "graph {} (order: {})\n{}args: {:?}\n",
+                    name, order, entry.graph, entry.args
+                )
+            })
+            .collect()
+    }
+
+    /// Sets clear color of color attachments of [render_graph::RenderGraph], which are cleared on load; returns `false`
+    /// if there is no graph with such name. New color is used since next frame
+    pub fn set_clear_color(&self, name: &str, color: [f32; 4]) -> bool {
+        let mut render_graphs = self.entries.lock().unwrap();
+
+        let Some(entry) = render_graphs
+            .iter_mut()
+            .find(|((_, graph_name), _)| graph_name == name)
+            .map(|(_, entry)| entry)
+        else {
+            return false;
+        };
+
+        entry
+            .graph
+            .passes
+            .iter_mut()
+            .flat_map(|pass| pass.color.iter_mut())
+            .filter(|attachment| {
+                matches!(
+                    attachment.load_op,
+                    render_graph::AttachmentLoadOp::Clear(render_graph::ClearValue::Float(_))
+                )
+            })
+            .for_each(|attachment| {
+                attachment.load_op =
+                    render_graph::AttachmentLoadOp::Clear(render_graph::ClearValue::Float(color));
+            });
+
+        true
+    }
+
+    /// INTERNAL: returns view of image allocated by renderer, image is recreated when its extent is outdated
+    fn allocated_image_view(&self, name: &str, definition: image::ImageDef) -> image::ImageView {
+        let mut images = self.images.lock().unwrap();
+
+        let key = (name.to_string(), definition.samples);
+        let outdated = images
+            .get(&key)
+            .is_none_or(|image_view| image_view.extent != definition.extent);
+
+        if outdated {
+            let image = image::ImageFactory::create(self.backend.as_ref(), definition);
+
+            images.insert(key.clone(), image.view());
+        }
+
+        images
+            .get(&key)
+            .cloned()
+            .expect("allocated image is missing")
+    }
+
+    /// INTERNAL: returns view of image to render attachment into and optional view of image to resolve it into
+    fn attachment_views(
+        &self,
+        frame: &frame::Frame,
+        graph: &RenderGraph,
+        attachment: &render_graph::Attachment,
+        samples: image::SampleCount,
+    ) -> (image::ImageView, Option<image::ImageView>) {
+        let extent = frame.extent;
+
+        let target = graph
+            .targets
+            .get(&attachment.target)
+            .expect("pass contains attachment with invalid target");
+
+        match target {
+            render_graph::Target::Swapchain => {
+                let image_view = image::ImageView {
+                    handle: frame.image_view.clone(),
+                    extent,
+                };
+
+                if samples == image::SampleCount::Sample1 {
+                    return (image_view, None);
+                }
+
+                let multisampled_image_view = self.allocated_image_view(
+                    &attachment.target,
+                    image::ImageDef {
+                        usage: image::ImageUsage::COLOR_ATTACHMENT,
+                        extent,
+                        format: image::ImageFormat::Swapchain,
+                        samples,
+                    },
+                );
+
+                (multisampled_image_view, Some(image_view))
+            }
+
+            render_graph::Target::Image { usage, format } => {
+                let depth = usage.contains(image::ImageUsage::DEPTH_ATTACHMENT);
+
+                if samples == image::SampleCount::Sample1 || depth {
+                    let image_view = self.allocated_image_view(
+                        &attachment.target,
+                        image::ImageDef {
+                            usage: *usage,
+                            extent,
+                            format: *format,
+                            samples,
+                        },
+                    );
+
+                    return (image_view, None);
+                }
+
+                let image_view = self.input_view(frame, graph, &attachment.target);
+
+                let multisampled_image_view = self.allocated_image_view(
+                    &attachment.target,
+                    image::ImageDef {
+                        usage: image::ImageUsage::COLOR_ATTACHMENT,
+                        extent,
+                        format: *format,
+                        samples,
+                    },
+                );
+
+                (multisampled_image_view, Some(image_view))
+            }
+        }
+    }
+}
+
+impl Renderer {
+    /// INTERNAL: returns view of single-sampled image of input target
+    fn input_view(
+        &self,
+        frame: &frame::Frame,
+        graph: &RenderGraph,
+        name: &str,
+    ) -> image::ImageView {
+        let target = graph
+            .targets
+            .get(name)
+            .expect("pass contains input with invalid target");
+
+        match target {
+            render_graph::Target::Image { usage, format } => self.allocated_image_view(
+                name,
+                image::ImageDef {
+                    usage: *usage,
+                    extent: frame.extent,
+                    format: *format,
+                    samples: image::SampleCount::Sample1,
+                },
+            ),
+
+            render_graph::Target::Swapchain => panic!("swapchain target can not be pass input"),
+        }
     }
 }
 
@@ -74,55 +326,51 @@ impl render_graph::RenderGraphExecutor for Renderer {
         graph: &RenderGraph,
         args: &BTreeMap<String, render_graph::Arg>,
     ) {
-        let targets: BTreeMap<_, _> = graph
-            .targets
-            .iter()
-            .map(|(target_name, target)| match target {
-                render_graph::Target::Swapchain => frame
-                    .swapchain
-                    .image_views
-                    .get(frame.image_index as usize)
-                    .map(|image_view| image::ImageView {
-                        handle: image_view.clone(),
-                        extent: frame.swapchain.extent,
-                    })
-                    .map(|image_view| (target_name.clone(), image_view))
-                    .expect("invalid image index"),
-            })
-            .collect();
-
         for pass in graph.passes.iter() {
+            let color: Vec<_> = pass
+                .color
+                .iter()
+                .map(|attachment| {
+                    let views = self.attachment_views(frame, graph, attachment, pass.samples);
+
+                    (attachment, views)
+                })
+                .collect();
+
+            let depth = pass.depth.as_ref().map(|attachment| {
+                let views = self.attachment_views(frame, graph, attachment, pass.samples);
+
+                (attachment, views)
+            });
+
             let rendering_info = vk::RenderingInfo {
-                color_attachments: pass
-                    .color
+                color_attachments: color
                     .iter()
-                    .map(|attachment| vk::RenderingAttachmentInfo {
-                        load_op: attachment.load_op.into(),
-                        clear_value: attachment.load_op.into(),
-                        store_op: attachment.store_op.into(),
+                    .map(|(attachment, (image_view, resolve_image_view))| {
+                        vk::RenderingAttachmentInfo {
+                            load_op: attachment.load_op.into(),
+                            clear_value: attachment.load_op.into(),
+                            store_op: attachment.store_op.into(),
+
+                            resolve_info: resolve_image_view.as_ref().map(|image_view| {
+                                vk::RenderingAttachmentResolveInfo::image_view(
+                                    image_view.handle.clone(),
+                                )
+                            }),
 
-                        ..vk::RenderingAttachmentInfo::image_view(
-                            targets
-                                .get(&attachment.target)
-                                .map(|target| target.handle.clone())
-                                .expect("pass contains color attachment with invalid target"),
-                        )
+                            ..vk::RenderingAttachmentInfo::image_view(image_view.handle.clone())
+                        }
                     })
                     .map(|attachment| Some(attachment))
                     .collect(),
 
-                depth_attachment: pass.depth.as_ref().map(|attachment| {
+                depth_attachment: depth.as_ref().map(|(attachment, (image_view, _))| {
                     vk::RenderingAttachmentInfo {
                         load_op: attachment.load_op.into(),
                         clear_value: attachment.load_op.into(),
                         store_op: attachment.store_op.into(),
 
-                        ..vk::RenderingAttachmentInfo::image_view(
-                            targets
-                                .get(&attachment.target)
-                                .map(|target| target.handle.clone())
-                                .expect("pass contains depth attachment with invalid target"),
-                        )
+                        ..vk::RenderingAttachmentInfo::image_view(image_view.handle.clone())
                     }
                 }),
 
@@ -135,23 +383,20 @@ impl render_graph::RenderGraphExecutor for Renderer {
                 command_list,
                 args,
                 attachments: render_graph::Attachments {
-                    color: pass
-                        .color
-                        .iter()
-                        .map(|attachment| {
-                            targets
-                                .get(&attachment.target)
-                                .cloned()
-                                .expect("pass contains color attachment with invalid target")
+                    color: color
+                        .into_iter()
+                        .map(|(_, (image_view, resolve_image_view))| {
+                            resolve_image_view.unwrap_or(image_view)
                         })
                         .collect(),
 
-                    depth: pass.depth.as_ref().map(|attachment| {
-                        targets
-                            .get(&attachment.target)
-                            .cloned()
-                            .expect("pass contains depth attachment with invalid target")
-                    }),
+                    depth: depth.map(|(_, (image_view, _))| image_view),
+
+                    inputs: pass
+                        .inputs
+                        .iter()
+                        .map(|name| self.input_view(frame, graph, name))
+                        .collect(),
                 },
             };
 
@@ -164,33 +409,179 @@ impl render_graph::RenderGraphExecutor for Renderer {
 
 /// INTERNAL: Renderer worker thread function
 fn worker_func(renderer: &Renderer) {
-    let entries = renderer.entries.lock().unwrap();
+    const TIMEOUT: Duration = Duration::from_millis(100);
+    const IDLE_INTERVAL: Duration = Duration::from_millis(10);
+
+    // NOTE: worker sleeps until there is a graph to render, timeout lets it observe cancellation
+    let (entries, _) = renderer
+        .entries_added
+        .wait_timeout_while(renderer.entries.lock().unwrap(), TIMEOUT, |entries| {
+            entries.is_empty()
+        })
+        .unwrap();
 
     if entries.is_empty() {
-        thread::yield_now();
+        return;
+    }
+
+    let Some(mut frame) = frame::FrameFactory::try_acquire(renderer.backend.as_ref()) else {
+        // NOTE: frame is not acquired while window is minimized, worker idles instead of spinning
+        drop(entries);
+        thread::sleep(IDLE_INTERVAL);
 
         return;
+    };
+
+    let mut command_list = renderer.command_list_allocator.new_list(
+        physical_device::QueueFamilyType::Graphics,
+        commands::CommandListUsage::Multiple,
+    );
+
+    for (_, entry) in entries.iter() {
+        renderer.execute(&frame, &mut command_list, &entry.graph, &entry.args);
     }
 
-    if let Some(frame) = frame::FrameFactory::try_acquire(renderer.backend.as_ref()) {
-        let mut command_list = renderer.command_list_allocator.new_list(
-            physical_device::QueueFamilyType::Graphics,
-            commands::CommandListUsage::Multiple,
+    drop(entries);
+
+    let screenshot = renderer.screenshot.lock().unwrap().take().map(|path| {
+        let [w, h] = frame.extent;
+
+        let readback = buffer::BufferFactory::create::<u8>(
+            renderer.backend.as_ref(),
+            buffer::BufferDef {
+                usage: buffer::BufferUsage::Readback,
+                data: buffer::BufferData::EmptySlice(4 * w as usize * h as usize),
+            },
         );
 
-        for (_, entry) in entries.iter() {
-            renderer.execute(&frame, &mut command_list, &entry.graph, &entry.args);
-        }
+        command_list.copy_image_to_buffer(&frame.image(), &readback);
+
+        (path, frame.extent, readback)
+    });
+
+    // NOTE: frame is awaited once to read its image back
+    frame.wait_on_submit |= screenshot.is_some();
+    frame.submit(command_list);
+
+    // NOTE: only pixels are copied out on render thread, they are encoded by screenshots worker
+    if let Some((path, extent, readback)) = screenshot {
+        let screenshot = Screenshot {
+            path,
+            extent,
+            pixels: readback.read().to_vec(),
+        };
+
+        let _ = renderer.screenshot_sender.lock().unwrap().send(screenshot);
+    }
+
+    renderer.frame.fetch_add(1, Ordering::Relaxed);
+}
 
-        frame.submit(command_list);
+/// INTERNAL: Screenshots worker thread function: converts pixels of frame image into RGBA and saves them into
+/// PNG file
+fn screenshots_worker_func(renderer: &Renderer) {
+    const TIMEOUT: Duration = Duration::from_millis(100);
+
+    let received = renderer
+        .screenshot_receiver
+        .lock()
+        .unwrap()
+        .recv_timeout(TIMEOUT);
+
+    let Ok(Screenshot {
+        path,
+        extent: [w, h],
+        mut pixels,
+    }) = received
+    else {
+        return;
+    };
+
+    if !renderer.backend.convert_to_rgba(&mut pixels) {
+        return;
+    }
+
+    match ::image::save_buffer(
+        &path,
+        &pixels,
+        w as u32,
+        h as u32,
+        ::image::ExtendedColorType::Rgba8,
+    ) {
+        Ok(_) => diagnostics::info(format_args!("screenshot is saved into {}", path.display())),
+        Err(error) => diagnostics::warning(format_args!(
+            "failed to save screenshot into {}: {}",
+            path.display(),
+            error
+        )),
     }
 }
 
-/// Spawns renderer worker thread
+/// Spawns renderer worker thread and screenshots worker thread, which saves requested screenshots
 pub fn spawn_worker(workers: &workers::Workers, renderer: Arc<Renderer>) -> handle::Handle {
-    workers.spawn("Renderer", move |token| {
-        while !token.is_cancelled() {
-            worker_func(&renderer);
-        }
-    })
+    let handles = [
+        workers.spawn("Renderer", {
+            let renderer = renderer.clone();
+
+            move |token| {
+                while !token.is_cancelled() {
+                    worker_func(&renderer);
+                    token.tick();
+                }
+            }
+        }),
+        workers.spawn("Screenshots", move |token| {
+            while !token.is_cancelled() {
+                screenshots_worker_func(&renderer);
+            }
+        }),
+    ];
+
+    let drop = move || {
+        drop(handles);
+    };
+
+    drop.into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn headless_frame_is_read_back_with_clear_color() {
+        let Some(backend) = backend::Backend::try_new_headless([4, 4]) else {
+            return;
+        };
+
+        let renderer = Renderer::new(backend.clone());
+
+        renderer.add_graph(
+            "test",
+            render_graph::RenderGraphBuilder::default()
+                .add_target("swapchain", render_graph::Target::Swapchain)
+                .add_pass(|pass_builder| {
+                    pass_builder
+                        .add_color(render_graph::Attachment {
+                            target: "swapchain".into(),
+                            load_op: render_graph::AttachmentLoadOp::Clear(
+                                render_graph::ClearValue::Float([1.0, 0.0, 0.0, 1.0]),
+                            ),
+                            store_op: render_graph::AttachmentStoreOp::Store,
+                        })
+                        .set_operation(render_graph::StatefulOperation::new((), |_, _| {}))
+                })
+                .build(),
+            [],
+        );
+
+        worker_func(&renderer);
+        backend.wait_idle();
+
+        let pixels = backend.read_pixels().expect("pixels should be read back");
+
+        assert_eq!(renderer.frame(), 1);
+        assert_eq!(pixels.len(), 4 * 4 * 4);
+        assert!(pixels.chunks(4).all(|pixel| pixel == [255, 0, 0, 255]));
+    }
 }
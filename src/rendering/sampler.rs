@@ -0,0 +1,42 @@
+use std::sync::Arc;
+
+mod vk {
+    pub use vulkano::image::sampler::{Filter, Sampler};
+}
+
+/// Enumeration of [Sampler] texel filters
+#[derive(Clone, Copy)]
+pub enum SamplerFilter {
+    /// Nearest texel is used
+    Nearest,
+    /// Texels are linearly interpolated
+    Linear,
+}
+
+impl From<SamplerFilter> for vk::Filter {
+    fn from(value: SamplerFilter) -> Self {
+        match value {
+            SamplerFilter::Nearest => Self::Nearest,
+            SamplerFilter::Linear => Self::Linear,
+        }
+    }
+}
+
+/// [Sampler] definition
+pub struct SamplerDef {
+    /// Filter of magnified and minified texels
+    pub filter: SamplerFilter,
+}
+
+/// Image sampler
+#[derive(Clone)]
+pub struct Sampler {
+    /// VK sampler handle
+    pub handle: Arc<vk::Sampler>,
+}
+
+/// Trait of [Sampler] factory
+pub trait SamplerFactory {
+    /// Creates instance of [Sampler] from [SamplerDef]
+    fn create(&self, definition: SamplerDef) -> Sampler;
+}
@@ -45,12 +45,19 @@ impl Swapchain {
     ) -> Swapchain {
         let size = window.inner_size().max(PhysicalSize::new(1, 1));
 
+        // NOTE: swapchain images are copied into buffer to take screenshots, if surface supports it
+        let image_usage = match physical_device.surface_readback {
+            true => vk::ImageUsage::COLOR_ATTACHMENT | vk::ImageUsage::TRANSFER_SRC,
+            false => vk::ImageUsage::COLOR_ATTACHMENT,
+        };
+
         let create_info = vk::SwapchainCreateInfo {
             image_extent: size.into(),
-            image_usage: vk::ImageUsage::COLOR_ATTACHMENT,
+            image_usage,
             image_format: physical_device.surface_format,
             image_color_space: physical_device.surface_color_space,
             min_image_count: physical_device.surface_image_count,
+            present_mode: physical_device.surface_present_mode.into(),
 
             ..Default::default()
         };
@@ -70,6 +77,13 @@ impl Swapchain {
         swapchain
     }
 
+    /// Checks if window of [Swapchain] is minimized: its inner size is zero, there is nothing to render into
+    pub fn is_minimized(&self) -> bool {
+        let size = self.window.inner_size();
+
+        size.width == 0 || size.height == 0
+    }
+
     /// Recreates [Swapchain]
     pub fn recreate(self) -> Swapchain {
         let size = self.window.inner_size().max(PhysicalSize::new(1, 1));
@@ -1,11 +1,13 @@
 use std::{
     collections::BTreeMap,
     marker::PhantomData,
+    mem,
     ptr::NonNull,
     sync::{Arc, RwLock, RwLockReadGuard},
 };
 
-use glam::{Mat4, Vec3};
+use glam::{Mat4, Vec2, Vec3};
+use serde::Deserialize;
 
 use crate::{
     assets, events,
@@ -33,19 +35,25 @@ pub struct ModelSceneEntity {
     pub matrix: Mat4,
     /// Color
     pub color: Vec3,
+    /// Opacity
+    pub alpha: f32,
     /// Mesh asset reference
     pub mesh: assets::AssetRef,
     /// Pipeline asset reference
     pub pipeline: assets::AssetRef,
+    /// Optional texture asset reference
+    pub texture: Option<assets::AssetRef>,
 }
 
 impl From<&entities::Spacecraft> for ModelSceneEntity {
     fn from(value: &entities::Spacecraft) -> Self {
         Self {
             matrix: value.transform.to_model_matrix(),
-            color: Vec3::new(0.1, 0.8, 0.1),
+            color: value.render.color,
+            alpha: value.render.alpha,
             mesh: value.render.mesh.clone(),
             pipeline: value.render.pipeline.clone(),
+            texture: value.render.texture.clone(),
         }
     }
 }
@@ -54,9 +62,11 @@ impl From<&entities::Asteroid> for ModelSceneEntity {
     fn from(value: &entities::Asteroid) -> Self {
         Self {
             matrix: value.transform.to_model_matrix(),
-            color: Vec3::new(0.6, 0.6, 0.6),
+            color: value.render.color,
+            alpha: value.render.alpha,
             mesh: value.render.mesh.clone(),
             pipeline: value.render.pipeline.clone(),
+            texture: value.render.texture.clone(),
         }
     }
 }
@@ -65,13 +75,81 @@ impl From<&entities::Bullet> for ModelSceneEntity {
     fn from(value: &entities::Bullet) -> Self {
         Self {
             matrix: value.transform.to_model_matrix(),
-            color: Vec3::new(1.0, 1.0, 1.0),
+            color: value.render.color,
+            alpha: value.render.alpha,
             mesh: value.render.mesh.clone(),
             pipeline: value.render.pipeline.clone(),
+            texture: value.render.texture.clone(),
         }
     }
 }
 
+impl From<&entities::PowerUp> for ModelSceneEntity {
+    fn from(value: &entities::PowerUp) -> Self {
+        Self {
+            matrix: value.transform.to_model_matrix(),
+            color: value.render.color,
+            alpha: value.render.alpha,
+            mesh: value.render.mesh.clone(),
+            pipeline: value.render.pipeline.clone(),
+            texture: value.render.texture.clone(),
+        }
+    }
+}
+
+impl From<&entities::Particle> for ModelSceneEntity {
+    fn from(value: &entities::Particle) -> Self {
+        Self {
+            matrix: value.transform.to_model_matrix(),
+            color: value.render.color,
+            alpha: value.render.alpha,
+            mesh: value.render.mesh.clone(),
+            pipeline: value.render.pipeline.clone(),
+            texture: value.render.texture.clone(),
+        }
+    }
+}
+
+impl From<&entities::Afterimage> for ModelSceneEntity {
+    fn from(value: &entities::Afterimage) -> Self {
+        Self {
+            matrix: value.transform.to_model_matrix(),
+            color: value.render.color,
+            alpha: value.render.alpha,
+            mesh: value.render.mesh.clone(),
+            pipeline: value.render.pipeline.clone(),
+            texture: value.render.texture.clone(),
+        }
+    }
+}
+
+/// Corner of the screen, which [TextSceneEntity] is positioned relative to
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TextAnchor {
+    /// Top-left corner, default
+    #[default]
+    TopLeft,
+    /// Top-right corner
+    TopRight,
+}
+
+/// Scene entity with text, which is drawn over the scene in screen space, e.g. HUD
+pub struct TextSceneEntity {
+    /// Text, characters without glyph in font are drawn as spaces
+    pub text: String,
+    /// Corner of the screen and same corner of text, which [TextSceneEntity::position] is measured between
+    pub anchor: TextAnchor,
+    /// Offset in pixels from anchor corner of the screen to anchor corner of text, towards the screen center
+    pub position: Vec2,
+    /// Size of font pixel in screen pixels
+    pub scale: f32,
+    /// Color
+    pub color: Vec3,
+    /// Opacity
+    pub alpha: f32,
+}
+
 /// Scene entity
 ///
 /// See content of next structures for specific details:
@@ -116,6 +194,8 @@ impl<'a> From<&'a SceneEntity> for Option<&'a ModelSceneEntity> {
 #[derive(Default)]
 struct Store {
     entities: RwLock<BTreeMap<EntityId, SceneEntity>>,
+    // NOTE: texts are not bound to ECS entities, they are stored by name instead
+    texts: RwLock<BTreeMap<String, TextSceneEntity>>,
 }
 
 /// Read-only lock over [SceneEntity] collection
@@ -179,13 +259,14 @@ impl Scene {
 
         let scene = Scene {
             store: store.clone(),
-            _handler: events.add_handler(move |event| match event {
-                events::Event::EntityDestroyed(entity_id) => {
-                    store.entities.write().unwrap().remove(entity_id);
-                }
-
-                _ => {}
-            }),
+            _handler: events.add_handler_for(
+                mem::discriminant(&events::Event::EntityDestroyed(Default::default())),
+                move |event| {
+                    if let events::Event::EntityDestroyed(entity_id) = event {
+                        store.entities.write().unwrap().remove(entity_id);
+                    }
+                },
+            ),
         };
 
         Arc::new(scene)
@@ -201,6 +282,32 @@ impl Scene {
         entities.insert(entity_id, entity.into());
     }
 
+    /// Removes scene entity
+    pub fn remove(&self, entity_id: EntityId) {
+        let mut entities = self.store.entities.write().unwrap();
+
+        entities.remove(&entity_id);
+    }
+
+    /// Dispatches text scene entity by its name, text with same name is replaced
+    pub fn dispatch_text(&self, name: &str, text: TextSceneEntity) {
+        let mut texts = self.store.texts.write().unwrap();
+
+        texts.insert(name.to_string(), text);
+    }
+
+    /// Removes text scene entity by its name
+    pub fn remove_text(&self, name: &str) {
+        let mut texts = self.store.texts.write().unwrap();
+
+        texts.remove(name);
+    }
+
+    /// Returns read-only lock over stored [TextSceneEntity] by their names
+    pub fn texts(&self) -> RwLockReadGuard<'_, BTreeMap<String, TextSceneEntity>> {
+        self.store.texts.read().unwrap()
+    }
+
     /// Gets locked [SceneEntity] type by its [EntityId]
     pub fn get<'a, E>(&self, entity_id: EntityId) -> SceneEntityReadLock<E>
     where
@@ -233,3 +340,25 @@ impl Scene {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn spacecraft_color_is_passed_to_model() {
+        let spacecraft = |color| {
+            let mut spacecraft = entities::Spacecraft::default();
+            spacecraft.render.color = color;
+
+            spacecraft
+        };
+
+        let red: ModelSceneEntity = (&spacecraft(Vec3::X)).into();
+        let blue: ModelSceneEntity = (&spacecraft(Vec3::Z)).into();
+
+        assert_eq!(red.color, Vec3::X);
+        assert_eq!(blue.color, Vec3::Z);
+        assert_ne!(red.color, blue.color);
+    }
+}
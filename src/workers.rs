@@ -1,21 +1,102 @@
 use std::{
     collections::BTreeMap,
     sync::{
-        Arc, Mutex,
-        atomic::{AtomicBool, Ordering},
+        Arc, Mutex, Weak,
+        atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering},
     },
     thread::{self, JoinHandle},
+    time::{Duration, Instant},
 };
 
-use crate::handle;
+use crate::{diagnostics, handle};
+
+/// Timings of worker updates
+#[derive(Clone, Copy, Debug, Default)]
+pub struct WorkerStats {
+    /// Last measured interval between worker updates in seconds
+    pub last_elapsed: f32,
+    /// Rolling average of interval between worker updates in seconds
+    pub average_elapsed: f32,
+}
+
+impl WorkerStats {
+    /// Returns average count of worker updates per second
+    pub fn rate(&self) -> f32 {
+        if self.average_elapsed > 0.0 {
+            1.0 / self.average_elapsed
+        } else {
+            0.0
+        }
+    }
+}
+
+/// INTERNAL: lock-free metrics of worker updates, `f32` values are stored as bits
+struct Metrics {
+    origin: Instant,
+    last_tick: AtomicU64,
+    last_elapsed: AtomicU32,
+    average_elapsed: AtomicU32,
+}
+
+impl Metrics {
+    /// INTERNAL: records worker update, interval is measured since previous update
+    fn tick(&self) {
+        const SMOOTHING: f32 = 0.05;
+
+        let now = self.origin.elapsed().as_nanos() as u64;
+        let last = self.last_tick.swap(now, Ordering::Relaxed);
+
+        if last == 0 {
+            return;
+        }
+
+        let elapsed = (now - last) as f32 / 1e9;
+        let average = f32::from_bits(self.average_elapsed.load(Ordering::Relaxed));
+        let average = if average == 0.0 {
+            elapsed
+        } else {
+            average + SMOOTHING * (elapsed - average)
+        };
+
+        self.last_elapsed
+            .store(elapsed.to_bits(), Ordering::Relaxed);
+        self.average_elapsed
+            .store(average.to_bits(), Ordering::Relaxed);
+    }
+
+    /// INTERNAL: returns snapshot of metrics
+    fn stats(&self) -> WorkerStats {
+        WorkerStats {
+            last_elapsed: f32::from_bits(self.last_elapsed.load(Ordering::Relaxed)),
+            average_elapsed: f32::from_bits(self.average_elapsed.load(Ordering::Relaxed)),
+        }
+    }
+}
+
+impl Default for Metrics {
+    fn default() -> Self {
+        Metrics {
+            origin: Instant::now(),
+            last_tick: Default::default(),
+            last_elapsed: Default::default(),
+            average_elapsed: Default::default(),
+        }
+    }
+}
 
 /// A cancellation token
 #[derive(Clone, Default)]
 pub struct CancellationToken {
     token: Arc<AtomicBool>,
+    metrics: Arc<Metrics>,
 }
 
 impl CancellationToken {
+    /// Records worker update, used to measure worker update rate, see [Workers::stats_reader]
+    pub fn tick(&self) {
+        self.metrics.tick();
+    }
+
     /// Checks if token is cancelled
     pub fn is_cancelled(&self) -> bool {
         self.token.load(Ordering::Relaxed)
@@ -23,9 +104,27 @@ impl CancellationToken {
 
     /// INTERNAL: cancels token
     fn cancel(&self) {
-        let _ = self
-            .token
-            .compare_exchange(false, true, Ordering::Relaxed, Ordering::Relaxed);
+        self.token.store(true, Ordering::Relaxed);
+    }
+}
+
+/// Configuration of periodic worker
+#[derive(Clone, Copy)]
+pub struct WorkerConfig {
+    /// Target count of worker updates per second
+    pub target_hz: f32,
+}
+
+impl WorkerConfig {
+    /// Returns target interval between worker updates in seconds
+    pub fn update_rate(&self) -> f32 {
+        1.0 / self.target_hz
+    }
+}
+
+impl Default for WorkerConfig {
+    fn default() -> Self {
+        WorkerConfig { target_hz: 120.0 }
     }
 }
 
@@ -63,12 +162,38 @@ impl Worker {
 
 impl Drop for Worker {
     fn drop(&mut self) {
+        const JOIN_TIMEOUT: Duration = Duration::from_secs(5);
+        const POLL_INTERVAL: Duration = Duration::from_millis(1);
+
         self.token.cancel();
 
-        if let Some(handle) = self.handle.take() {
-            if let Err(_) = handle.join() {
-                // TODO: notify
+        let Some(handle) = self.handle.take() else {
+            return;
+        };
+
+        let name = handle.thread().name().unwrap_or("unnamed").to_string();
+        let deadline = Instant::now() + JOIN_TIMEOUT;
+
+        while !handle.is_finished() {
+            if Instant::now() >= deadline {
+                diagnostics::warning(format_args!(
+                    "worker {name} did not stop in {JOIN_TIMEOUT:?}, it is detached"
+                ));
+
+                return;
             }
+
+            thread::sleep(POLL_INTERVAL);
+        }
+
+        if let Err(payload) = handle.join() {
+            let message = payload
+                .downcast_ref::<&str>()
+                .copied()
+                .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+                .unwrap_or("unknown panic payload");
+
+            diagnostics::warning(format_args!("worker {name} panicked: {message}"));
         }
     }
 }
@@ -96,10 +221,123 @@ impl Workers {
 
         let workers = self.workers.clone();
         let drop = move || {
-            let mut workers = workers.lock().unwrap();
-            workers.remove(&name);
+            let worker = workers.lock().unwrap().remove(&name);
+
+            // NOTE: worker is joined outside of lock, so stopping worker still can read stats of workers
+            drop(worker);
         };
 
         drop.into()
     }
+
+    /// Returns reader of timings of workers, which can be moved into worker; reader does not keep workers alive
+    pub fn stats_reader(&self) -> WorkersStatsReader {
+        WorkersStatsReader {
+            workers: Arc::downgrade(&self.workers),
+        }
+    }
+}
+
+/// Reader of timings of workers, see [Workers::stats_reader]
+#[derive(Clone)]
+pub struct WorkersStatsReader {
+    workers: Weak<Mutex<BTreeMap<String, Worker>>>,
+}
+
+impl WorkersStatsReader {
+    /// Returns timings of workers by their names, returns [None] if workers are dropped
+    pub fn stats(&self) -> Option<BTreeMap<String, WorkerStats>> {
+        self.workers
+            .upgrade()
+            .map(|workers| collect_stats(&workers))
+    }
+}
+
+/// INTERNAL: collects timings of workers by their names
+fn collect_stats(workers: &Mutex<BTreeMap<String, Worker>>) -> BTreeMap<String, WorkerStats> {
+    workers
+        .lock()
+        .unwrap()
+        .iter()
+        .map(|(name, worker)| (name.clone(), worker.token.metrics.stats()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn drop_of_looping_worker_returns_promptly() {
+        const MAX_DURATION: Duration = Duration::from_secs(1);
+
+        let workers: Workers = Default::default();
+
+        let handle = workers.spawn("Test", |token| {
+            while !token.is_cancelled() {
+                thread::sleep(Duration::from_millis(1));
+            }
+        });
+
+        let start = Instant::now();
+        drop(handle);
+
+        assert!(start.elapsed() < MAX_DURATION);
+    }
+
+    #[test]
+    fn drop_of_worker_cancels_and_joins_it() {
+        let workers: Workers = Default::default();
+        let finished: Arc<AtomicBool> = Default::default();
+
+        let handle = workers.spawn("Test", {
+            let finished = finished.clone();
+
+            move |token| {
+                while !token.is_cancelled() {
+                    thread::yield_now();
+                }
+
+                finished.store(true, Ordering::Relaxed);
+            }
+        });
+
+        drop(handle);
+
+        assert!(finished.load(Ordering::Relaxed));
+        assert!(workers.stats_reader().stats().unwrap().is_empty());
+    }
+
+    #[test]
+    fn stats_measure_worker_updates() {
+        const UPDATES: usize = 5;
+        const INTERVAL: Duration = Duration::from_millis(2);
+
+        let workers: Workers = Default::default();
+        let (done_tx, done_rx) = std::sync::mpsc::channel();
+
+        let _handle = workers.spawn("Test", move |token| {
+            for _ in 0..UPDATES {
+                token.tick();
+                thread::sleep(INTERVAL);
+            }
+
+            let _ = done_tx.send(());
+        });
+
+        done_rx.recv().unwrap();
+
+        let stats = workers.stats_reader().stats().unwrap()["Test"];
+
+        assert!(stats.last_elapsed >= INTERVAL.as_secs_f32());
+        assert!(stats.average_elapsed >= INTERVAL.as_secs_f32());
+        assert!(stats.rate() > 0.0 && stats.rate() <= 1.0 / INTERVAL.as_secs_f32());
+    }
+
+    #[test]
+    fn stats_of_idle_worker_are_zero() {
+        let stats = WorkerStats::default();
+
+        assert_eq!(stats.rate(), 0.0);
+    }
 }